@@ -7,7 +7,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Generate Rust code from protobuf definitions
     tonic_build::configure()
         .build_server(true)
-        .build_client(false)
+        .build_client(true)
         .out_dir(&out_dir)
         .compile_protos(&["proto/envoy/service/discovery/v3/ads.proto"], &["proto"])?;
 
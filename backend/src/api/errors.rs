@@ -137,6 +137,9 @@ pub enum ApiError {
     
     #[error("Insufficient permissions")]
     Forbidden,
+
+    #[error("Control plane is in read-only mode")]
+    ReadOnly,
 }
 
 impl ApiError {
@@ -175,6 +178,7 @@ impl ApiError {
             ApiError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
             ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::ReadOnly => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -188,6 +192,7 @@ impl ApiError {
             ApiError::Internal { .. } => "internal",
             ApiError::Unauthorized => "unauthorized",
             ApiError::Forbidden => "forbidden",
+            ApiError::ReadOnly => "read_only",
         };
 
         tracing::error!(
@@ -209,6 +214,10 @@ impl IntoResponse for ApiError {
             ApiError::Internal { message } => (StatusCode::INTERNAL_SERVER_ERROR, message),
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Authentication required".to_string()),
             ApiError::Forbidden => (StatusCode::FORBIDDEN, "Insufficient permissions".to_string()),
+            ApiError::ReadOnly => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Control plane is in read-only mode; changes are rejected until it is turned off".to_string(),
+            ),
         };
 
         let body = Json(json!({
@@ -253,11 +262,39 @@ impl From<ValidationErrors> for ApiError {
                         "range" => format!("{} value is out of range", field),
                         "invalid_route_name" => format!("{} contains invalid characters (only alphanumeric, underscore, hyphen allowed)", field),
                         "invalid_cluster_name" => format!("{} contains invalid characters (only alphanumeric, underscore, period, hyphen allowed)", field),
+                        "invalid_label" => format!("{} keys and values may only contain alphanumerics, '.', '_', '-' (and '/' in keys), max 63 chars", field),
+                        "too_many_labels" => format!("{} contains too many labels (max 20)", field),
+                        "invalid_http_protocol" => format!("{} must be 'auto', 'http1' or 'http2'", field),
+                        "invalid_headers_with_underscores_action" => format!("{} must be one of ALLOW, REJECT_REQUEST, DROP_HEADER", field),
+                        "invalid_weighted_clusters" => format!("{} must list clusters with valid names and positive weights", field),
+                        "invalid_runtime_key_prefix" => format!("{} runtime_key_prefix must be dot-separated alphanumeric segments", field),
+                        "invalid_routing_priority" => format!("{} must be 'default' or 'high'", field),
+                        "invalid_cluster_metadata" => format!("{} must map namespaces of 1-100 characters to JSON objects", field),
+                        "invalid_alpn_protocols" => format!("{} must be a non-empty list of ALPN protocol ids such as h2 or http/1.1", field),
+                        "invalid_dns_resolvers" => format!("{} must list 1-10 resolver addresses as IP:port, e.g. 10.0.0.2:53 or [fd00::2]:53", field),
+                        "invalid_retry_budget" => format!("{} budget_percent must be greater than 0 and at most 100", field),
+                        "invalid_stat_name" => format!("{} must be dot-separated segments of alphanumeric, underscore or hyphen characters", field),
+                        "invalid_health_check" => format!("{} needs a path starting with '/', an interval of 1-300 seconds, a timeout no longer than the interval and thresholds of 1-10", field),
+                        "invalid_health_check_delay" => format!("{} initial_health_check_delay_seconds must be at most 300", field),
+                        "invalid_content_type_match" => format!("{} needs header 'accept' or 'content-type' and a media type like 'application/json'", field),
+                        "invalid_scheme" => format!("{} must be 'http' or 'https'", field),
+                        "invalid_header_to_remove" => format!("{} must contain valid HTTP header names other than pseudo-headers and host", field),
+                        "invalid_response_header" => format!("{} must contain valid HTTP header names and values", field),
+                        "invalid_hostname" => format!("{} must be a valid DNS hostname", field),
                         "invalid_host" => format!("{} contains invalid characters", field),
+                        "invalid_ip_address" => format!("{} must be a valid IPv4 or IPv6 address", field),
+                        "invalid_disabled_filters" => format!("{} must list distinct HTTP filter names", field),
+                        "invalid_additional_addresses" => format!("{} must list 1-4 distinct IPv4 or IPv6 addresses", field),
                         "invalid_path_format" => format!("{} must start with / and contain only safe URL characters", field),
                         "path_traversal_detected" => format!("{} contains path traversal attempt (.. or //)", field),
                         "invalid_http_method" => format!("{} contains invalid HTTP method", field),
                         "invalid_lb_policy" => format!("{} contains invalid load balancing policy", field),
+                        "invalid_regex_rewrite" => format!("{} must contain a valid regex pattern (max 200 chars)", field),
+                        "invalid_retry_policy" => format!("{} must set known retry_on conditions and use status codes between 100 and 599", field),
+                        "invalid_grpc_retry_on" => format!("{} grpc_retry_on may only contain cancelled, deadline-exceeded, internal, resource-exhausted, unavailable", field),
+                        "invalid_retry_back_off" => format!("{} backoff intervals must be positive with base_interval_ms <= max_interval_ms", field),
+                        "invalid_locality_weights" => format!("{} must map non-empty locality names to positive weights", field),
+                        "invalid_health_status" => format!("{} must be one of HEALTHY, DEGRADED, UNHEALTHY", field),
                         "empty_http_methods" => format!("{} cannot be empty", field),
                         "too_many_http_methods" => format!("{} contains too many methods (max 10)", field),
                         _ => format!("{} validation failed: {}", field, error.code),
@@ -0,0 +1,91 @@
+use axum::{
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::api::errors::ApiError;
+use crate::api::routes::AppState;
+
+/// JSON body extractor that honours `server.reject_unknown_fields`
+///
+/// With the flag off this behaves exactly like `Json<T>`. With it on, a field that `T` (or
+/// one of its nested types) doesn't declare is answered with 400 naming its path, such as
+/// `endpoints[0].wieght`, instead of being ignored.
+pub struct ApiJson<T>(pub T);
+
+impl<T> FromRequest<AppState> for ApiJson<T>
+where
+    T: DeserializeOwned + Serialize + Send,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        if !state.config.current().control_plane.server.reject_unknown_fields {
+            let Json(payload) = Json::<T>::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+            return Ok(Self(payload));
+        }
+
+        let Json(body) = Json::<Value>::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+        let payload: T = serde_json::from_value(body.clone()).map_err(|e| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Failed to deserialize the JSON body into the target type: {e}"),
+            )
+                .into_response()
+        })?;
+
+        if let Some(field) = first_unknown_field(&body, &serde_json::to_value(&payload).unwrap_or(Value::Null), "") {
+            return Err(ApiError::validation(format!("Unknown field '{field}' in request body")).into_response());
+        }
+
+        Ok(Self(payload))
+    }
+}
+
+/// Path of the first key in `body` that is missing from the re-serialized payload
+///
+/// Walks nested objects and arrays; free-form values such as filter configs round-trip
+/// unchanged, so only fields of typed structs can be reported.
+fn first_unknown_field(body: &Value, known: &Value, path: &str) -> Option<String> {
+    match (body, known) {
+        (Value::Object(body), Value::Object(known)) => body.iter().find_map(|(key, value)| {
+            let field_path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+            match known.get(key) {
+                Some(known_value) => first_unknown_field(value, known_value, &field_path),
+                None => Some(field_path),
+            }
+        }),
+        (Value::Array(body), Value::Array(known)) => body
+            .iter()
+            .zip(known)
+            .enumerate()
+            .find_map(|(i, (value, known_value))| first_unknown_field(value, known_value, &format!("{path}[{i}]"))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_first_unknown_field_reports_nested_path() {
+        let known = json!({
+            "endpoints": [{"host": "10.0.0.1", "weight": null}],
+            "health_check": {"interval_seconds": 5}
+        });
+
+        let body = json!({"endpoints": [{"host": "10.0.0.1", "wieght": 3}], "health_check": {"interval_seconds": 5}});
+        assert_eq!(first_unknown_field(&body, &known, "").as_deref(), Some("endpoints[0].wieght"));
+
+        let body = json!({"endpoints": [{"host": "10.0.0.1"}], "health_check": {"intervl": 5}});
+        assert_eq!(first_unknown_field(&body, &known, "").as_deref(), Some("health_check.intervl"));
+
+        let body = json!({"endpoints": [{"host": "10.0.0.1", "weight": 3}]});
+        assert_eq!(first_unknown_field(&body, &known, ""), None);
+    }
+}
@@ -1,17 +1,31 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::sse::{Event, KeepAlive, Sse},
     response::Json,
 };
+use base64::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::sync::atomic::Ordering;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::{wrappers::ReceiverStream, Stream};
 use validator::Validate;
 
 use crate::api::errors::ApiError;
+use crate::api::extract::ApiJson;
 use crate::api::routes::AppState;
 use crate::envoy::ConfigGenerator;
-use crate::storage::{Cluster, Endpoint, Route, LoadBalancingPolicy, HttpFilter, RouteFilters};
+use crate::xds::conversion::routes::route_config_name_for;
+use crate::xds::conversion::{conversion_status, explain_route_match, find_shadowed_routes, get_resources_by_type, ResourceStatus, NODE_ID_LABEL, RouteMatchExplanation, ShadowedRoute};
+use crate::xds::XdsClientInfo;
+use crate::xds::filters::request_validation::{simulate_request, SimulatedRequest, SimulationResult};
+use crate::xds::filters::{render_typed_config, FilterStrategyRegistry};
+use crate::storage::{Cluster, Endpoint, StorageError, Route, LoadBalancingPolicy, HttpFilter, RouteFilters, RegexRewrite, RetryPolicy, ChangeAction, ChangelogEntry, ConfigDiff, ConfigSnapshot, diff_snapshots, apply_patch, EndpointGauge, PatchOperation};
 use crate::validation::{
     ValidatedCreateRouteRequest, ValidatedUpdateRouteRequest,
-    ValidatedCreateClusterRequest, ValidatedUpdateClusterRequest,
+    ValidatedCreateClusterRequest, ValidatedUpdateClusterRequest, ValidatedCreateEndpointRequest,
 };
 
 
@@ -22,6 +36,23 @@ pub struct CreateRouteRequest {
     pub cluster_name: String,
     pub prefix_rewrite: Option<String>,
     pub http_methods: Option<Vec<String>>, // GET, POST, PUT, DELETE, etc.
+    pub regex_rewrite: Option<RegexRewrite>,
+    pub idle_timeout_seconds: Option<u64>,
+    pub maintenance_mode: Option<bool>,
+    pub maintenance_body: Option<String>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub timeout_seconds: Option<u64>,
+    pub labels: Option<HashMap<String, String>>,
+    pub response_headers_to_add: Option<HashMap<String, String>>,
+    pub scheme_match: Option<String>,
+    pub weighted_clusters: Option<crate::storage::WeightedClusters>,
+    pub routing_priority: Option<String>,
+    pub description: Option<String>,
+    pub content_type_match: Option<crate::storage::ContentTypeMatch>,
+    pub listener: Option<String>,
+    pub case_sensitive: Option<bool>,
+    pub request_headers_to_remove: Option<Vec<String>>,
+    pub disabled_filters: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,6 +61,23 @@ pub struct UpdateRouteRequest {
     pub cluster_name: String,
     pub prefix_rewrite: Option<String>,
     pub http_methods: Option<Vec<String>>, // GET, POST, PUT, DELETE, etc.
+    pub regex_rewrite: Option<RegexRewrite>,
+    pub idle_timeout_seconds: Option<u64>,
+    pub maintenance_mode: Option<bool>,
+    pub maintenance_body: Option<String>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub timeout_seconds: Option<u64>,
+    pub labels: Option<HashMap<String, String>>,
+    pub response_headers_to_add: Option<HashMap<String, String>>,
+    pub scheme_match: Option<String>,
+    pub weighted_clusters: Option<crate::storage::WeightedClusters>,
+    pub routing_priority: Option<String>,
+    pub description: Option<String>,
+    pub content_type_match: Option<crate::storage::ContentTypeMatch>,
+    pub listener: Option<String>,
+    pub case_sensitive: Option<bool>,
+    pub request_headers_to_remove: Option<Vec<String>>,
+    pub disabled_filters: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,18 +85,60 @@ pub struct CreateClusterRequest {
     pub name: String,
     pub endpoints: Vec<CreateEndpointRequest>,
     pub lb_policy: Option<String>, // Optional: will use config default if None
+    pub choice_count: Option<u32>,
+    pub locality_weighted_lb: Option<bool>,
+    pub locality_weights: Option<HashMap<String, u32>>,
+    pub labels: Option<HashMap<String, String>>,
+    pub per_connection_buffer_limit_bytes: Option<u32>,
+    pub http_protocol: Option<String>,
+    pub ignore_health_on_host_removal: Option<bool>,
+    pub wait_for_warm_on_init: Option<bool>,
+    pub respect_dns_ttl: Option<bool>,
+    pub health_check: Option<crate::storage::HealthCheck>,
+    pub alt_stat_name: Option<String>,
+    pub source_address: Option<String>,
+    pub retry_budget: Option<crate::storage::RetryBudget>,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub alpn_protocols: Option<Vec<String>>,
+    pub dns_resolvers: Option<Vec<String>>,
+    pub headers_with_underscores_action: Option<String>,
+    pub dns_failure_refresh_rate_seconds: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateClusterRequest {
     pub endpoints: Vec<CreateEndpointRequest>,
     pub lb_policy: Option<String>, // Optional: will use config default if None
+    pub choice_count: Option<u32>,
+    pub locality_weighted_lb: Option<bool>,
+    pub locality_weights: Option<HashMap<String, u32>>,
+    pub labels: Option<HashMap<String, String>>,
+    pub per_connection_buffer_limit_bytes: Option<u32>,
+    pub http_protocol: Option<String>,
+    pub ignore_health_on_host_removal: Option<bool>,
+    pub wait_for_warm_on_init: Option<bool>,
+    pub respect_dns_ttl: Option<bool>,
+    pub health_check: Option<crate::storage::HealthCheck>,
+    pub alt_stat_name: Option<String>,
+    pub source_address: Option<String>,
+    pub retry_budget: Option<crate::storage::RetryBudget>,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub alpn_protocols: Option<Vec<String>>,
+    pub dns_resolvers: Option<Vec<String>>,
+    pub headers_with_underscores_action: Option<String>,
+    pub dns_failure_refresh_rate_seconds: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateEndpointRequest {
     pub host: String,
     pub port: u16,
+    pub health_status: Option<String>, // HEALTHY, DEGRADED, UNHEALTHY
+    pub tls: Option<bool>,
+    pub locality: Option<String>,
+    pub hostname: Option<String>,
+    pub weight: Option<u32>,
+    pub additional_addresses: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -80,7 +170,7 @@ impl<T> ApiResponse<T> {
 // Route handlers
 pub async fn create_route(
     State(app_state): State<AppState>,
-    Json(payload): Json<ValidatedCreateRouteRequest>,
+    ApiJson(payload): ApiJson<ValidatedCreateRouteRequest>,
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Validate the input
     payload.validate()?;
@@ -96,13 +186,33 @@ pub async fn create_route(
         )));
     }
 
-    let route = Route::with_methods(
-        payload.name.clone(),
-        payload.path, 
-        payload.cluster_name, 
-        payload.prefix_rewrite,
-        payload.http_methods
-    );
+    let route = Route {
+        regex_rewrite: payload.regex_rewrite,
+        idle_timeout_seconds: payload.idle_timeout_seconds,
+        maintenance_mode: payload.maintenance_mode.unwrap_or(false),
+        maintenance_body: payload.maintenance_body,
+        retry_policy: payload.retry_policy,
+        timeout_seconds: payload.timeout_seconds,
+        labels: payload.labels,
+        response_headers_to_add: payload.response_headers_to_add,
+        scheme_match: payload.scheme_match,
+        weighted_clusters: payload.weighted_clusters,
+        routing_priority: payload.routing_priority,
+        description: payload.description,
+        content_type_match: payload.content_type_match,
+        listener: payload.listener,
+        case_sensitive: payload.case_sensitive,
+        request_headers_to_remove: payload.request_headers_to_remove,
+        disabled_filters: payload.disabled_filters,
+        ..Route::with_methods(
+            payload.name.clone(),
+            payload.path, 
+            payload.cluster_name, 
+            payload.prefix_rewrite,
+            payload.http_methods
+        )
+    };
+    check_disabled_filters(&app_state, &route)?;
     let name = app_state.store.add_route(route)?;
 
     // Increment version to notify Envoy of the change
@@ -114,22 +224,15 @@ pub async fn create_route(
 pub async fn update_route(
     State(app_state): State<AppState>,
     Path(name): Path<String>,
-    Json(payload): Json<ValidatedUpdateRouteRequest>,
+    ApiJson(payload): ApiJson<ValidatedUpdateRouteRequest>,
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Validate the input
     payload.validate()?;
     
     // Convert to internal type
     let payload: UpdateRouteRequest = payload.into();
-    
-    // Create updated route with the same ID
-    let updated_route = Route {
-        name: name.clone(),
-        path: payload.path,
-        cluster_name: payload.cluster_name,
-        prefix_rewrite: payload.prefix_rewrite,
-        http_methods: payload.http_methods,
-    };
+    let updated_route = route_from_update(name.clone(), payload);
+    check_disabled_filters(&app_state, &updated_route)?;
 
     // update_route will return StorageError if route doesn't exist
     app_state.store.update_route(&name, updated_route)?;
@@ -139,6 +242,76 @@ pub async fn update_route(
     Ok(Json(ApiResponse::success(name, "Route updated successfully")))
 }
 
+/// Content type for RFC 6902 JSON Patch documents
+const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// Apply an RFC 6902 JSON Patch to a stored route, then re-validate the result
+pub async fn patch_route(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    Json(operations): Json<Vec<PatchOperation>>,
+) -> Result<Json<ApiResponse<Route>>, ApiError> {
+    // Compare the media type only; parameters such as `charset=utf-8` are allowed
+    let media_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(str::trim);
+    if !media_type.is_some_and(|media_type| media_type.eq_ignore_ascii_case(JSON_PATCH_CONTENT_TYPE)) {
+        return Err(ApiError::validation(format!("Route patches must use Content-Type: {}", JSON_PATCH_CONTENT_TYPE)));
+    }
+
+    let current = app_state.store.get_route(&name)?;
+    let current = serde_json::to_value(&*current)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize route '{}': {}", name, e)))?;
+    let patched = apply_patch(&current, &operations)
+        .map_err(|e| ApiError::validation(format!("Failed to apply patch to route '{}': {}", name, e)))?;
+
+    if patched.get("name") != current.get("name") {
+        return Err(ApiError::validation("Route name cannot be changed by a patch"));
+    }
+
+    // Run the patched route through the same validation as a full update
+    let payload: ValidatedUpdateRouteRequest = serde_json::from_value(patched)
+        .map_err(|e| ApiError::validation(format!("Patched route is invalid: {}", e)))?;
+    payload.validate()?;
+    let updated_route = route_from_update(name.clone(), payload.into());
+    check_disabled_filters(&app_state, &updated_route)?;
+
+    app_state.store.update_route(&name, updated_route.clone())?;
+    app_state.xds_server.increment_version();
+    Ok(Json(ApiResponse::success(updated_route, "Route patched successfully")))
+}
+
+/// Build the stored route for an update, keeping the route's name
+fn route_from_update(name: String, payload: UpdateRouteRequest) -> Route {
+    Route {
+        name,
+        path: payload.path,
+        cluster_name: payload.cluster_name,
+        prefix_rewrite: payload.prefix_rewrite,
+        http_methods: payload.http_methods,
+        regex_rewrite: payload.regex_rewrite,
+        idle_timeout_seconds: payload.idle_timeout_seconds,
+        maintenance_mode: payload.maintenance_mode.unwrap_or(false),
+        maintenance_body: payload.maintenance_body,
+        retry_policy: payload.retry_policy,
+        timeout_seconds: payload.timeout_seconds,
+        labels: payload.labels,
+        response_headers_to_add: payload.response_headers_to_add,
+        scheme_match: payload.scheme_match,
+        weighted_clusters: payload.weighted_clusters,
+        routing_priority: payload.routing_priority,
+        description: payload.description,
+        content_type_match: payload.content_type_match,
+        listener: payload.listener,
+        case_sensitive: payload.case_sensitive,
+        request_headers_to_remove: payload.request_headers_to_remove,
+        disabled_filters: payload.disabled_filters,
+    }
+}
+
 pub async fn get_route(
     State(app_state): State<AppState>,
     Path(name): Path<String>,
@@ -147,10 +320,41 @@ pub async fn get_route(
     Ok(Json(ApiResponse::success((*route).clone(), "Route found")))
 }
 
-pub async fn list_routes(State(app_state): State<AppState>) -> Json<ApiResponse<Vec<Route>>> {
+/// Prefix marking a list query parameter as a label selector, e.g. `?label.team=payments`
+const LABEL_SELECTOR_PREFIX: &str = "label.";
+
+/// Extract `label.<key>=<value>` pairs from list query parameters
+fn label_selector(params: &HashMap<String, String>) -> HashMap<&str, &str> {
+    params
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(LABEL_SELECTOR_PREFIX)
+                .map(|label| (label, value.as_str()))
+        })
+        .collect()
+}
+
+/// A resource matches when it carries every selected label with the same value
+fn matches_labels(labels: Option<&HashMap<String, String>>, selector: &HashMap<&str, &str>) -> bool {
+    selector.iter().all(|(key, value)| {
+        labels
+            .and_then(|labels| labels.get(*key))
+            .is_some_and(|actual| actual == value)
+    })
+}
+
+pub async fn list_routes(
+    State(app_state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<ApiResponse<Vec<Route>>> {
+    let selector = label_selector(&params);
     let routes = app_state.store.list_routes();
     Json(ApiResponse::success(
-        routes.iter().map(|r| (**r).clone()).collect(),
+        routes
+            .iter()
+            .filter(|r| matches_labels(r.labels.as_ref(), &selector))
+            .map(|r| (**r).clone())
+            .collect(),
         "Routes retrieved successfully",
     ))
 }
@@ -166,10 +370,210 @@ pub async fn delete_route(
     Ok(Json(ApiResponse::success((), "Route deleted successfully")))
 }
 
+/// Reject routes that disable HTTP filters which don't exist
+fn check_disabled_filters(app_state: &AppState, route: &Route) -> Result<(), ApiError> {
+    let unknown: Vec<&str> = route
+        .disabled_filters
+        .iter()
+        .flatten()
+        .filter(|name| app_state.store.get_http_filter(name).is_err())
+        .map(String::as_str)
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(ApiError::validation(format!(
+        "Route '{}' disables unknown HTTP filters: {}",
+        route.name,
+        unknown.join(", ")
+    )))
+}
+
+/// Reject load balancing policies missing from `load_balancing.available_policies`
+fn check_lb_policy_allowed(app_state: &AppState, policy: &str) -> Result<(), ApiError> {
+    let config = app_state.config.current();
+    let allowed = &config.control_plane.load_balancing.available_policies;
+    if allowed.iter().any(|p| p == policy) {
+        return Ok(());
+    }
+    Err(ApiError::validation(format!(
+        "Load balancing policy '{}' is not allowed; allowed policies: {}",
+        policy,
+        allowed.join(", ")
+    )))
+}
+
+/// Reject per-connection buffer limits above the configured ceiling
+fn check_buffer_limit(app_state: &AppState, cluster: &Cluster) -> Result<(), ApiError> {
+    let ceiling = app_state.config.current().envoy_generation.cluster.max_buffer_limit_bytes;
+    match cluster.per_connection_buffer_limit_bytes {
+        Some(limit) if limit > ceiling => Err(ApiError::validation(format!(
+            "per_connection_buffer_limit_bytes {} exceeds the configured maximum of {} bytes",
+            limit, ceiling
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject DNS-only options when clusters are not discovered through DNS
+fn check_dns_options(app_state: &AppState, cluster: &Cluster) -> Result<(), ApiError> {
+    let config = app_state.config.current();
+    let cluster_config = &config.envoy_generation.cluster;
+    if cluster.respect_dns_ttl.is_some() && !cluster_config.is_dns_discovery() {
+        return Err(ApiError::validation(format!(
+            "respect_dns_ttl requires STRICT_DNS or LOGICAL_DNS discovery, but clusters use {}",
+            cluster_config.discovery_type
+        )));
+    }
+    if cluster.dns_resolvers.is_some() && !cluster_config.is_dns_discovery() {
+        return Err(ApiError::validation(format!(
+            "dns_resolvers requires STRICT_DNS or LOGICAL_DNS discovery, but clusters use {}",
+            cluster_config.discovery_type
+        )));
+    }
+    if cluster.dns_failure_refresh_rate_seconds.is_some() && !cluster_config.is_dns_discovery() {
+        return Err(ApiError::validation(format!(
+            "dns_failure_refresh_rate_seconds requires STRICT_DNS or LOGICAL_DNS discovery, but clusters use {}",
+            cluster_config.discovery_type
+        )));
+    }
+    Ok(())
+}
+
+/// Reject clusters where only some endpoints carry a load balancing weight
+///
+/// Envoy's weighted round robin and least request balancers expect weights on all
+/// endpoints or none; a partial set silently treats the rest as weight 1.
+fn check_endpoint_weights(cluster: &Cluster) -> Result<(), ApiError> {
+    let unweighted: Vec<String> = cluster
+        .endpoints
+        .iter()
+        .filter(|e| e.weight.is_none())
+        .map(|e| format!("{}:{}", e.host, e.port))
+        .collect();
+    if unweighted.is_empty() || unweighted.len() == cluster.endpoints.len() {
+        return Ok(());
+    }
+    Err(ApiError::validation(format!(
+        "Endpoint weights must be set on all endpoints of cluster '{}' or none; missing on {}",
+        cluster.name,
+        unweighted.join(", ")
+    )))
+}
+
+/// Reject a `node_id` label that can't be compared under the configured node match mode
+///
+/// Otherwise a regex that doesn't compile would silently withhold the cluster from every node.
+fn check_node_tag(app_state: &AppState, cluster: &Cluster) -> Result<(), ApiError> {
+    let Some(tag) = cluster.labels.as_ref().and_then(|labels| labels.get(NODE_ID_LABEL)) else {
+        return Ok(());
+    };
+    let config = app_state.config.current();
+    crate::config::validate_node_tag(&config.control_plane.server.node_match_mode, tag).map_err(|e| {
+        ApiError::validation(format!(
+            "Label '{}' on cluster '{}' is not a valid node pattern: {}",
+            NODE_ID_LABEL, cluster.name, e
+        ))
+    })
+}
+
+/// Reject choice_count unless the cluster resolves to LEAST_REQUEST, including via the configured default
+fn check_choice_count(app_state: &AppState, cluster: &Cluster) -> Result<(), ApiError> {
+    if cluster.choice_count.is_none() {
+        return Ok(());
+    }
+    let config = app_state.config.current();
+    let lb_policy = cluster.effective_lb_policy(&config.control_plane.load_balancing.default_policy);
+    if lb_policy == LoadBalancingPolicy::LeastRequest {
+        return Ok(());
+    }
+    Err(ApiError::validation(format!(
+        "choice_count on cluster '{}' is only valid with the LEAST_REQUEST load balancing policy",
+        cluster.name
+    )))
+}
+
+/// Optionally resolve endpoint hosts of DNS clusters so typos surface at create time
+async fn check_endpoint_resolution(app_state: &AppState, cluster: &Cluster) -> Result<(), ApiError> {
+    let config = app_state.config.current();
+    let cluster_config = &config.envoy_generation.cluster;
+    let check = &cluster_config.dns_resolution_check;
+
+    if !check.enabled || !cluster_config.is_dns_discovery() {
+        return Ok(());
+    }
+
+    let unresolvable = crate::validation::dns::find_unresolvable_hosts(
+        &cluster.endpoints,
+        std::time::Duration::from_millis(check.timeout_ms),
+    )
+    .await;
+
+    if unresolvable.is_empty() {
+        return Ok(());
+    }
+
+    if check.reject_unresolvable {
+        return Err(ApiError::validation(format!(
+            "Cluster '{}' has unresolvable endpoint hosts: {}",
+            cluster.name,
+            unresolvable.join(", ")
+        )));
+    }
+
+    tracing::warn!(
+        "Cluster '{}' has unresolvable endpoint hosts: {}",
+        cluster.name,
+        unresolvable.join(", ")
+    );
+    Ok(())
+}
+
+/// Lowercase endpoint hostnames and collapse duplicate host:port endpoints
+///
+/// Envoy treats duplicates as separate hosts, which skews load balancing. The
+/// first occurrence is kept unless `cluster.reject_duplicate_endpoints` is set.
+fn normalize_endpoints(app_state: &AppState, cluster: &mut Cluster) -> Result<(), ApiError> {
+    let mut seen = std::collections::HashSet::new();
+    let mut duplicates = Vec::new();
+
+    for endpoint in std::mem::take(&mut cluster.endpoints) {
+        let endpoint = Endpoint {
+            host: endpoint.host.to_ascii_lowercase(),
+            hostname: endpoint.hostname.map(|hostname| hostname.to_ascii_lowercase()),
+            ..endpoint
+        };
+        if seen.insert((endpoint.host.clone(), endpoint.port)) {
+            cluster.endpoints.push(endpoint);
+        } else {
+            duplicates.push(format!("{}:{}", endpoint.host, endpoint.port));
+        }
+    }
+
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+
+    if app_state.config.current().envoy_generation.cluster.reject_duplicate_endpoints {
+        return Err(ApiError::validation(format!(
+            "Cluster '{}' has duplicate endpoints: {}",
+            cluster.name,
+            duplicates.join(", ")
+        )));
+    }
+
+    tracing::warn!(
+        "Cluster '{}' had duplicate endpoints removed: {}",
+        cluster.name,
+        duplicates.join(", ")
+    );
+    Ok(())
+}
+
 // Cluster handlers
 pub async fn create_cluster(
     State(app_state): State<AppState>,
-    Json(payload): Json<ValidatedCreateClusterRequest>,
+    ApiJson(payload): ApiJson<ValidatedCreateClusterRequest>,
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Validate the input
     payload.validate()?;
@@ -181,12 +585,21 @@ pub async fn create_cluster(
     let endpoints: Vec<Endpoint> = payload
         .endpoints
         .into_iter()
-        .map(|e| Endpoint::new(e.host, e.port))
+        .map(|e| Endpoint {
+            health_status: e.health_status,
+            tls: e.tls,
+            locality: e.locality,
+            hostname: e.hostname,
+            weight: e.weight,
+            additional_addresses: e.additional_addresses,
+            ..Endpoint::new(e.host, e.port)
+        })
         .collect();
 
     // Handle load balancing policy - validation already done in validation layer
-    let cluster = match payload.lb_policy {
+    let base_cluster = match payload.lb_policy {
         Some(policy_str) => {
+            check_lb_policy_allowed(&app_state, &policy_str)?;
             let lb_policy = policy_str.parse::<LoadBalancingPolicy>()
                 .map_err(|_| ApiError::validation(format!("Invalid load balancing policy: {}", policy_str)))?;
             Cluster::with_lb_policy(payload.name, endpoints, lb_policy)
@@ -196,6 +609,35 @@ pub async fn create_cluster(
             Cluster::new(payload.name, endpoints)
         }
     };
+    let mut cluster = Cluster {
+        choice_count: payload.choice_count,
+        locality_weighted_lb: payload.locality_weighted_lb.unwrap_or(false),
+        locality_weights: payload.locality_weights,
+        labels: payload.labels,
+        per_connection_buffer_limit_bytes: payload.per_connection_buffer_limit_bytes,
+        http_protocol: payload.http_protocol,
+        ignore_health_on_host_removal: payload.ignore_health_on_host_removal,
+        wait_for_warm_on_init: payload.wait_for_warm_on_init,
+        respect_dns_ttl: payload.respect_dns_ttl,
+        health_check: payload.health_check,
+        alt_stat_name: payload.alt_stat_name,
+        source_address: payload.source_address,
+        retry_budget: payload.retry_budget,
+        metadata: payload.metadata,
+        alpn_protocols: payload.alpn_protocols,
+        dns_resolvers: payload.dns_resolvers,
+        headers_with_underscores_action: payload.headers_with_underscores_action,
+        dns_failure_refresh_rate_seconds: payload.dns_failure_refresh_rate_seconds,
+        ..base_cluster
+    };
+
+    normalize_endpoints(&app_state, &mut cluster)?;
+    check_buffer_limit(&app_state, &cluster)?;
+    check_dns_options(&app_state, &cluster)?;
+    check_endpoint_weights(&cluster)?;
+    check_choice_count(&app_state, &cluster)?;
+    check_node_tag(&app_state, &cluster)?;
+    check_endpoint_resolution(&app_state, &cluster).await?;
 
     let name = app_state.store.add_cluster(cluster)?;
 
@@ -216,10 +658,18 @@ pub async fn get_cluster(
     Ok(Json(ApiResponse::success((*cluster).clone(), "Cluster found")))
 }
 
-pub async fn list_clusters(State(app_state): State<AppState>) -> Json<ApiResponse<Vec<Cluster>>> {
+pub async fn list_clusters(
+    State(app_state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<ApiResponse<Vec<Cluster>>> {
+    let selector = label_selector(&params);
     let clusters = app_state.store.list_clusters();
     Json(ApiResponse::success(
-        clusters.iter().map(|c| (**c).clone()).collect(),
+        clusters
+            .iter()
+            .filter(|c| matches_labels(c.labels.as_ref(), &selector))
+            .map(|c| (**c).clone())
+            .collect(),
         "Clusters retrieved successfully",
     ))
 }
@@ -241,7 +691,7 @@ pub async fn delete_cluster(
 pub async fn update_cluster(
     State(app_state): State<AppState>,
     Path(name): Path<String>,
-    Json(payload): Json<ValidatedUpdateClusterRequest>,
+    ApiJson(payload): ApiJson<ValidatedUpdateClusterRequest>,
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Validate the input
     payload.validate()?;
@@ -256,12 +706,21 @@ pub async fn update_cluster(
     let endpoints: Vec<Endpoint> = payload
         .endpoints
         .into_iter()
-        .map(|e| Endpoint::new(e.host, e.port))
+        .map(|e| Endpoint {
+            health_status: e.health_status,
+            tls: e.tls,
+            locality: e.locality,
+            hostname: e.hostname,
+            weight: e.weight,
+            additional_addresses: e.additional_addresses,
+            ..Endpoint::new(e.host, e.port)
+        })
         .collect();
 
     // Handle load balancing policy - validation already done in validation layer
-    let cluster = match payload.lb_policy {
+    let base_cluster = match payload.lb_policy {
         Some(policy_str) => {
+            check_lb_policy_allowed(&app_state, &policy_str)?;
             let lb_policy = policy_str.parse::<LoadBalancingPolicy>()
                 .map_err(|_| ApiError::validation(format!("Invalid load balancing policy: {}", policy_str)))?;
             Cluster::with_lb_policy(name.clone(), endpoints, lb_policy)
@@ -271,6 +730,34 @@ pub async fn update_cluster(
             Cluster::new(name.clone(), endpoints)
         }
     };
+    let mut cluster = Cluster {
+        choice_count: payload.choice_count,
+        locality_weighted_lb: payload.locality_weighted_lb.unwrap_or(false),
+        locality_weights: payload.locality_weights,
+        labels: payload.labels,
+        per_connection_buffer_limit_bytes: payload.per_connection_buffer_limit_bytes,
+        http_protocol: payload.http_protocol,
+        ignore_health_on_host_removal: payload.ignore_health_on_host_removal,
+        wait_for_warm_on_init: payload.wait_for_warm_on_init,
+        respect_dns_ttl: payload.respect_dns_ttl,
+        health_check: payload.health_check,
+        alt_stat_name: payload.alt_stat_name,
+        source_address: payload.source_address,
+        retry_budget: payload.retry_budget,
+        metadata: payload.metadata,
+        alpn_protocols: payload.alpn_protocols,
+        dns_resolvers: payload.dns_resolvers,
+        headers_with_underscores_action: payload.headers_with_underscores_action,
+        dns_failure_refresh_rate_seconds: payload.dns_failure_refresh_rate_seconds,
+        ..base_cluster
+    };
+
+    normalize_endpoints(&app_state, &mut cluster)?;
+    check_buffer_limit(&app_state, &cluster)?;
+    check_dns_options(&app_state, &cluster)?;
+    check_endpoint_weights(&cluster)?;
+    check_choice_count(&app_state, &cluster)?;
+    check_node_tag(&app_state, &cluster)?;
 
     // Update the cluster using the new update_cluster method
     app_state.store.update_cluster(&name, cluster)?;
@@ -284,6 +771,88 @@ pub async fn update_cluster(
     )))
 }
 
+pub async fn list_cluster_endpoints(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<Vec<Endpoint>>>, ApiError> {
+    let cluster = app_state.store.get_cluster(&name)?;
+    Ok(Json(ApiResponse::success(
+        cluster.endpoints.clone(),
+        "Cluster endpoints retrieved successfully",
+    )))
+}
+
+pub async fn add_cluster_endpoint(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    ApiJson(payload): ApiJson<ValidatedCreateEndpointRequest>,
+) -> Result<Json<ApiResponse<Endpoint>>, ApiError> {
+    payload.validate()?;
+    let payload: CreateEndpointRequest = payload.into();
+
+    let mut cluster = (*app_state.store.get_cluster(&name)?).clone();
+    let endpoint = Endpoint {
+        health_status: payload.health_status,
+        tls: payload.tls,
+        locality: payload.locality,
+        hostname: payload.hostname.map(|hostname| hostname.to_ascii_lowercase()),
+        weight: payload.weight,
+        additional_addresses: payload.additional_addresses,
+        ..Endpoint::new(payload.host.to_ascii_lowercase(), payload.port)
+    };
+
+    if cluster
+        .endpoints
+        .iter()
+        .any(|e| e.host.eq_ignore_ascii_case(&endpoint.host) && e.port == endpoint.port)
+    {
+        return Err(StorageError::ResourceConflict {
+            resource_type: "Endpoint".to_string(),
+            resource_id: format!("{}:{}", endpoint.host, endpoint.port),
+        }
+        .into());
+    }
+
+    cluster.endpoints.push(endpoint.clone());
+    check_endpoint_weights(&cluster)?;
+    check_endpoint_resolution(&app_state, &cluster).await?;
+    app_state.store.update_cluster(&name, cluster)?;
+
+    // Increment version to notify Envoy of the change
+    app_state.xds_server.increment_version();
+
+    Ok(Json(ApiResponse::success(endpoint, "Endpoint added successfully")))
+}
+
+/// Remove a single endpoint; a cluster must keep at least one endpoint
+pub async fn remove_cluster_endpoint(
+    State(app_state): State<AppState>,
+    Path((name, host, port)): Path<(String, String, u16)>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    let mut cluster = (*app_state.store.get_cluster(&name)?).clone();
+
+    let index = cluster
+        .endpoints
+        .iter()
+        .position(|e| e.host.eq_ignore_ascii_case(&host) && e.port == port)
+        .ok_or_else(|| ApiError::not_found(format!("Endpoint '{}:{}' not found in cluster '{}'", host, port, name)))?;
+
+    if cluster.endpoints.len() == 1 {
+        return Err(ApiError::validation(format!(
+            "Cannot remove the last endpoint of cluster '{}'; clusters need at least one endpoint",
+            name
+        )));
+    }
+
+    cluster.endpoints.remove(index);
+    app_state.store.update_cluster(&name, cluster)?;
+
+    // Increment version to notify Envoy of the change
+    app_state.xds_server.increment_version();
+
+    Ok(Json(ApiResponse::success((), "Endpoint removed successfully")))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GenerateConfigRequest {
     pub proxy_name: String,
@@ -295,7 +864,7 @@ pub async fn get_supported_http_methods(
 ) -> Result<Json<ApiResponse<Vec<String>>>, ApiError> {
     // Use shared app config from state (no file I/O!)
     Ok(Json(ApiResponse::success(
-        app_state.config.control_plane.http_methods.supported_methods.clone(),
+        app_state.config.current().control_plane.http_methods.supported_methods.clone(),
         "Supported HTTP methods retrieved successfully",
     )))
 }
@@ -305,7 +874,7 @@ pub async fn generate_bootstrap_config(
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Use shared app config from state (no file I/O!)
     // Generate bootstrap configuration
-    match ConfigGenerator::generate_bootstrap_config(&app_state.config) {
+    match ConfigGenerator::generate_bootstrap_config(&app_state.config.current()) {
         Ok(bootstrap_yaml) => Ok(Json(ApiResponse::success(
             bootstrap_yaml,
             "Bootstrap configuration generated successfully",
@@ -320,15 +889,17 @@ pub async fn generate_envoy_config(
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Use shared app config from state (no file I/O!)
 
+    let config = app_state.config.current();
+
     // Generate Envoy configuration
     let envoy_config =
-        match ConfigGenerator::generate_config(&app_state.store, &app_state.config, payload.proxy_port) {
-            Ok(config) => config,
+        match ConfigGenerator::generate_config(&app_state.store, &config, payload.proxy_port) {
+            Ok(envoy_config) => envoy_config,
             Err(e) => return Err(ApiError::configuration(format!("Failed to load application configuration: {}", e))),
         };
 
     // Write to file
-    let config_dir = &app_state.config.envoy_generation.config_dir;
+    let config_dir = &config.envoy_generation.config_dir;
     let file_path = config_dir.join(format!("{}.yaml", payload.proxy_name));
 
     // Ensure config directory exists
@@ -360,6 +931,8 @@ pub struct CreateHttpFilterRequest {
     pub filter_type: String,
     pub config: serde_json::Value,
     pub enabled: Option<bool>,
+    pub disabled_by_default: Option<bool>,
+    pub path_prefix: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -367,6 +940,8 @@ pub struct UpdateHttpFilterRequest {
     pub filter_type: String,
     pub config: serde_json::Value,
     pub enabled: Option<bool>,
+    pub disabled_by_default: Option<bool>,
+    pub path_prefix: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -388,13 +963,25 @@ pub async fn create_http_filter(
     Json(payload): Json<CreateHttpFilterRequest>,
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Get supported filters from config
-    let supported_filters = &app_state.config.control_plane.http_filters.supported_filters;
-    
+    let config = app_state.config.current();
+    let supported_filters = &config.control_plane.http_filters.supported_filters;
+
+    // Report a duplicate name before complaining about the new filter's config
+    if app_state.store.get_http_filter(&payload.name).is_ok() {
+        return Err(StorageError::ResourceConflict {
+            resource_type: "HttpFilter".to_string(),
+            resource_id: payload.name,
+        }
+        .into());
+    }
+
     let filter = HttpFilter::new(
         payload.name.clone(),
         payload.filter_type,
         payload.config,
-    ).with_enabled(payload.enabled.unwrap_or(true));
+    ).with_enabled(payload.enabled.unwrap_or(true))
+    .with_disabled_by_default(payload.disabled_by_default.unwrap_or(false))
+    .with_path_prefix(payload.path_prefix);
 
     let name = app_state.store.add_http_filter(filter, supported_filters)?;
 
@@ -426,13 +1013,16 @@ pub async fn update_http_filter(
     Json(payload): Json<UpdateHttpFilterRequest>,
 ) -> Result<Json<ApiResponse<String>>, ApiError> {
     // Get supported filters from config
-    let supported_filters = &app_state.config.control_plane.http_filters.supported_filters;
+    let config = app_state.config.current();
+    let supported_filters = &config.control_plane.http_filters.supported_filters;
     
     let updated_filter = HttpFilter::new(
         name.clone(),
         payload.filter_type,
         payload.config,
-    ).with_enabled(payload.enabled.unwrap_or(true));
+    ).with_enabled(payload.enabled.unwrap_or(true))
+    .with_disabled_by_default(payload.disabled_by_default.unwrap_or(false))
+    .with_path_prefix(payload.path_prefix);
 
     app_state.store.update_http_filter(&name, updated_filter, supported_filters)?;
 
@@ -453,6 +1043,89 @@ pub async fn delete_http_filter(
     Ok(Json(ApiResponse::success((), "HTTP filter deleted successfully")))
 }
 
+/// Report which route on the main listener a sample request would match, in RDS order
+pub async fn explain_route(
+    State(app_state): State<AppState>,
+    Json(request): Json<SimulatedRequest>,
+) -> Json<ApiResponse<RouteMatchExplanation>> {
+    let config = app_state.config.current();
+    let main_route_config = route_config_name_for(&config, None);
+    let routes: Vec<Route> = app_state
+        .store
+        .list_routes()
+        .iter()
+        .filter(|route| route_config_name_for(&config, route.listener.as_deref()) == main_route_config)
+        .map(|route| (**route).clone())
+        .collect();
+
+    let explanation = explain_route_match(&routes, &request);
+    let message = if explanation.matched_route.is_some() {
+        "Request would match a route"
+    } else {
+        "Request would not match any route"
+    };
+    Json(ApiResponse::success(explanation, message))
+}
+
+/// Report routes that can never match because an earlier route in the same RouteConfiguration covers them
+pub async fn list_shadowed_routes(State(app_state): State<AppState>) -> Json<ApiResponse<Vec<ShadowedRoute>>> {
+    let config = app_state.config.current();
+
+    // Group in RDS order; routes only shadow each other within one route configuration
+    let mut route_groups: Vec<(Option<String>, Vec<Route>)> = Vec::new();
+    for route in app_state.store.list_routes() {
+        let route_config = route_config_name_for(&config, route.listener.as_deref());
+        match route_groups.iter_mut().find(|(name, _)| *name == route_config) {
+            Some((_, routes)) => routes.push((*route).clone()),
+            None => route_groups.push((route_config, vec![(*route).clone()])),
+        }
+    }
+
+    let shadowed: Vec<ShadowedRoute> = route_groups
+        .iter()
+        .flat_map(|(_, routes)| find_shadowed_routes(routes))
+        .collect();
+    let message = format!("Found {} shadowed routes", shadowed.len());
+    Json(ApiResponse::success(shadowed, &message))
+}
+
+/// Dry-run a sample request against a stored request_validation filter
+pub async fn simulate_http_filter(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<SimulatedRequest>,
+) -> Result<Json<ApiResponse<SimulationResult>>, ApiError> {
+    let filter = app_state.store.get_http_filter(&name)?;
+    if filter.filter_type != "request_validation" {
+        return Err(ApiError::validation(format!(
+            "Simulation is not supported for filter type '{}'",
+            filter.filter_type
+        )));
+    }
+
+    let result = simulate_request(&filter, &request).map_err(|e| ApiError::validation(e.to_string()))?;
+    let message = if result.allowed {
+        "Request would be allowed"
+    } else {
+        "Request would be denied"
+    };
+    Ok(Json(ApiResponse::success(result, message)))
+}
+
+/// Render the Envoy typed config a stored filter converts to, for debugging
+pub async fn get_http_filter_envoy_config(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let filter = app_state.store.get_http_filter(&name)?;
+    let config = app_state.config.current();
+    let config_type = FilterStrategyRegistry::new(&config)
+        .convert_filter(&filter)
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    let rendered = render_typed_config(&config_type).map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(Json(ApiResponse::success(rendered, "Envoy filter config rendered successfully")))
+}
+
 // Route-Filter association handlers
 pub async fn create_route_filters(
     State(app_state): State<AppState>,
@@ -471,6 +1144,32 @@ pub async fn create_route_filters(
     Ok(Json(ApiResponse::success(route_name, "Route filters created successfully")))
 }
 
+/// Create the filter association for the route named in the path (`POST /routes/{name}/filters`)
+pub async fn create_filters_for_route(
+    State(app_state): State<AppState>,
+    Path(route_name): Path<String>,
+    Json(payload): Json<UpdateRouteFiltersRequest>,
+) -> Result<Json<ApiResponse<String>>, ApiError> {
+    if app_state.store.get_route_filters(&route_name).is_some() {
+        return Err(ApiError::validation(format!(
+            "Route filters for '{}' already exist, use PUT to update them",
+            route_name
+        )));
+    }
+
+    let route_filters = RouteFilters::new(
+        route_name,
+        payload.filter_names,
+    ).with_custom_order(payload.custom_order.unwrap_or_default());
+
+    let route_name = app_state.store.add_route_filters(route_filters)?;
+
+    // Increment version to notify Envoy of the change
+    app_state.xds_server.increment_version();
+
+    Ok(Json(ApiResponse::success(route_name, "Route filters created successfully")))
+}
+
 pub async fn get_route_filters(
     State(app_state): State<AppState>,
     Path(route_name): Path<String>,
@@ -491,7 +1190,14 @@ pub async fn update_route_filters(
         payload.filter_names,
     ).with_custom_order(payload.custom_order.unwrap_or_default());
 
-    app_state.store.remove_route_filters(&route_name)?;
+    // PUT only replaces an existing association; add_route_filters validates before overwriting it
+    if app_state.store.get_route_filters(&route_name).is_none() {
+        return Err(StorageError::ResourceNotFound {
+            resource_type: "RouteFilters".to_string(),
+            resource_id: route_name,
+        }
+        .into());
+    }
     app_state.store.add_route_filters(updated_route_filters)?;
 
     // Increment version to notify Envoy of the change
@@ -516,7 +1222,7 @@ pub async fn get_supported_http_filter_types(
     State(app_state): State<AppState>,
 ) -> Json<ApiResponse<Vec<String>>> {
     Json(ApiResponse::success(
-        app_state.config.control_plane.http_filters.supported_filters.clone(),
+        app_state.config.current().control_plane.http_filters.supported_filters.clone(),
         "Supported HTTP filter types retrieved successfully",
     ))
 }
@@ -526,7 +1232,236 @@ pub async fn get_default_http_filter_order(
     State(app_state): State<AppState>,
 ) -> Json<ApiResponse<Vec<String>>> {
     Json(ApiResponse::success(
-        app_state.config.control_plane.http_filters.default_order.clone(),
+        app_state.config.current().control_plane.http_filters.default_order.clone(),
         "Default HTTP filter order retrieved successfully",
     ))
 }
+
+// Get the in-memory changelog of recent store mutations (oldest first)
+pub async fn get_changelog(
+    State(app_state): State<AppState>,
+) -> Json<ApiResponse<Vec<ChangelogEntry>>> {
+    Json(ApiResponse::success(
+        app_state.store.list_changelog(),
+        "Changelog retrieved successfully",
+    ))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigStatusReport {
+    pub failing: usize,
+    pub resources: Vec<ResourceStatus>,
+}
+
+/// Run the startup conversion self-test on demand and report every resource
+pub async fn get_config_status(
+    State(app_state): State<AppState>,
+) -> Json<ApiResponse<ConfigStatusReport>> {
+    let resources = conversion_status(&app_state.store, &app_state.config.current());
+    let failing = resources.iter().filter(|status| !status.ok).count();
+    let message = if failing == 0 {
+        "All resources convert successfully".to_string()
+    } else {
+        format!("{} resource(s) fail conversion", failing)
+    };
+
+    Json(ApiResponse::success(ConfigStatusReport { failing, resources }, &message))
+}
+
+/// Effective configuration after environment overrides, with secrets redacted
+pub async fn get_effective_config(State(app_state): State<AppState>) -> Json<ApiResponse<serde_json::Value>> {
+    Json(ApiResponse::success(app_state.config.current().redacted(), "Effective configuration retrieved"))
+}
+
+/// Dry-run comparison of the submitted desired state against the current store
+pub async fn diff_config(
+    State(app_state): State<AppState>,
+    Json(desired): Json<ConfigSnapshot>,
+) -> Json<ApiResponse<ConfigDiff>> {
+    let diff = diff_snapshots(&app_state.store.snapshot(), &desired);
+    let message = if diff.is_empty() {
+        "No changes detected"
+    } else {
+        "Config diff computed successfully"
+    };
+    Json(ApiResponse::success(diff, message))
+}
+
+/// Payload of a `change` event on the `/events` stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub action: ChangeAction,
+    pub name: String,
+}
+
+impl From<ChangelogEntry> for ChangeEvent {
+    fn from(entry: ChangelogEntry) -> Self {
+        Self {
+            resource_type: entry.resource_type,
+            action: entry.action,
+            name: entry.resource_name,
+        }
+    }
+}
+
+/// Server-Sent Events stream of store mutations for live dashboards
+pub async fn stream_events(
+    State(app_state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let mut changes = app_state.store.subscribe_changes();
+    let (tx, rx) = tokio::sync::mpsc::channel(16);
+
+    tokio::spawn(async move {
+        loop {
+            let entry = match changes.recv().await {
+                Ok(entry) => entry,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("SSE client lagged, skipped {} change events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let event = match Event::default().event("change").json_data(ChangeEvent::from(entry)) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("Failed to serialize change event: {}", e);
+                    continue;
+                }
+            };
+
+            // Client disconnected
+            if tx.send(Ok(event)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Sse::new(ReceiverStream::new(rx)).keep_alive(KeepAlive::default())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForcePushResponse {
+    pub streams_notified: usize,
+    pub version: u64,
+}
+
+// Force an immediate xDS resync to all connected Envoy instances
+pub async fn force_push(
+    State(app_state): State<AppState>,
+) -> Json<ApiResponse<ForcePushResponse>> {
+    let streams_notified = app_state.xds_server.force_push();
+
+    Json(ApiResponse::success(
+        ForcePushResponse {
+            streams_notified,
+            version: app_state.xds_server.current_version(),
+        },
+        "xDS push triggered successfully",
+    ))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct XdsServingStatus {
+    pub paused: bool,
+    pub version: u64,
+}
+
+/// Freeze xDS pushes at the last committed version for maintenance
+pub async fn pause_xds(State(app_state): State<AppState>) -> Json<ApiResponse<XdsServingStatus>> {
+    let version = app_state.xds_server.pause();
+
+    Json(ApiResponse::success(
+        XdsServingStatus { paused: true, version },
+        "xDS serving paused successfully",
+    ))
+}
+
+/// Resume xDS pushes, sending any changes made while paused
+pub async fn resume_xds(State(app_state): State<AppState>) -> Json<ApiResponse<XdsServingStatus>> {
+    let version = app_state.xds_server.resume();
+
+    Json(ApiResponse::success(
+        XdsServingStatus { paused: false, version },
+        "xDS serving resumed successfully",
+    ))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadOnlyStatus {
+    pub read_only: bool,
+}
+
+/// Report whether the control plane is in read-only mode
+pub async fn get_read_only(State(app_state): State<AppState>) -> Json<ApiResponse<ReadOnlyStatus>> {
+    let read_only = app_state.read_only.load(Ordering::Relaxed);
+    Json(ApiResponse::success(ReadOnlyStatus { read_only }, "Read-only status retrieved"))
+}
+
+/// Turn read-only mode on or off; while on, configuration changes get 503 but reads and xDS keep serving
+pub async fn set_read_only(
+    State(app_state): State<AppState>,
+    Json(payload): Json<ReadOnlyStatus>,
+) -> Json<ApiResponse<ReadOnlyStatus>> {
+    app_state.read_only.store(payload.read_only, Ordering::Relaxed);
+
+    let message = if payload.read_only { "Read-only mode enabled" } else { "Read-only mode disabled" };
+    tracing::warn!("{}", message);
+    Json(ApiResponse::success(payload, message))
+}
+
+/// A converted xDS resource exactly as it goes on the wire
+#[derive(Debug, Serialize, Deserialize)]
+pub struct XdsResourceDump {
+    pub type_url: String,
+    pub value: String, // Base64 of the protobuf-encoded resource
+}
+
+/// Base64 dump of the resources ADS would send for `clusters`, `routes` or `listeners`
+pub async fn dump_xds_resources(
+    State(app_state): State<AppState>,
+    Path(resource_type): Path<String>,
+) -> Result<Json<ApiResponse<Vec<XdsResourceDump>>>, ApiError> {
+    let type_url = match resource_type.as_str() {
+        "clusters" => "type.googleapis.com/envoy.config.cluster.v3.Cluster",
+        "routes" => "type.googleapis.com/envoy.config.route.v3.RouteConfiguration",
+        "listeners" => "type.googleapis.com/envoy.config.listener.v3.Listener",
+        other => {
+            return Err(ApiError::validation(format!(
+                "Unknown xDS resource type '{}'; expected clusters, routes or listeners",
+                other
+            )))
+        }
+    };
+
+    let resources = get_resources_by_type(type_url, &app_state.store).map_err(|e| ApiError::internal(e.to_string()))?;
+    let dump = resources
+        .into_iter()
+        .map(|resource| XdsResourceDump {
+            type_url: resource.type_url,
+            value: BASE64_STANDARD.encode(resource.value),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(dump, "xDS resources retrieved successfully")))
+}
+
+/// Endpoint counts per cluster for dashboards; empty when `storage.behavior.enable_metrics` is off
+pub async fn get_endpoint_gauges(
+    State(app_state): State<AppState>,
+) -> Json<ApiResponse<BTreeMap<String, EndpointGauge>>> {
+    Json(ApiResponse::success(
+        app_state.store.endpoint_gauges(),
+        "Endpoint gauges retrieved successfully",
+    ))
+}
+
+/// List Envoy instances currently connected over ADS
+pub async fn list_xds_clients(State(app_state): State<AppState>) -> Json<ApiResponse<Vec<XdsClientInfo>>> {
+    Json(ApiResponse::success(
+        app_state.xds_server.connected_clients(),
+        "Connected xDS clients retrieved successfully",
+    ))
+}
@@ -1,4 +1,5 @@
 pub mod errors;
+pub mod extract;
 pub mod handlers;
 pub mod routes;
 
@@ -1,16 +1,21 @@
 use axum::{
-    middleware,
+    extract::{Request, State},
+    middleware::{self, Next},
+    response::Response,
     routing::{delete, get, post, put},
     Router,
 };
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 use axum::http::{Method, HeaderName};
 
+use super::errors::ApiError;
 use super::handlers;
 use crate::auth::JwtKeys;
 use crate::auth_handlers;
 use crate::auth_middleware::{auth_middleware, optional_auth_middleware};
-use crate::config::AppConfig;
+use crate::config::{AppConfig, SharedConfig};
 use crate::rbac::RbacEnforcer;
 use crate::storage::ConfigStore;
 use crate::xds::SimpleXdsServer;
@@ -21,7 +26,8 @@ pub struct AppState {
     pub xds_server: SimpleXdsServer,
     pub jwt_keys: JwtKeys,
     pub rbac: RbacEnforcer,
-    pub config: std::sync::Arc<AppConfig>,
+    pub config: SharedConfig,
+    pub read_only: Arc<AtomicBool>, // Runtime read-only mode, seeded from `server.read_only`
 }
 
 pub fn create_router(
@@ -29,28 +35,33 @@ pub fn create_router(
     xds_server: SimpleXdsServer,
     jwt_keys: JwtKeys,
     rbac: RbacEnforcer,
-    config: std::sync::Arc<AppConfig>,
+    config: impl Into<SharedConfig>,
 ) -> Router {
+    let config = config.into();
     // Create secure CORS configuration based on application config
-    let cors_layer = create_cors_layer(&config).expect("Failed to create CORS configuration");
+    let cors_layer = create_cors_layer(&config.current()).expect("Failed to create CORS configuration");
+    let read_only = Arc::new(AtomicBool::new(config.current().control_plane.server.read_only));
     let app_state = AppState {
         store,
         xds_server,
         jwt_keys: jwt_keys.clone(),
         rbac: rbac.clone(),
-        config: config.clone(),
+        config,
+        read_only,
     };
 
-    // Protected routes that require full authentication & authorization
-    let protected_routes = Router::new()
+    // Protected routes that change stored configuration; rejected while in read-only mode
+    let mutating_routes = Router::new()
         // Route management (write operations)
         .route("/routes", post(handlers::create_route))
-        .route("/routes/{name}", put(handlers::update_route))
+        .route("/routes/{name}", put(handlers::update_route).patch(handlers::patch_route))
         .route("/routes/{name}", delete(handlers::delete_route))
         // Cluster management (write operations)
         .route("/clusters", post(handlers::create_cluster))
         .route("/clusters/{name}", put(handlers::update_cluster))
         .route("/clusters/{name}", delete(handlers::delete_cluster))
+        .route("/clusters/{name}/endpoints", post(handlers::add_cluster_endpoint))
+        .route("/clusters/{name}/endpoints/{host}/{port}", delete(handlers::remove_cluster_endpoint))
         // HTTP Filter management (write operations)
         .route("/http-filters", post(handlers::create_http_filter))
         .route("/http-filters/{name}", put(handlers::update_http_filter))
@@ -59,9 +70,39 @@ pub fn create_router(
         .route("/route-filters", post(handlers::create_route_filters))
         .route("/route-filters/{route_name}", put(handlers::update_route_filters))
         .route("/route-filters/{route_name}", delete(handlers::delete_route_filters))
+        .route("/routes/{name}/filters", post(handlers::create_filters_for_route))
+        .route("/routes/{name}/filters", put(handlers::update_route_filters))
+        .route("/routes/{name}/filters", delete(handlers::delete_route_filters))
+        // Checked after authentication, so anonymous callers still get 401
+        .layer(middleware::from_fn_with_state(app_state.clone(), read_only_guard))
+        .layer(middleware::from_fn_with_state(
+            (jwt_keys.clone(), rbac.clone()),
+            auth_middleware,
+        ));
+
+    // Protected routes that require full authentication & authorization
+    let protected_routes = Router::new()
+        // Dry runs that don't change stored configuration
+        .route("/routes/explain", post(handlers::explain_route))
+        .route("/http-filters/{name}/simulate", post(handlers::simulate_http_filter))
         // Config generation (sensitive operations)
         .route("/generate-config", post(handlers::generate_envoy_config))
         .route("/generate-bootstrap", get(handlers::generate_bootstrap_config))
+        .route("/config/diff", post(handlers::diff_config))
+        // Admin inspection endpoints
+        .route("/admin/changelog", get(handlers::get_changelog))
+        .route("/admin/push", post(handlers::force_push))
+        .route("/admin/xds/pause", post(handlers::pause_xds))
+        .route("/admin/xds/resume", post(handlers::resume_xds))
+        .route("/admin/read-only", get(handlers::get_read_only).post(handlers::set_read_only))
+        .route("/admin/xds/{type}", get(handlers::dump_xds_resources))
+        .route("/admin/xds-clients", get(handlers::list_xds_clients))
+        .route("/admin/endpoint-gauges", get(handlers::get_endpoint_gauges))
+        .route("/admin/routes/shadowed", get(handlers::list_shadowed_routes))
+        .route("/admin/config-status", get(handlers::get_config_status))
+        .route("/admin/config", get(handlers::get_effective_config))
+        // Live change notifications (Server-Sent Events)
+        .route("/events", get(handlers::stream_events))
         // Apply full authentication + authorization middleware
         .layer(middleware::from_fn_with_state(
             (jwt_keys.clone(), rbac.clone()),
@@ -78,6 +119,7 @@ pub fn create_router(
     // Protected auth routes (require authentication)
     let protected_auth_routes = Router::new()
         .route("/auth/me", get(auth_handlers::get_user_info))
+        .route("/admin/auth/toggle", post(auth_handlers::toggle_authentication))
         // Apply full authentication + authorization middleware
         .layer(middleware::from_fn_with_state(
             (jwt_keys.clone(), rbac.clone()),
@@ -92,11 +134,14 @@ pub fn create_router(
         .route("/routes/{name}", get(handlers::get_route))
         .route("/clusters", get(handlers::list_clusters))
         .route("/clusters/{name}", get(handlers::get_cluster))
+        .route("/clusters/{name}/endpoints", get(handlers::list_cluster_endpoints))
         // HTTP Filter read operations
         .route("/http-filters", get(handlers::list_http_filters))
         .route("/http-filters/{name}", get(handlers::get_http_filter))
+        .route("/http-filters/{name}/envoy", get(handlers::get_http_filter_envoy_config))
         // Route-Filter association read operations
         .route("/route-filters/{route_name}", get(handlers::get_route_filters))
+        .route("/routes/{name}/filters", get(handlers::get_route_filters))
         // System info (public)
         .route("/supported-http-methods", get(handlers::get_supported_http_methods))
         .route("/supported-http-filter-types", get(handlers::get_supported_http_filter_types))
@@ -110,6 +155,7 @@ pub fn create_router(
 
     // Combine all routes
     Router::new()
+        .merge(mutating_routes)
         .merge(protected_routes)
         .merge(protected_auth_routes)
         .merge(auth_routes)
@@ -151,7 +197,7 @@ fn create_cors_layer(config: &AppConfig) -> anyhow::Result<CorsLayer> {
                 Ok(method) => {
                     // Only allow methods that our API endpoints actually use
                     match method {
-                        Method::GET | Method::POST | Method::PUT | 
+                        Method::GET | Method::POST | Method::PUT | Method::PATCH |
                         Method::DELETE | Method::OPTIONS => Some(method),
                         _ => {
                             // Log unsupported methods for debugging
@@ -268,6 +314,14 @@ fn load_cors_origins() -> anyhow::Result<Vec<axum::http::HeaderValue>> {
     Ok(origins)
 }
 
+/// Answer 503 instead of running the handler while the control plane is in read-only mode
+async fn read_only_guard(State(app_state): State<AppState>, request: Request, next: Next) -> Result<Response, ApiError> {
+    if app_state.read_only.load(Ordering::Relaxed) {
+        return Err(ApiError::ReadOnly);
+    }
+    Ok(next.run(request).await)
+}
+
 async fn health_check() -> &'static str {
     "OK"
 }
@@ -7,6 +7,8 @@ use axum_extra::{
 };
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use crate::config::AuthenticationConfig;
 
@@ -46,6 +48,8 @@ pub struct JwtKeys {
     pub encoding_key: EncodingKey,
     pub validation: Validation,
     pub config: AuthenticationConfig,
+    /// Runtime enforcement switch shared by every clone; starts at `config.enabled`
+    enabled: Arc<AtomicBool>,
 }
 
 impl JwtKeys {
@@ -60,9 +64,20 @@ impl JwtKeys {
             decoding_key,
             encoding_key,
             validation,
+            enabled: Arc::new(AtomicBool::new(config.enabled)),
             config,
         }
     }
+
+    /// Whether requests are currently authenticated and authorized
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Turn enforcement on or off for all middleware sharing these keys
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
 }
 
 /// Modern 2024 pattern: Simple JWT extractor that uses State
@@ -74,7 +89,7 @@ pub async fn extract_jwt_claims(
     println!("🔍 JWT Extractor: Starting token extraction...");
     
     // Check if authentication is enabled
-    if !jwt_keys.config.enabled {
+    if !jwt_keys.is_enabled() {
         println!("⚠️  JWT Extractor: Authentication disabled");
         return Err(StatusCode::UNAUTHORIZED);
     }
@@ -117,7 +132,8 @@ mod tests {
             jwt_secret: generate_test_jwt_secret(),
             jwt_expiry_hours: 1,
             jwt_issuer: "test-issuer".to_string(),
-            password_hash_cost: 8, // Lower cost for faster tests
+            password_hash_cost: crate::config::validation::MIN_TEST_BCRYPT_COST,
+            password_hash_algorithm: "bcrypt".to_string(),
         }
     }
     
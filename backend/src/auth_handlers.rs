@@ -1,15 +1,19 @@
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
 };
 use axum_extra::extract::CookieJar;
 use time::Duration;
 use serde::{Deserialize, Serialize};
 
+use crate::api::errors::ApiError;
 use crate::api::handlers::ApiResponse;
 use crate::api::routes::AppState;
 use crate::auth::{create_jwt_token, Claims};
+use crate::auth_middleware::request_token;
+use crate::config::AuthenticationConfig;
+use crate::password_hashing;
 
 /// Login request payload
 #[derive(Debug, Deserialize)]
@@ -36,19 +40,26 @@ pub struct UserInfo {
     pub permissions: std::collections::HashMap<String, Vec<String>>,
 }
 
+/// Requested authentication enforcement state
+#[derive(Debug, Deserialize)]
+pub struct AuthToggleRequest {
+    pub enabled: bool,
+}
+
 /// Simple user database (in production, this would be a real database)
-/// For demo purposes, we'll have some hardcoded users with proper bcrypt hashing
+/// For demo purposes, we'll have some hardcoded users with proper password hashing
 struct User {
     pub id: String,
     pub username: String,
-    pub password_hash: String, // bcrypt hashed password
+    pub password_hash: String, // bcrypt or argon2 hashed password
 }
 
 impl User {
-    fn new(id: &str, username: &str, password: &str, bcrypt_cost: u32) -> Self {
-        // Hash password with bcrypt for security
-        let password_hash = bcrypt::hash(password, bcrypt_cost)
-            .expect("Failed to hash password with bcrypt");
+    fn new(id: &str, username: &str, password: &str, auth_config: &AuthenticationConfig) -> Self {
+        // Hash password with the configured algorithm
+        let password_hash = password_hashing::configured_scheme(auth_config)
+            .hash(password)
+            .expect("Failed to hash password");
         
         Self {
             id: id.to_string(),
@@ -58,16 +69,15 @@ impl User {
     }
     
     fn verify_password(&self, password: &str) -> bool {
-        // Use bcrypt::verify for secure password verification
-        bcrypt::verify(password, &self.password_hash)
-            .unwrap_or(false)
+        // Verify with whichever algorithm produced the stored hash
+        password_hashing::verify_password(password, &self.password_hash)
     }
 }
 
 /// Get demo users from secure configuration
-/// Uses bcrypt hashing for password security
+/// Uses the configured password hashing algorithm for password security
 /// Credentials are loaded from environment variables for security
-fn get_demo_users(bcrypt_cost: u32) -> Vec<User> {
+fn get_demo_users(auth_config: &AuthenticationConfig) -> Vec<User> {
     let credentials = crate::config::AppConfig::load_demo_credentials();
     
     credentials
@@ -75,11 +85,29 @@ fn get_demo_users(bcrypt_cost: u32) -> Vec<User> {
         .enumerate()
         .map(|(idx, (username, password))| {
             let user_id = if idx == 0 { "admin".to_string() } else { format!("user_{}", idx) };
-            User::new(&user_id, &username, &password, bcrypt_cost)
+            User::new(&user_id, &username, &password, auth_config)
         })
         .collect()
 }
 
+/// Find the user named in the login request and verify their password against the stored hash
+fn authenticate<'a>(users: &'a [User], login_req: &LoginRequest) -> Result<&'a User, StatusCode> {
+    let user = users
+        .iter()
+        .find(|u| u.username == login_req.username)
+        .ok_or_else(|| {
+            println!("❌ User '{}' not found", login_req.username);
+            StatusCode::UNAUTHORIZED
+        })?;
+
+    if !user.verify_password(&login_req.password) {
+        println!("❌ Invalid password for user '{}'", login_req.username);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(user)
+}
+
 /// Login endpoint - authenticates user and sets httpOnly cookie
 pub async fn login(
     State(app_state): State<AppState>,
@@ -88,27 +116,16 @@ pub async fn login(
 ) -> Result<(CookieJar, Json<ApiResponse<LoginResponse>>), StatusCode> {
     println!("🔐 Login attempt for user: {}", login_req.username);
 
-    // Check if authentication is enabled
+    // Only authentication disabled in config stops logins; while enforcement is switched off at
+    // runtime admins still need a fresh token to switch it back on
     if !app_state.jwt_keys.config.enabled {
         println!("⚠️  Authentication is disabled");
         return Err(StatusCode::SERVICE_UNAVAILABLE);
     }
 
-    // Find user in our demo database
-    let users = get_demo_users(app_state.jwt_keys.config.password_hash_cost);
-    let user = users
-        .iter()
-        .find(|u| u.username == login_req.username)
-        .ok_or_else(|| {
-            println!("❌ User '{}' not found", login_req.username);
-            StatusCode::UNAUTHORIZED
-        })?;
-
-    // Verify password
-    if !user.verify_password(&login_req.password) {
-        println!("❌ Invalid password for user '{}'", login_req.username);
-        return Err(StatusCode::UNAUTHORIZED);
-    }
+    // Find user in our demo database and check the password
+    let users = get_demo_users(&app_state.jwt_keys.config);
+    let user = authenticate(&users, &login_req)?;
 
     // Create JWT token
     let token = create_jwt_token(
@@ -200,6 +217,51 @@ pub async fn get_user_info(
     )))
 }
 
+/// Turn authentication enforcement on or off without a restart (admin only)
+///
+/// Disabling is always allowed. While enforcement is off the middleware lets every
+/// request through, so enabling checks the caller's token itself: only a valid token with
+/// system write permission may turn it back on, which keeps the caller from locking
+/// themselves out. `login` keeps issuing tokens while enforcement is switched off, so an
+/// admin can get one even after the token used to disable it has expired.
+pub async fn toggle_authentication(
+    State(app_state): State<AppState>,
+    jar: CookieJar,
+    headers: HeaderMap,
+    Json(request): Json<AuthToggleRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    if request.enabled && !app_state.jwt_keys.is_enabled() {
+        let token = request_token(&jar, &headers).ok_or(ApiError::Unauthorized)?;
+        let claims = jsonwebtoken::decode::<Claims>(
+            &token,
+            &app_state.jwt_keys.decoding_key,
+            &app_state.jwt_keys.validation,
+        )
+        .map_err(|_| ApiError::Unauthorized)?
+        .claims;
+
+        let allowed = app_state
+            .rbac
+            .check_permission(claims.user_id(), "system", "write")
+            .await
+            .map_err(|e| ApiError::internal(format!("RBAC check failed: {}", e)))?;
+        if !allowed {
+            return Err(ApiError::Forbidden);
+        }
+    }
+
+    app_state.jwt_keys.set_enabled(request.enabled);
+    println!(
+        "🔐 Authentication enforcement {}",
+        if request.enabled { "enabled" } else { "disabled" }
+    );
+
+    Ok(Json(ApiResponse::success(
+        serde_json::json!({ "authentication_enabled": request.enabled }),
+        if request.enabled { "Authentication enabled" } else { "Authentication disabled" },
+    )))
+}
+
 /// Logout endpoint - clears the httpOnly authentication cookie
 pub async fn logout(jar: CookieJar) -> (CookieJar, Json<ApiResponse<()>>) {
     println!("👋 User logged out");
@@ -235,11 +297,12 @@ pub async fn logout(jar: CookieJar) -> (CookieJar, Json<ApiResponse<()>>) {
 /// Health check for auth system - secure version without credential exposure
 pub async fn auth_health(State(app_state): State<AppState>) -> Json<ApiResponse<serde_json::Value>> {
     let status = serde_json::json!({
-        "authentication_enabled": app_state.jwt_keys.config.enabled,
+        "authentication_enabled": app_state.jwt_keys.is_enabled(),
         "jwt_issuer": app_state.jwt_keys.config.jwt_issuer,
         "jwt_expiry_hours": app_state.jwt_keys.config.jwt_expiry_hours,
         "bcrypt_cost": app_state.jwt_keys.config.password_hash_cost,
-        "available_demo_users": get_demo_users(app_state.jwt_keys.config.password_hash_cost)
+        "password_hash_algorithm": app_state.jwt_keys.config.password_hash_algorithm,
+        "available_demo_users": get_demo_users(&app_state.jwt_keys.config)
             .iter()
             .map(|u| &u.username)
             .collect::<Vec<_>>(),
@@ -255,7 +318,6 @@ pub async fn auth_health(State(app_state): State<AppState>) -> Json<ApiResponse<
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::AuthenticationConfig;
 
     fn create_test_config() -> AuthenticationConfig {
         AuthenticationConfig {
@@ -263,7 +325,8 @@ mod tests {
             jwt_secret: generate_test_jwt_secret(),
             jwt_expiry_hours: 1,
             jwt_issuer: "test-issuer".to_string(),
-            password_hash_cost: 8, // Lower cost for faster tests
+            password_hash_cost: crate::config::validation::MIN_TEST_BCRYPT_COST,
+            password_hash_algorithm: "bcrypt".to_string(),
         }
     }
     
@@ -286,14 +349,52 @@ mod tests {
 
     #[test]
     fn test_user_password_verification() {
-        let user = User::new("test", "testuser", "password123", 8); // Lower cost for tests
+        let user = User::new("test", "testuser", "password123", &create_test_config());
+        assert!(user.verify_password("password123"));
+        assert!(!user.verify_password("wrong_password"));
+    }
+
+    #[test]
+    fn test_argon2_user_password_verification() {
+        let config = AuthenticationConfig {
+            password_hash_algorithm: "argon2".to_string(),
+            ..create_test_config()
+        };
+        let user = User::new("test", "testuser", "password123", &config);
+        assert!(user.password_hash.starts_with("$argon2id$"));
         assert!(user.verify_password("password123"));
         assert!(!user.verify_password("wrong_password"));
     }
 
+    #[test]
+    fn test_bcrypt_user_logs_in_after_switching_to_argon2() {
+        // Hash stored while bcrypt was configured
+        let legacy_user = User::new("legacy", "legacy", "password123", &create_test_config());
+        assert!(legacy_user.password_hash.starts_with("$2"));
+
+        // Users created after switching get argon2 hashes, alongside the stored bcrypt one
+        let config = AuthenticationConfig {
+            password_hash_algorithm: "argon2".to_string(),
+            ..create_test_config()
+        };
+        let users = vec![legacy_user, User::new("new", "new", "password456", &config)];
+        assert!(users[1].password_hash.starts_with("$argon2id$"));
+
+        let login = |username: &str, password: &str| LoginRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+        };
+        assert_eq!(authenticate(&users, &login("legacy", "password123")).unwrap().id, "legacy");
+        assert_eq!(authenticate(&users, &login("new", "password456")).unwrap().id, "new");
+        assert_eq!(
+            authenticate(&users, &login("legacy", "wrong_password")).err(),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+    }
+
     #[test]
     fn test_demo_users_creation() {
-        let users = get_demo_users(8); // Lower cost for tests
+        let users = get_demo_users(&create_test_config());
         assert_eq!(users.len(), 3); // Updated to match secure implementation
         assert!(users.iter().any(|u| u.username == "admin"));
         assert!(users.iter().any(|u| u.username == "user"));
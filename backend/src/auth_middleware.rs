@@ -1,6 +1,6 @@
 use axum::{
     extract::{Request, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     middleware::Next,
     response::Response,
 };
@@ -22,37 +22,16 @@ pub async fn auth_middleware(
     println!("🛡️  Auth Middleware: Starting authentication & authorization...");
     
     // Skip auth if disabled
-    if !jwt_keys.config.enabled {
+    if !jwt_keys.is_enabled() {
         println!("⚠️  Auth Middleware: Authentication disabled - allowing request");
         return Ok(next.run(request).await);
     }
     
     // Step 1: Extract JWT token from cookie or Authorization header
-    let jwt_token = match jar.get("auth_token") {
-        Some(cookie) => {
-            println!("🍪 Auth Middleware: Found JWT token in httpOnly cookie");
-            cookie.value().to_string()
-        }
-        None => {
-            // Fallback to Authorization header for backward compatibility
-            println!("🔍 Auth Middleware: No cookie found, checking Authorization header...");
-            request
-                .headers()
-                .get("authorization")
-                .and_then(|header| header.to_str().ok())
-                .and_then(|auth_str| {
-                    if auth_str.starts_with("Bearer ") {
-                        Some(auth_str.strip_prefix("Bearer ")?.to_string())
-                    } else {
-                        None
-                    }
-                })
-                .ok_or_else(|| {
-                    println!("❌ Auth Middleware: No JWT token found in cookie or Authorization header");
-                    StatusCode::UNAUTHORIZED
-                })?
-        }
-    };
+    let jwt_token = request_token(&jar, request.headers()).ok_or_else(|| {
+        println!("❌ Auth Middleware: No JWT token found in cookie or Authorization header");
+        StatusCode::UNAUTHORIZED
+    })?;
     
     // Step 2: JWT Authentication - Validate JWT token
     println!("🔍 Auth Middleware: Step 1 - JWT Authentication");
@@ -116,33 +95,13 @@ pub async fn optional_auth_middleware(
     println!("🔓 Optional Auth Middleware: Checking for optional authentication...");
     
     // Skip if auth is disabled
-    if !jwt_keys.config.enabled {
+    if !jwt_keys.is_enabled() {
         println!("⚠️  Optional Auth: Authentication disabled");
         return next.run(request).await;
     }
     
     // Try to extract JWT from cookie or Authorization header
-    let jwt_token = match jar.get("auth_token") {
-        Some(cookie) => {
-            println!("🍪 Optional Auth: Found JWT token in httpOnly cookie");
-            Some(cookie.value().to_string())
-        }
-        None => {
-            // Fallback to Authorization header
-            println!("🔍 Optional Auth: No cookie found, checking Authorization header...");
-            request
-                .headers()
-                .get("authorization")
-                .and_then(|header| header.to_str().ok())
-                .and_then(|auth_str| {
-                    if auth_str.starts_with("Bearer ") {
-                        Some(auth_str.strip_prefix("Bearer ")?.to_string())
-                    } else {
-                        None
-                    }
-                })
-        }
-    };
+    let jwt_token = request_token(&jar, request.headers());
 
     if let Some(token) = jwt_token {
         println!("🔍 Optional Auth: Found JWT token, validating...");
@@ -164,6 +123,18 @@ pub async fn optional_auth_middleware(
     next.run(request).await
 }
 
+/// JWT from the `auth_token` cookie, falling back to an `Authorization: Bearer` header
+pub fn request_token(jar: &CookieJar, headers: &HeaderMap) -> Option<String> {
+    if let Some(cookie) = jar.get("auth_token") {
+        return Some(cookie.value().to_string());
+    }
+    headers
+        .get("authorization")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|auth_str| auth_str.strip_prefix("Bearer "))
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,7 +154,8 @@ mod tests {
             jwt_secret: generate_test_jwt_secret(),
             jwt_expiry_hours: 1,
             jwt_issuer: "test-issuer".to_string(),
-            password_hash_cost: 8,
+            password_hash_cost: crate::config::validation::MIN_TEST_BCRYPT_COST,
+            password_hash_algorithm: "bcrypt".to_string(),
         }
     }
     
@@ -233,4 +205,4 @@ mod tests {
         // Should succeed with optional auth (no token required)
         assert_eq!(response.status(), StatusCode::OK);
     }
-}
\ No newline at end of file
+}
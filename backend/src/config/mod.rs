@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Placeholder shown in place of secrets when configuration is exposed over the API
+const REDACTED: &str = "[REDACTED]";
+
+pub mod reload;
 pub mod validation;
 
+pub use reload::SharedConfig;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub control_plane: ControlPlaneConfig,
@@ -29,6 +35,83 @@ pub struct ServerConfig {
     pub rest_port: u16,
     pub xds_port: u16,
     pub host: String,
+    #[serde(default)]
+    pub xds_keepalive: XdsKeepaliveConfig,
+    #[serde(default)]
+    pub strict_resource_types: bool, // Answer unknown xDS type URLs with UNIMPLEMENTED instead of an empty response
+    #[serde(default = "ServerConfig::default_node_match_mode")]
+    pub node_match_mode: String, // How a resource's node_id tag is compared to the requesting node: exact, prefix or regex
+    #[serde(default)]
+    pub reject_unknown_fields: bool, // Answer API bodies with unrecognised fields with 400 instead of ignoring them
+    #[serde(default)]
+    pub last_known_good_grace_seconds: Option<u64>, // Serve the last good xDS resources this long after a failed conversion; unset = until the next success
+    #[serde(default)]
+    pub read_only: bool, // Start in read-only mode: mutating REST calls get 503 while reads and xDS keep serving
+}
+
+impl ServerConfig {
+    fn default_node_match_mode() -> String {
+        "exact".to_string()
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Compiled regex node tags, keyed by tag, so CDS responses don't recompile them per cluster
+    static ref NODE_TAG_REGEXES: dashmap::DashMap<String, regex::Regex> = dashmap::DashMap::new();
+}
+
+/// Whether a resource tagged with `tag` should be served to the node `node_id` under `node_match_mode`
+///
+/// Regex tags must match the whole node id. Fails only when a regex tag doesn't compile.
+pub fn node_matches(node_match_mode: &str, tag: &str, node_id: &str) -> Result<bool, regex::Error> {
+    Ok(match node_match_mode {
+        "prefix" => node_id.starts_with(tag),
+        "regex" => node_tag_regex(tag)?.is_match(node_id),
+        _ => tag == node_id,
+    })
+}
+
+/// Check that a node tag can be compared under `node_match_mode`, i.e. that regex tags compile
+pub fn validate_node_tag(node_match_mode: &str, tag: &str) -> Result<(), regex::Error> {
+    if node_match_mode == "regex" {
+        node_tag_regex(tag)?;
+    }
+    Ok(())
+}
+
+fn node_tag_regex(tag: &str) -> Result<regex::Regex, regex::Error> {
+    if let Some(regex) = NODE_TAG_REGEXES.get(tag) {
+        return Ok(regex.clone());
+    }
+    let regex = regex::Regex::new(&format!("^(?:{})$", tag))?;
+    NODE_TAG_REGEXES.insert(tag.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// HTTP/2 keepalive (PING) settings for long-lived xDS streams
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct XdsKeepaliveConfig {
+    pub interval_seconds: u64, // How often to send HTTP/2 PING frames on idle connections
+    pub timeout_seconds: u64,  // How long to wait for a PING ack before closing the connection
+}
+
+impl Default for XdsKeepaliveConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 30,  // Below typical proxy/load balancer idle timeouts
+            timeout_seconds: 10,
+        }
+    }
+}
+
+impl XdsKeepaliveConfig {
+    pub fn interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.interval_seconds)
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_seconds)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,6 +119,14 @@ pub struct TlsConfig {
     pub cert_path: String,
     pub key_path: String,
     pub enabled: bool,
+    #[serde(default = "TlsConfig::default_min_tls_version")]
+    pub min_tls_version: String, // Oldest TLS version the xDS server accepts: "1.2" or "1.3"
+}
+
+impl TlsConfig {
+    fn default_min_tls_version() -> String {
+        "1.2".to_string()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -62,6 +153,14 @@ pub struct AuthenticationConfig {
     pub jwt_expiry_hours: u64,
     pub jwt_issuer: String,
     pub password_hash_cost: u32,
+    #[serde(default = "AuthenticationConfig::default_password_hash_algorithm")]
+    pub password_hash_algorithm: String, // "bcrypt" (uses password_hash_cost) or "argon2"
+}
+
+impl AuthenticationConfig {
+    fn default_password_hash_algorithm() -> String {
+        "bcrypt".to_string()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -101,7 +200,9 @@ impl Default for StorageLimitsConfig {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StorageBehaviorConfig {
     pub reject_on_capacity: bool,  // true = reject, false = warn and allow
-    pub enable_metrics: bool,      // Enable capacity metrics collection
+    pub enable_metrics: bool,      // Enable capacity metrics and per-cluster endpoint gauges
+    #[serde(default)]
+    pub abort_on_conversion_error: bool, // true = refuse to start, false = log invalid resources
 }
 
 
@@ -110,6 +211,7 @@ impl Default for StorageBehaviorConfig {
         Self {
             reject_on_capacity: true,     // Conservative default for production safety
             enable_metrics: true,         // Enable observability by default
+            abort_on_conversion_error: false, // Log and keep serving by default
         }
     }
 }
@@ -120,6 +222,9 @@ pub struct HttpFiltersFeatureConfig {
     pub supported_filters: Vec<String>,
     pub default_order: Vec<String>,
     pub limits: HttpFiltersLimitsConfig,
+    /// Insert a gzip compressor into every listener without a filter resource
+    #[serde(default)]
+    pub enable_compression: bool,
 }
 
 impl Default for HttpFiltersFeatureConfig {
@@ -132,6 +237,7 @@ impl Default for HttpFiltersFeatureConfig {
                 "header_manipulation".to_string(),
                 "authentication".to_string(),
                 "request_validation".to_string(),
+                "header_to_metadata".to_string(),
             ],
             default_order: vec![
                 "authentication".to_string(),
@@ -139,8 +245,10 @@ impl Default for HttpFiltersFeatureConfig {
                 "cors".to_string(),
                 "header_manipulation".to_string(),
                 "request_validation".to_string(),
+                "header_to_metadata".to_string(),
             ],
             limits: HttpFiltersLimitsConfig::default(),
+            enable_compression: false,
         }
     }
 }
@@ -182,6 +290,93 @@ pub struct AdminConfig {
 pub struct ListenerConfig {
     pub binding_address: String,
     pub default_port: u16,
+    #[serde(default)]
+    pub forwarded_headers: ForwardedHeadersConfig,
+    #[serde(default)]
+    pub http3: bool, // Also expose a QUIC (HTTP/3) listener on the same port over UDP
+    #[serde(default)]
+    pub downstream_tls: Option<DownstreamTlsConfig>,
+    #[serde(default)]
+    pub allow_early_data: Option<bool>, // Accept TLS 1.3 early data (0-RTT) on the HTTP/3 listener; Envoy's default when unset
+    #[serde(default)]
+    pub default_request_timeout_seconds: Option<u64>, // Applied to routes without their own timeout_seconds
+    #[serde(default)]
+    pub per_connection_buffer_limit_bytes: Option<u32>, // Envoy defaults to 1 MiB when unset
+    #[serde(default)]
+    pub exact_connection_balance: bool, // Spread accepted connections evenly across worker threads
+    #[serde(default = "ListenerConfig::default_drain_type")]
+    pub drain_type: String, // DEFAULT or MODIFY_ONLY
+    #[serde(default)]
+    pub drain_timeout_seconds: Option<u64>, // HCM drain_timeout for graceful connection close
+    #[serde(default = "ListenerConfig::default_server_header_transformation")]
+    pub server_header_transformation: String, // OVERWRITE, APPEND_IF_ABSENT or PASS_THROUGH
+    #[serde(default)]
+    pub server_name: Option<String>, // Value written to the `server` header; Envoy sends "envoy" when unset
+    #[serde(default)]
+    pub suppress_envoy_headers: bool, // Drop x-envoy-* headers added by the router filter
+    #[serde(default)]
+    pub access_log: Option<AccessLogConfig>, // File access log for the HCM; no access log when unset
+    #[serde(default)]
+    pub additional_listeners: Vec<AdditionalListenerConfig>, // Extra TCP listeners, each with its own route configuration
+    #[serde(default)]
+    pub local_reply: Option<LocalReplyFormatConfig>, // Body format for every Envoy-generated local reply; Envoy's plain text when unset
+}
+
+impl ListenerConfig {
+    fn default_drain_type() -> String {
+        "DEFAULT".to_string()
+    }
+
+    fn default_server_header_transformation() -> String {
+        "OVERWRITE".to_string()
+    }
+}
+
+/// Extra TCP listener on the binding address serving the routes assigned to it by name
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdditionalListenerConfig {
+    pub name: String,
+    pub port: u16,
+    #[serde(default)]
+    pub domains: Option<Vec<String>>, // Virtual host domains of this listener's route config; defaults to naming.default_domains
+}
+
+/// JSON body Envoy renders for its own (local) replies, such as 404 no-route or 503 no-healthy-upstream
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalReplyFormatConfig {
+    pub json_format: serde_json::Map<String, serde_json::Value>, // Values may use command operators like %RESPONSE_CODE%
+}
+
+/// HCM file access log, optionally restricted to errors and/or a sample of requests
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccessLogConfig {
+    #[serde(default = "AccessLogConfig::default_path")]
+    pub path: String,
+    #[serde(default)]
+    pub min_status_code: Option<u32>, // Only log responses at or above this status, e.g. 400 for 4xx/5xx
+    #[serde(default)]
+    pub sample_fraction: Option<f64>, // Log this fraction (0-1) of requests
+}
+
+impl AccessLogConfig {
+    fn default_path() -> String {
+        "/dev/stdout".to_string()
+    }
+}
+
+/// Certificate used by Envoy to terminate downstream TLS (required for QUIC)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownstreamTlsConfig {
+    pub cert_path: String,
+    pub key_path: String,
+}
+
+/// X-Forwarded-* handling for the HTTP connection manager
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ForwardedHeadersConfig {
+    pub use_remote_address: bool,      // Use the downstream connection address as the client address
+    pub xff_num_trusted_hops: u32,     // Trusted proxy hops in X-Forwarded-For (unsigned, so never negative)
+    pub append_x_forwarded_host: bool, // Append x-forwarded-host when the host header is rewritten
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -190,6 +385,41 @@ pub struct ClusterConfig {
     pub discovery_type: String,
     pub dns_lookup_family: String,
     pub default_protocol: String,
+    #[serde(default)]
+    pub dns_resolution_check: DnsResolutionCheckConfig,
+    #[serde(default = "ClusterConfig::default_max_buffer_limit_bytes")]
+    pub max_buffer_limit_bytes: u32, // Ceiling for per-cluster per_connection_buffer_limit_bytes
+    #[serde(default)]
+    pub reject_duplicate_endpoints: bool, // true = reject clusters with repeated host:port, false = dedupe with a warning
+}
+
+impl ClusterConfig {
+    fn default_max_buffer_limit_bytes() -> u32 {
+        16 * 1024 * 1024 // 16 MiB
+    }
+
+    /// Whether generated clusters resolve their endpoints through DNS
+    pub fn is_dns_discovery(&self) -> bool {
+        matches!(self.discovery_type.as_str(), "STRICT_DNS" | "LOGICAL_DNS")
+    }
+}
+
+/// Create-time DNS resolution check for endpoint hosts of DNS-based clusters
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsResolutionCheckConfig {
+    pub enabled: bool,
+    pub reject_unresolvable: bool, // true = reject the cluster, false = log a warning
+    pub timeout_ms: u64,
+}
+
+impl Default for DnsResolutionCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,              // Opt-in: resolution depends on the control plane's resolver
+            reject_unresolvable: false,
+            timeout_ms: 500,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -216,11 +446,95 @@ pub struct HttpFiltersConfig {
     pub hcm_filter_name: String,
 }
 
+/// Bounded retry with exponential backoff for loading the config file at startup
+#[derive(Debug, Clone)]
+pub struct ConfigLoadRetry {
+    pub max_attempts: u32,
+    pub initial_backoff: std::time::Duration,
+    pub max_backoff: std::time::Duration,
+}
+
+impl Default for ConfigLoadRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: std::time::Duration::from_millis(500),
+            max_backoff: std::time::Duration::from_secs(8),
+        }
+    }
+}
+
+impl ConfigLoadRetry {
+    /// Defaults overridden by CONFIG_LOAD_MAX_ATTEMPTS and CONFIG_LOAD_BACKOFF_MS
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mut retry = Self::default();
+
+        if let Ok(attempts) = std::env::var("CONFIG_LOAD_MAX_ATTEMPTS") {
+            match attempts.parse::<u32>() {
+                Ok(attempts) if (1..=20).contains(&attempts) => retry.max_attempts = attempts,
+                _ => return Err(anyhow::anyhow!("CONFIG_LOAD_MAX_ATTEMPTS must be a number between 1 and 20")),
+            }
+        }
+
+        if let Ok(backoff) = std::env::var("CONFIG_LOAD_BACKOFF_MS") {
+            match backoff.parse::<u64>() {
+                Ok(millis) if (1..=60_000).contains(&millis) => {
+                    retry.initial_backoff = std::time::Duration::from_millis(millis);
+                }
+                _ => return Err(anyhow::anyhow!("CONFIG_LOAD_BACKOFF_MS must be a number between 1 and 60,000")),
+            }
+        }
+
+        Ok(retry)
+    }
+}
+
 impl AppConfig {
     pub fn load() -> anyhow::Result<Self> {
+        Self::load_from("config")
+    }
+
+    /// Keep retrying `load_from` so a config file mounted shortly after startup is still picked up
+    ///
+    /// Only a missing or unreadable file is retried; parse and validation errors fail at once.
+    pub async fn load_with_retry(path: &str, retry: &ConfigLoadRetry) -> anyhow::Result<Self> {
+        let mut backoff = retry.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match Self::load_from(path) {
+                Ok(config) => return Ok(config),
+                Err(e) if attempt < retry.max_attempts && Self::is_retryable_load_error(&e) => {
+                    println!(
+                        "⚠️  Config load attempt {}/{} failed: {}. Retrying in {:?}",
+                        attempt, retry.max_attempts, e, backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(retry.max_backoff);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.context(format!("Failed to load configuration after {} attempt(s)", attempt))),
+            }
+        }
+    }
+
+    /// Whether a `load_from` failure may clear up on its own: the file is missing or can't be read yet
+    fn is_retryable_load_error(error: &anyhow::Error) -> bool {
+        error.chain().any(|cause| {
+            cause.is::<std::io::Error>()
+                || match cause.downcast_ref::<config::ConfigError>() {
+                    Some(config::ConfigError::NotFound(_)) => true,
+                    // config-rs reports a missing or unreadable file as a wrapped I/O error
+                    Some(config::ConfigError::Foreign(inner)) => inner.is::<std::io::Error>(),
+                    _ => false,
+                }
+        })
+    }
+
+    /// Load configuration from `path` (extension optional), applying env overrides and validation
+    pub fn load_from(path: &str) -> anyhow::Result<Self> {
         let settings = config::Config::builder()
             // Start with config file as base
-            .add_source(config::File::with_name("config"))
+            .add_source(config::File::with_name(path))
             // Override with environment variables (higher priority)
             .add_source(
                 config::Environment::with_prefix("ENVOY_CP")
@@ -231,11 +545,8 @@ impl AppConfig {
 
         let mut config: Self = settings.try_deserialize()?;
 
-        // Apply security-focused environment variable overrides
-        Self::apply_security_env_overrides(&mut config)?;
-        
-        // Apply storage configuration overrides
-        Self::apply_storage_env_overrides(&mut config)?;
+        // Apply security and storage environment variable overrides
+        Self::apply_env_overrides(&mut config)?;
 
         // Validate the loaded configuration
         validation::validate_config(&config)?;
@@ -243,6 +554,27 @@ impl AppConfig {
         Ok(config)
     }
 
+    /// Apply security and storage environment variable overrides on top of file config
+    pub fn apply_env_overrides(config: &mut Self) -> anyhow::Result<()> {
+        Self::apply_security_env_overrides(config)?;
+        Self::apply_storage_env_overrides(config)
+    }
+
+    /// Serialize the effective configuration with secrets and private key paths masked
+    pub fn redacted(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or(serde_json::Value::Null);
+        for pointer in [
+            "/control_plane/authentication/jwt_secret",
+            "/control_plane/tls/key_path",
+            "/envoy_generation/listener/downstream_tls/key_path",
+        ] {
+            if let Some(secret) = value.pointer_mut(pointer) {
+                *secret = serde_json::Value::String(REDACTED.to_string());
+            }
+        }
+        value
+    }
+
 
     /// Apply critical security environment variables with validation
     fn apply_security_env_overrides(config: &mut Self) -> anyhow::Result<()> {
@@ -291,12 +623,14 @@ impl AppConfig {
         // Password Hash Cost override
         if let Ok(hash_cost) = std::env::var("BCRYPT_COST") {
             match hash_cost.parse::<u32>() {
-                Ok(cost) if cost >= 10 && cost <= 15 => { // Reasonable bcrypt cost range
+                Ok(cost) if (validation::MIN_BCRYPT_COST..=validation::MAX_BCRYPT_COST).contains(&cost) => {
                     config.control_plane.authentication.password_hash_cost = cost;
                 }
                 _ => {
                     return Err(anyhow::anyhow!(
-                        "BCRYPT_COST must be a number between 10 and 15"
+                        "BCRYPT_COST must be a number between {} and {}",
+                        validation::MIN_BCRYPT_COST,
+                        validation::MAX_BCRYPT_COST
                     ));
                 }
             }
@@ -456,11 +790,18 @@ impl AppConfig {
                     rest_port: 8080,
                     xds_port: 18000,
                     host: "0.0.0.0".to_string(),
+                    xds_keepalive: XdsKeepaliveConfig::default(),
+                    strict_resource_types: false,
+                    node_match_mode: "exact".to_string(),
+                    reject_unknown_fields: false,
+                    last_known_good_grace_seconds: None,
+                    read_only: false,
                 },
                 tls: TlsConfig {
                     cert_path: "./certs/server.crt".to_string(),
                     key_path: "./certs/server.key".to_string(),
                     enabled: true,
+                    min_tls_version: "1.2".to_string(),
                 },
                 logging: LoggingConfig {
                     level: "info".to_string(),
@@ -483,7 +824,8 @@ impl AppConfig {
                     jwt_secret: Self::generate_test_jwt_secret(),
                     jwt_expiry_hours: 24,
                     jwt_issuer: "envoy-control-plane-test".to_string(),
-                    password_hash_cost: 8,  // Lower cost for faster tests
+                    password_hash_cost: validation::MIN_TEST_BCRYPT_COST,
+                    password_hash_algorithm: "bcrypt".to_string(),
                 },
                 storage: StorageConfig {
                     limits: StorageLimitsConfig {
@@ -495,6 +837,7 @@ impl AppConfig {
                     behavior: StorageBehaviorConfig {
                         reject_on_capacity: true,
                         enable_metrics: false,  // Disabled for tests to reduce noise
+                        abort_on_conversion_error: false,
                     },
                 },
                 http_filters: HttpFiltersFeatureConfig::default(),
@@ -508,12 +851,30 @@ impl AppConfig {
                 listener: ListenerConfig {
                     binding_address: "0.0.0.0".to_string(),
                     default_port: 10000,
+                    forwarded_headers: ForwardedHeadersConfig::default(),
+                    http3: false,
+                    downstream_tls: None,
+                    default_request_timeout_seconds: None,
+                    per_connection_buffer_limit_bytes: None,
+                    exact_connection_balance: false,
+                    drain_type: "DEFAULT".to_string(),
+                    drain_timeout_seconds: None,
+                    server_header_transformation: "OVERWRITE".to_string(),
+                    server_name: None,
+                    suppress_envoy_headers: false,
+                    access_log: None,
+                    additional_listeners: vec![],
+                    allow_early_data: None,
+                    local_reply: None,
                 },
                 cluster: ClusterConfig {
                     connect_timeout_seconds: 5,
                     discovery_type: "STRICT_DNS".to_string(),
                     dns_lookup_family: "V4_ONLY".to_string(),
                     default_protocol: "TCP".to_string(),
+                    dns_resolution_check: DnsResolutionCheckConfig::default(),
+                    max_buffer_limit_bytes: 16 * 1024 * 1024,
+                    reject_duplicate_endpoints: false,
                 },
                 naming: NamingConfig {
                     listener_name: "listener_0".to_string(),
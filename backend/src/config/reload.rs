@@ -0,0 +1,149 @@
+use super::AppConfig;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// Fields that take effect without a restart when the config file is reloaded
+pub const HOT_RELOADABLE_FIELDS: &[&str] = &[
+    "control_plane.logging.level",
+    "control_plane.load_balancing.default_policy",
+    "control_plane.http_filters.default_order",
+];
+
+/// Outcome of applying a reloaded config file to the running process
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReloadReport {
+    /// Hot-reloadable fields whose new values are now in effect
+    pub applied: Vec<String>,
+    /// Changed fields that keep their old values until the process restarts
+    pub requires_restart: Vec<String>,
+}
+
+impl ReloadReport {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.requires_restart.is_empty()
+    }
+}
+
+/// Application config shared across handlers that can be swapped at runtime
+///
+/// Readers take a cheap snapshot with `current()`; a reload only ever replaces
+/// the hot-reloadable fields listed in `HOT_RELOADABLE_FIELDS`.
+#[derive(Debug, Clone)]
+pub struct SharedConfig {
+    inner: Arc<RwLock<Arc<AppConfig>>>,
+}
+
+impl SharedConfig {
+    pub fn new(config: AppConfig) -> Self {
+        Self::from(Arc::new(config))
+    }
+
+    /// Snapshot of the config currently in effect
+    pub fn current(&self) -> Arc<AppConfig> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Copy hot-reloadable fields from `reloaded` and report every field that changed
+    pub fn apply_reload(&self, reloaded: &AppConfig) -> ReloadReport {
+        let mut guard = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        let report = diff_fields(&guard, reloaded);
+        if report.applied.is_empty() {
+            return report;
+        }
+
+        let mut next = AppConfig::clone(&guard);
+        next.control_plane.logging.level = reloaded.control_plane.logging.level.clone();
+        next.control_plane.load_balancing.default_policy =
+            reloaded.control_plane.load_balancing.default_policy.clone();
+        next.control_plane.http_filters.default_order = reloaded.control_plane.http_filters.default_order.clone();
+        *guard = Arc::new(next);
+
+        report
+    }
+}
+
+impl From<Arc<AppConfig>> for SharedConfig {
+    fn from(config: Arc<AppConfig>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(config)),
+        }
+    }
+}
+
+/// Classify every changed leaf field as hot-reloadable or restart-only
+fn diff_fields(current: &AppConfig, reloaded: &AppConfig) -> ReloadReport {
+    let mut before = BTreeMap::new();
+    let mut after = BTreeMap::new();
+    flatten("", &serde_json::to_value(current).unwrap_or(Value::Null), &mut before);
+    flatten("", &serde_json::to_value(reloaded).unwrap_or(Value::Null), &mut after);
+
+    let mut report = ReloadReport::default();
+    let paths: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for path in paths {
+        if before.get(path) == after.get(path) {
+            continue;
+        }
+        if HOT_RELOADABLE_FIELDS.contains(&path.as_str()) {
+            report.applied.push(path.clone());
+        } else {
+            report.requires_restart.push(path.clone());
+        }
+    }
+    report
+}
+
+/// Flatten nested objects into dotted paths; arrays and scalars are compared whole
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, child, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_updates_default_policy_in_memory() {
+        let shared = SharedConfig::new(AppConfig::create_test_config());
+
+        let mut reloaded = AppConfig::clone(&shared.current());
+        reloaded.control_plane.load_balancing.default_policy = "LEAST_REQUEST".to_string();
+        reloaded.control_plane.server.rest_port = 9090;
+
+        let report = shared.apply_reload(&reloaded);
+        assert_eq!(report.applied, vec!["control_plane.load_balancing.default_policy"]);
+        assert_eq!(report.requires_restart, vec!["control_plane.server.rest_port"]);
+
+        let current = shared.current();
+        assert_eq!(current.control_plane.load_balancing.default_policy, "LEAST_REQUEST");
+        assert_ne!(current.control_plane.server.rest_port, 9090, "restart-only fields keep their old value");
+    }
+
+    #[test]
+    fn test_reload_of_restart_only_field_keeps_running_value() {
+        let shared = SharedConfig::new(AppConfig::create_test_config());
+        let original_port = shared.current().control_plane.server.xds_port;
+
+        let mut reloaded = AppConfig::clone(&shared.current());
+        reloaded.control_plane.server.xds_port = original_port + 1;
+
+        let report = shared.apply_reload(&reloaded);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.requires_restart, vec!["control_plane.server.xds_port"]);
+        assert_eq!(shared.current().control_plane.server.xds_port, original_port);
+    }
+}
@@ -1,6 +1,20 @@
-use super::{AppConfig, EnvoyGenerationConfig, ServerConfig, StorageConfig};
+use super::{
+    AppConfig, AuthenticationConfig, EnvoyGenerationConfig, ServerConfig, StorageConfig, TlsConfig, XdsKeepaliveConfig,
+};
 use anyhow::{bail, Result};
 
+/// Lowest bcrypt cost accepted from any source (config file or `BCRYPT_COST`)
+pub const MIN_BCRYPT_COST: u32 = 10;
+
+/// Highest bcrypt cost accepted; beyond this a single login takes seconds
+pub const MAX_BCRYPT_COST: u32 = 15;
+
+/// Lowest bcrypt cost for the test profile: configs that tests build in code and never load
+///
+/// This is bcrypt's own minimum, so test logins hash quickly. Loaded configs are still held
+/// to `MIN_BCRYPT_COST`, which a test config would fail.
+pub const MIN_TEST_BCRYPT_COST: u32 = 4;
+
 /// Configuration validation errors with helpful messages
 #[derive(Debug, thiserror::Error)]
 pub enum ValidationError {
@@ -26,6 +40,49 @@ pub fn validate_config(config: &AppConfig) -> Result<()> {
     validate_server_config(&config.control_plane.server)?;
     validate_envoy_config(&config.envoy_generation)?;
     validate_storage_config(&config.control_plane.storage)?;
+    validate_authentication_config(&config.control_plane.authentication)?;
+    validate_tls_config(&config.control_plane.tls)?;
+    validate_http_filters_config(config)?;
+    Ok(())
+}
+
+/// Validates the HTTP filter chain settings against the filter strategies' ordering hints
+fn validate_http_filters_config(config: &AppConfig) -> Result<()> {
+    let registry = crate::xds::filters::FilterStrategyRegistry::new(config);
+    if let Some((earlier, later)) = registry.first_order_violation(&config.control_plane.http_filters.default_order) {
+        bail!("http_filters.default_order must list {} before {}", earlier, later);
+    }
+    Ok(())
+}
+
+/// Validates xDS server TLS settings
+fn validate_tls_config(tls: &TlsConfig) -> Result<()> {
+    if !matches!(tls.min_tls_version.as_str(), "1.2" | "1.3") {
+        bail!("tls.min_tls_version must be \"1.2\" or \"1.3\", got '{}'", tls.min_tls_version);
+    }
+    Ok(())
+}
+
+/// Validates authentication settings, regardless of whether they came from the file or env
+fn validate_authentication_config(auth: &AuthenticationConfig) -> Result<()> {
+    // The cost only applies to new bcrypt hashes; argon2 uses its own parameters
+    if auth.password_hash_algorithm == "bcrypt"
+        && !(MIN_BCRYPT_COST..=MAX_BCRYPT_COST).contains(&auth.password_hash_cost)
+    {
+        bail!(
+            "authentication.password_hash_cost must be between {} and {}, got {}",
+            MIN_BCRYPT_COST,
+            MAX_BCRYPT_COST,
+            auth.password_hash_cost
+        );
+    }
+    if !crate::password_hashing::SUPPORTED_ALGORITHMS.contains(&auth.password_hash_algorithm.as_str()) {
+        bail!(
+            "authentication.password_hash_algorithm must be one of {}, got '{}'",
+            crate::password_hashing::SUPPORTED_ALGORITHMS.join(", "),
+            auth.password_hash_algorithm
+        );
+    }
     Ok(())
 }
 
@@ -41,6 +98,34 @@ fn validate_server_config(server: &ServerConfig) -> Result<()> {
     // Validate host address
     validate_host(&server.host)?;
 
+    validate_xds_keepalive(&server.xds_keepalive)?;
+
+    if !matches!(server.node_match_mode.as_str(), "exact" | "prefix" | "regex") {
+        bail!(
+            "server.node_match_mode must be one of exact, prefix or regex, got '{}'",
+            server.node_match_mode
+        );
+    }
+
+    Ok(())
+}
+
+/// Validates xDS HTTP/2 keepalive settings
+fn validate_xds_keepalive(keepalive: &XdsKeepaliveConfig) -> Result<()> {
+    if keepalive.interval_seconds == 0 || keepalive.interval_seconds > 3600 {
+        bail!(ValidationError::InvalidTimeout {
+            value: keepalive.interval_seconds,
+            reason: "xds_keepalive.interval_seconds must be between 1 and 3600".to_string()
+        });
+    }
+
+    if keepalive.timeout_seconds == 0 || keepalive.timeout_seconds >= keepalive.interval_seconds {
+        bail!(ValidationError::InvalidTimeout {
+            value: keepalive.timeout_seconds,
+            reason: "xds_keepalive.timeout_seconds must be at least 1 and less than interval_seconds".to_string()
+        });
+    }
+
     Ok(())
 }
 
@@ -196,9 +281,171 @@ fn validate_envoy_config(envoy: &EnvoyGenerationConfig) -> Result<()> {
     validate_host(&envoy.admin.host)?;
     validate_host(&envoy.listener.binding_address)?;
 
+    if envoy.listener.default_request_timeout_seconds == Some(0) {
+        bail!("listener.default_request_timeout_seconds must be greater than 0 (omit it to use Envoy's default)");
+    }
+
+    if envoy.cluster.max_buffer_limit_bytes == 0 {
+        bail!("cluster.max_buffer_limit_bytes must be greater than 0");
+    }
+
+    if let Some(limit) = envoy.listener.per_connection_buffer_limit_bytes {
+        if limit == 0 || limit > envoy.cluster.max_buffer_limit_bytes {
+            bail!(
+                "listener.per_connection_buffer_limit_bytes must be between 1 and {} (cluster.max_buffer_limit_bytes)",
+                envoy.cluster.max_buffer_limit_bytes
+            );
+        }
+    }
+
+    if !matches!(envoy.listener.drain_type.as_str(), "DEFAULT" | "MODIFY_ONLY") {
+        bail!(
+            "listener.drain_type must be DEFAULT or MODIFY_ONLY, got '{}'",
+            envoy.listener.drain_type
+        );
+    }
+
+    if let Some(drain_timeout) = envoy.listener.drain_timeout_seconds {
+        validate_timeout(drain_timeout, "listener.drain_timeout_seconds")?;
+    }
+
+    if !matches!(
+        envoy.listener.server_header_transformation.as_str(),
+        "OVERWRITE" | "APPEND_IF_ABSENT" | "PASS_THROUGH"
+    ) {
+        bail!(
+            "listener.server_header_transformation must be OVERWRITE, APPEND_IF_ABSENT or PASS_THROUGH, got '{}'",
+            envoy.listener.server_header_transformation
+        );
+    }
+
+    if let Some(server_name) = &envoy.listener.server_name {
+        if server_name.is_empty()
+            || server_name.len() > 100
+            || crate::validation::security::Validator::validate_http_header_value(server_name).is_err()
+        {
+            bail!("listener.server_name must be a valid header value of 1-100 characters");
+        }
+    }
+
+    if let Some(access_log) = &envoy.listener.access_log {
+        if access_log.path.is_empty() {
+            bail!("listener.access_log.path cannot be empty");
+        }
+        if access_log.min_status_code.is_some_and(|code| !(100..=599).contains(&code)) {
+            bail!("listener.access_log.min_status_code must be an HTTP status code between 100 and 599");
+        }
+        if access_log.sample_fraction.is_some_and(|fraction| !(0.0..=1.0).contains(&fraction)) {
+            bail!("listener.access_log.sample_fraction must be between 0 and 1");
+        }
+    }
+
+    if let Some(local_reply) = &envoy.listener.local_reply {
+        validate_local_reply_format(&local_reply.json_format)?;
+    }
+
+    validate_domains("naming.default_domains", &envoy.naming.default_domains)?;
+
+    let mut listener_names = std::collections::HashSet::from([envoy.bootstrap.main_listener_name.as_str()]);
+    let mut listener_ports = std::collections::HashSet::from([envoy.listener.default_port]);
+    for additional in &envoy.listener.additional_listeners {
+        if additional.name.is_empty() || !listener_names.insert(additional.name.as_str()) {
+            bail!(
+                "listener.additional_listeners names must be non-empty and unique, got '{}'",
+                additional.name
+            );
+        }
+        if additional.port == 0 || !listener_ports.insert(additional.port) {
+            bail!(
+                "listener.additional_listeners['{}'].port must be non-zero and not used by another listener",
+                additional.name
+            );
+        }
+        if let Some(domains) = &additional.domains {
+            validate_domains(&format!("listener.additional_listeners['{}'].domains", additional.name), domains)?;
+        }
+    }
+
+    // QUIC is always encrypted, so an HTTP/3 listener needs a certificate
+    if envoy.listener.http3 && envoy.listener.downstream_tls.is_none() {
+        bail!("listener.http3 requires listener.downstream_tls to be configured");
+    }
+
+    // Envoy only accepts downstream early data on QUIC, which always runs TLS 1.3
+    if envoy.listener.allow_early_data.is_some() && !envoy.listener.http3 {
+        bail!("listener.allow_early_data requires listener.http3 with listener.downstream_tls");
+    }
+
+    Ok(())
+}
+
+/// Local reply `json_format`: a non-empty object of strings, numbers, booleans or nested objects,
+/// whose strings only use well-formed `%COMMAND_OPERATOR%` tokens
+fn validate_local_reply_format(json_format: &serde_json::Map<String, serde_json::Value>) -> Result<()> {
+    if json_format.is_empty() {
+        bail!("listener.local_reply.json_format cannot be empty");
+    }
+
+    let operator = regex::Regex::new(r"%[A-Z][A-Z0-9_]*(\([^()]*\))?(:[0-9]+)?%").expect("valid regex");
+    let mut pending: Vec<(String, &serde_json::Value)> =
+        json_format.iter().map(|(key, value)| (key.clone(), value)).collect();
+    while let Some((key, value)) = pending.pop() {
+        match value {
+            serde_json::Value::String(text) => {
+                if operator.replace_all(text, "").contains('%') {
+                    bail!(
+                        "listener.local_reply.json_format.{} has a malformed command operator; use tokens like %RESPONSE_CODE%",
+                        key
+                    );
+                }
+            }
+            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {}
+            serde_json::Value::Object(fields) => {
+                pending.extend(fields.iter().map(|(field, value)| (format!("{}.{}", key, field), value)));
+            }
+            _ => bail!(
+                "listener.local_reply.json_format.{} must be a string, number, boolean or object",
+                key
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Virtual host domains must be a non-empty list of unique Envoy domain patterns
+fn validate_domains(field: &str, domains: &[String]) -> Result<()> {
+    if domains.is_empty() {
+        bail!("{} cannot be empty", field);
+    }
+    let mut seen = std::collections::HashSet::new();
+    for domain in domains {
+        if !is_valid_domain_pattern(domain) || !seen.insert(domain.to_ascii_lowercase()) {
+            bail!(
+                "{} entries must be unique domain patterns such as 'api.example.com', '*.example.com' or 'example.*', got '{}'",
+                field,
+                domain
+            );
+        }
+    }
     Ok(())
 }
 
+/// `*`, or a host with at most a leading or trailing `*` wildcard and an optional `:port`
+fn is_valid_domain_pattern(domain: &str) -> bool {
+    if domain == "*" {
+        return true;
+    }
+    let host = match domain.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok_and(|port| port != 0) => host,
+        Some(_) => return false,
+        None => domain,
+    };
+    let host = host.strip_prefix('*').or_else(|| host.strip_suffix('*')).unwrap_or(host);
+    !host.is_empty()
+        && host.len() <= 253
+        && host.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
 /// Validates timeout values (must be reasonable for network operations)
 fn validate_timeout(timeout_seconds: u64, field_name: &str) -> Result<()> {
     const MIN_TIMEOUT: u64 = 1; // At least 1 second
@@ -285,8 +532,9 @@ fn validate_storage_config(storage: &StorageConfig) -> Result<()> {
 mod tests {
     use super::*;
     use crate::config::{
-        ControlPlaneConfig, EnvoyGenerationConfig, HttpMethodsConfig, LoadBalancingConfig, LoggingConfig, TlsConfig,
-        StorageConfig, StorageLimitsConfig, StorageBehaviorConfig, HttpFiltersFeatureConfig, HttpFiltersLimitsConfig,
+        node_matches, ControlPlaneConfig, EnvoyGenerationConfig, HttpMethodsConfig, LoadBalancingConfig, LoggingConfig,
+        TlsConfig, StorageConfig, StorageLimitsConfig, StorageBehaviorConfig, HttpFiltersFeatureConfig,
+        HttpFiltersLimitsConfig,
     };
     use std::path::PathBuf;
 
@@ -297,11 +545,18 @@ mod tests {
                     rest_port: 8080,
                     xds_port: 18000,
                     host: "0.0.0.0".to_string(),
+                    xds_keepalive: XdsKeepaliveConfig::default(),
+                    strict_resource_types: false,
+                    node_match_mode: "exact".to_string(),
+                    reject_unknown_fields: false,
+                    last_known_good_grace_seconds: None,
+                    read_only: false,
                 },
                 tls: TlsConfig {
                     cert_path: "./certs/server.crt".to_string(),
                     key_path: "./certs/server.key".to_string(),
                     enabled: true,
+                    min_tls_version: "1.2".to_string(),
                 },
                 logging: LoggingConfig {
                     level: "info".to_string(),
@@ -324,7 +579,8 @@ mod tests {
                     jwt_secret: "test-secret-key".to_string(),
                     jwt_expiry_hours: 1,
                     jwt_issuer: "test-issuer".to_string(),
-                    password_hash_cost: 4,  // Low cost for fast tests
+                    password_hash_cost: MIN_BCRYPT_COST,
+                    password_hash_algorithm: "bcrypt".to_string(),
                 },
                 storage: StorageConfig {
                     limits: StorageLimitsConfig {
@@ -336,6 +592,7 @@ mod tests {
                     behavior: StorageBehaviorConfig {
                         reject_on_capacity: true,
                         enable_metrics: false,  // Disabled for tests to reduce noise
+                        abort_on_conversion_error: false,
                     },
                 },
                 http_filters: HttpFiltersFeatureConfig {
@@ -354,6 +611,7 @@ mod tests {
                         max_filters_per_route: 5,
                         max_global_filters: 20,
                     },
+                    enable_compression: false,
                 },
             },
             envoy_generation: EnvoyGenerationConfig {
@@ -366,12 +624,30 @@ mod tests {
                 listener: crate::config::ListenerConfig {
                     binding_address: "0.0.0.0".to_string(),
                     default_port: 10000,
+                    forwarded_headers: crate::config::ForwardedHeadersConfig::default(),
+                    http3: false,
+                    downstream_tls: None,
+                    default_request_timeout_seconds: None,
+                    per_connection_buffer_limit_bytes: None,
+                    exact_connection_balance: false,
+                    drain_type: "DEFAULT".to_string(),
+                    drain_timeout_seconds: None,
+                    server_header_transformation: "OVERWRITE".to_string(),
+                    server_name: None,
+                    suppress_envoy_headers: false,
+                    access_log: None,
+                    additional_listeners: vec![],
+                    allow_early_data: None,
+                    local_reply: None,
                 },
                 cluster: crate::config::ClusterConfig {
                     connect_timeout_seconds: 5,
                     discovery_type: "STRICT_DNS".to_string(),
                     dns_lookup_family: "V4_ONLY".to_string(),
                     default_protocol: "TCP".to_string(),
+                    dns_resolution_check: crate::config::DnsResolutionCheckConfig::default(),
+                    max_buffer_limit_bytes: 16 * 1024 * 1024,
+                    reject_duplicate_endpoints: false,
                 },
                 naming: crate::config::NamingConfig {
                     listener_name: "listener_0".to_string(),
@@ -522,6 +798,55 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_listener_buffer_limit_must_be_within_ceiling() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.per_connection_buffer_limit_bytes = Some(0);
+        assert!(validate_config(&config).is_err());
+
+        config.envoy_generation.listener.per_connection_buffer_limit_bytes =
+            Some(config.envoy_generation.cluster.max_buffer_limit_bytes + 1);
+        assert!(validate_config(&config).is_err());
+
+        config.envoy_generation.listener.per_connection_buffer_limit_bytes = Some(1024 * 1024);
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_listener_access_log_filters_validated() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.access_log = Some(crate::config::AccessLogConfig {
+            path: "/dev/stdout".to_string(),
+            min_status_code: Some(400),
+            sample_fraction: Some(1.5),
+        });
+        assert!(validate_config(&config).is_err());
+
+        if let Some(access_log) = config.envoy_generation.listener.access_log.as_mut() {
+            access_log.sample_fraction = Some(0.25);
+        }
+        assert!(validate_config(&config).is_ok());
+
+        if let Some(access_log) = config.envoy_generation.listener.access_log.as_mut() {
+            access_log.min_status_code = Some(42);
+        }
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_listener_drain_settings_validated() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.drain_type = "IMMEDIATE".to_string();
+        assert!(validate_config(&config).is_err());
+
+        config.envoy_generation.listener.drain_type = "MODIFY_ONLY".to_string();
+        config.envoy_generation.listener.drain_timeout_seconds = Some(0);
+        assert!(validate_config(&config).is_err());
+
+        config.envoy_generation.listener.drain_timeout_seconds = Some(30);
+        assert!(validate_config(&config).is_ok());
+    }
+
     #[test]
     fn test_admin_port_validation() {
         let mut config = create_test_config();
@@ -534,4 +859,296 @@ mod tests {
             .to_string()
             .contains("admin.port cannot be 0"));
     }
+
+    #[test]
+    fn test_xds_keepalive_defaults_applied() {
+        let config = create_test_config();
+        let keepalive = &config.control_plane.server.xds_keepalive;
+
+        assert_eq!(keepalive.interval(), std::time::Duration::from_secs(30));
+        assert_eq!(keepalive.timeout(), std::time::Duration::from_secs(10));
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_xds_keepalive_timeout_must_be_shorter_than_interval() {
+        let mut config = create_test_config();
+        config.control_plane.server.xds_keepalive.interval_seconds = 20;
+        config.control_plane.server.xds_keepalive.timeout_seconds = 20;
+
+        let result = validate_config(&config);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("less than interval_seconds"));
+    }
+
+    #[test]
+    fn test_negative_xff_num_trusted_hops_rejected() {
+        let yaml = "use_remote_address: true\nxff_num_trusted_hops: -1\nappend_x_forwarded_host: false\n";
+        let result: std::result::Result<crate::config::ForwardedHeadersConfig, _> = serde_yaml::from_str(yaml);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_http3_requires_downstream_tls() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.http3 = true;
+
+        let result = validate_config(&config);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("requires listener.downstream_tls"));
+
+        config.envoy_generation.listener.downstream_tls = Some(crate::config::DownstreamTlsConfig {
+            cert_path: "/etc/envoy/certs/listener.crt".to_string(),
+            key_path: "/etc/envoy/certs/listener.key".to_string(),
+        });
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_out_of_range_bcrypt_cost_rejected() {
+        let mut config = create_test_config();
+
+        config.control_plane.authentication.password_hash_cost = MAX_BCRYPT_COST + 1;
+        let result = validate_config(&config);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("password_hash_cost must be between"));
+
+        config.control_plane.authentication.password_hash_cost = MIN_BCRYPT_COST - 1;
+        assert!(validate_config(&config).is_err());
+
+        config.control_plane.authentication.password_hash_cost = 12;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_test_profile_bcrypt_cost_only_valid_for_tests() {
+        let test_profile = AppConfig::create_test_config();
+        assert_eq!(test_profile.control_plane.authentication.password_hash_cost, MIN_TEST_BCRYPT_COST);
+
+        let mut config = create_test_config();
+        config.control_plane.authentication.password_hash_cost = MIN_TEST_BCRYPT_COST;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_password_hash_algorithm_validated() {
+        let mut config = create_test_config();
+
+        config.control_plane.authentication.password_hash_algorithm = "argon2".to_string();
+        assert!(validate_config(&config).is_ok());
+
+        // The bcrypt cost range doesn't apply to argon2
+        config.control_plane.authentication.password_hash_cost = MAX_BCRYPT_COST + 1;
+        assert!(validate_config(&config).is_ok());
+
+        config.control_plane.authentication.password_hash_algorithm = "md5".to_string();
+        let result = validate_config(&config);
+        assert!(result.unwrap_err().to_string().contains("password_hash_algorithm must be one of bcrypt, argon2"));
+    }
+
+    #[test]
+    fn test_config_file_bcrypt_cost_rejected_at_load() {
+        let yaml = std::fs::read_to_string("config.yaml").unwrap();
+        let yaml = yaml.replace("password_hash_cost: 12", "password_hash_cost: 31");
+        let config: AppConfig = serde_yaml::from_str(&yaml).unwrap();
+
+        let result = validate_config(&config);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("got 31"));
+    }
+
+    #[test]
+    fn test_e2e_config_file_loads() {
+        // docker-compose.test.*.yml mount this file as the control plane's config
+        let config = AppConfig::load_from("config.e2e.yaml").unwrap();
+        assert!(config.control_plane.authentication.password_hash_cost >= MIN_BCRYPT_COST);
+    }
+
+    #[tokio::test]
+    async fn test_config_load_retries_until_file_appears() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("config.yaml");
+        let writer_target = target.clone();
+
+        // Simulate a config volume that is mounted shortly after startup
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(150));
+            let yaml = std::fs::read_to_string("config.yaml").unwrap();
+            let staged = writer_target.with_extension("tmp");
+            std::fs::write(&staged, yaml).unwrap();
+            std::fs::rename(staged, writer_target).unwrap();
+        });
+
+        let path = dir.path().join("config");
+        let path = path.to_str().unwrap();
+        assert!(AppConfig::load_from(path).is_err(), "file should not exist yet");
+
+        let retry = crate::config::ConfigLoadRetry {
+            max_attempts: 10,
+            initial_backoff: std::time::Duration::from_millis(50),
+            max_backoff: std::time::Duration::from_millis(200),
+        };
+        let config = AppConfig::load_with_retry(path, &retry).await.unwrap();
+        writer.join().unwrap();
+
+        assert!(target.exists());
+        assert_eq!(config.control_plane.server.xds_port, 18000);
+    }
+
+    #[tokio::test]
+    async fn test_config_load_does_not_retry_invalid_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = std::fs::read_to_string("config.yaml").unwrap();
+        let yaml = yaml.replace("password_hash_cost: 12", "password_hash_cost: 31");
+        std::fs::write(dir.path().join("config.yaml"), yaml).unwrap();
+
+        let path = dir.path().join("config");
+        let retry = crate::config::ConfigLoadRetry {
+            max_attempts: 5,
+            initial_backoff: std::time::Duration::from_secs(10),
+            max_backoff: std::time::Duration::from_secs(10),
+        };
+
+        // A retry would sleep for the whole backoff; validation errors must fail straight away
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            AppConfig::load_with_retry(path.to_str().unwrap(), &retry),
+        )
+        .await
+        .expect("invalid config should not be retried");
+        assert!(format!("{:#}", result.unwrap_err()).contains("got 31"));
+    }
+
+    #[test]
+    fn test_additional_listeners_must_be_unique() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.additional_listeners = vec![crate::config::AdditionalListenerConfig {
+            name: "admin_listener".to_string(),
+            port: 10001,
+            domains: None,
+        }];
+        assert!(validate_config(&config).is_ok());
+
+        // Port already taken by the main listener
+        config.envoy_generation.listener.additional_listeners[0].port = 10000;
+        assert!(validate_config(&config).is_err());
+
+        // Name already taken by the main listener
+        config.envoy_generation.listener.additional_listeners[0].port = 10001;
+        config.envoy_generation.listener.additional_listeners[0].name = "main_listener".to_string();
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_local_reply_format_validated() {
+        let mut config = create_test_config();
+        let format = |value: serde_json::Value| {
+            Some(crate::config::LocalReplyFormatConfig {
+                json_format: value.as_object().unwrap().clone(),
+            })
+        };
+
+        config.envoy_generation.listener.local_reply = format(serde_json::json!({
+            "status": "%RESPONSE_CODE%",
+            "error": {"message": "%LOCAL_REPLY_BODY%", "path": "%REQ(:path)%"},
+            "branded": true
+        }));
+        assert!(validate_config(&config).is_ok());
+
+        for invalid in [
+            serde_json::json!({}),
+            serde_json::json!({"status": "%RESPONSE_CODE"}),
+            serde_json::json!({"error": {"details": ["%LOCAL_REPLY_BODY%"]}}),
+        ] {
+            config.envoy_generation.listener.local_reply = format(invalid);
+            assert!(validate_config(&config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_virtual_host_domains_validated() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.additional_listeners = vec![crate::config::AdditionalListenerConfig {
+            name: "tenant_listener".to_string(),
+            port: 10001,
+            domains: Some(vec!["*.tenant.example.com".to_string(), "tenant.*".to_string()]),
+        }];
+        assert!(validate_config(&config).is_ok());
+
+        for domains in [vec![], vec!["api.*.example.com"], vec!["api.example.com:0"], vec!["a.com", "A.com"]] {
+            config.envoy_generation.listener.additional_listeners[0].domains =
+                Some(domains.into_iter().map(String::from).collect());
+            assert!(validate_config(&config).is_err());
+        }
+
+        config.envoy_generation.listener.additional_listeners.clear();
+        config.envoy_generation.naming.default_domains = vec!["bad domain".to_string()];
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_min_tls_version_validated() {
+        let mut config = create_test_config();
+        config.control_plane.tls.min_tls_version = "1.3".to_string();
+        assert!(validate_config(&config).is_ok());
+
+        config.control_plane.tls.min_tls_version = "1.1".to_string();
+        let result = validate_config(&config);
+        assert!(result.unwrap_err().to_string().contains("tls.min_tls_version"));
+    }
+
+    #[test]
+    fn test_rate_limit_before_authentication_rejected() {
+        let mut config = create_test_config();
+        config.control_plane.http_filters.default_order = vec!["rate_limit".to_string(), "authentication".to_string()];
+        let result = validate_config(&config);
+        assert!(result.unwrap_err().to_string().contains("must list authentication before rate_limit"));
+
+        config.control_plane.http_filters.default_order.reverse();
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_node_match_mode_validated() {
+        let mut config = create_test_config();
+        config.control_plane.server.node_match_mode = "glob".to_string();
+        let result = validate_config(&config);
+        assert!(result.unwrap_err().to_string().contains("server.node_match_mode"));
+    }
+
+    #[test]
+    fn test_prefix_node_match_serves_tagged_resource() {
+        let mut config = create_test_config();
+        assert!(!node_matches(&config.control_plane.server.node_match_mode, "prod-", "prod-1").unwrap());
+
+        config.control_plane.server.node_match_mode = "prefix".to_string();
+        assert!(validate_config(&config).is_ok());
+        assert!(node_matches("prefix", "prod-", "prod-1").unwrap());
+        assert!(!node_matches("prefix", "prod-", "staging-1").unwrap());
+    }
+
+    #[test]
+    fn test_regex_node_match_requires_valid_pattern() {
+        assert!(node_matches("regex", "prod-[0-9]+", "prod-1").unwrap());
+        assert!(!node_matches("regex", "prod-[0-9]+", "prod-1-canary").unwrap());
+        assert!(node_matches("regex", "prod-[", "prod-1").is_err());
+    }
+
+    #[test]
+    fn test_allow_early_data_requires_http3() {
+        let mut config = create_test_config();
+        config.envoy_generation.listener.allow_early_data = Some(true);
+        let result = validate_config(&config);
+        assert!(result.unwrap_err().to_string().contains("listener.allow_early_data"));
+
+        config.envoy_generation.listener.http3 = true;
+        config.envoy_generation.listener.downstream_tls = Some(crate::config::DownstreamTlsConfig {
+            cert_path: "/etc/envoy/certs/listener.crt".to_string(),
+            key_path: "/etc/envoy/certs/listener.key".to_string(),
+        });
+        assert!(validate_config(&config).is_ok());
+    }
 }
@@ -521,4 +521,29 @@ mod tests {
         assert!(bootstrap_yaml.contains("listeners:"));
         assert!(bootstrap_yaml.contains("admin:"));
     }
+
+    #[test]
+    fn test_bootstrap_config_uses_admin_and_control_plane_cluster() {
+        let mut config = AppConfig::create_test_config();
+        config.envoy_generation.admin.host = "0.0.0.0".to_string();
+        config.envoy_generation.admin.port = 19001;
+        config.envoy_generation.bootstrap.control_plane_cluster_name = "xds_primary".to_string();
+
+        let bootstrap_yaml = ConfigGenerator::generate_bootstrap_config(&config)
+            .expect("Should generate bootstrap config");
+        let bootstrap: serde_yaml::Value =
+            serde_yaml::from_str(&bootstrap_yaml).expect("Bootstrap should be valid YAML");
+
+        let admin_address = &bootstrap["admin"]["address"]["socket_address"];
+        assert_eq!(admin_address["address"].as_str(), Some("0.0.0.0"));
+        assert_eq!(admin_address["port_value"].as_u64(), Some(19001));
+
+        // ADS must point at the static cluster that targets this control plane
+        let ads_cluster = &bootstrap["dynamic_resources"]["ads_config"]["grpc_services"][0]["envoy_grpc"]["cluster_name"];
+        assert_eq!(ads_cluster.as_str(), Some("xds_primary"));
+        let static_cluster = &bootstrap["static_resources"]["clusters"][0];
+        assert_eq!(static_cluster["name"].as_str(), Some("xds_primary"));
+        let cp_address = &static_cluster["load_assignment"]["endpoints"][0]["lb_endpoints"][0]["endpoint"]["address"]["socket_address"];
+        assert_eq!(cp_address["port_value"].as_u64(), Some(config.control_plane.server.xds_port as u64));
+    }
 }
@@ -3,6 +3,7 @@ pub mod auth;
 pub mod auth_handlers;
 pub mod auth_middleware;
 pub mod config;
+pub mod password_hashing;
 pub mod envoy;
 pub mod rbac;
 pub mod security;
@@ -4,6 +4,7 @@ mod auth_handlers;
 mod auth_middleware;
 mod config;
 mod envoy;
+mod password_hashing;
 mod rbac;
 mod security;
 mod storage;
@@ -11,23 +12,66 @@ mod validation;
 mod xds;
 
 use auth::JwtKeys;
-use config::AppConfig;
+use config::{AppConfig, ConfigLoadRetry, SharedConfig};
 use rbac::RbacEnforcer;
+use std::future::Future;
+use std::pin::Pin;
 use storage::ConfigStore;
 use tokio::net::TcpListener;
 use tonic::transport::Server;
+use tracing_subscriber::{prelude::*, reload, EnvFilter, Registry};
+
+/// Handle for swapping the active log filter when `logging.level` is reloaded
+type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Running xDS gRPC server, boxed so the TLS and plaintext variants share one type
+type XdsServeFuture = Pin<Box<dyn Future<Output = Result<(), tonic::transport::Error>> + Send>>;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
-
-    // Load configuration and create storage
-    let config = std::sync::Arc::new(AppConfig::load()?);
+    // Initialize logging behind a reloadable filter; RUST_LOG takes precedence over logging.level at startup
+    let (log_filter, log_filter_handle) =
+        reload::Layer::new(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")));
+    tracing_subscriber::registry()
+        .with(log_filter)
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // Load configuration (retrying while a mounted file becomes available) and create storage
+    let config = AppConfig::load_with_retry("config", &ConfigLoadRetry::from_env()?).await?;
+    if std::env::var_os("RUST_LOG").is_none() {
+        set_log_level(&log_filter_handle, &config.control_plane.logging.level);
+    }
+    let shared_config = SharedConfig::new(config);
+    let config = shared_config.current();
     let store = ConfigStore::with_config(config.control_plane.storage.clone());
 
+    // Fail fast if any stored resource no longer converts to a valid xDS resource
+    let self_test_failures = xds::conversion::run_conversion_self_test(&store, &config);
+    if !self_test_failures.is_empty() {
+        eprintln!(
+            "⚠️  Conversion self-test found {} invalid resource(s)",
+            self_test_failures.len()
+        );
+        if config.control_plane.storage.behavior.abort_on_conversion_error {
+            anyhow::bail!(
+                "Aborting startup: {} stored resource(s) failed xDS conversion",
+                self_test_failures.len()
+            );
+        }
+    }
+
     // Create xDS server
-    let xds_server = xds::SimpleXdsServer::new(store.clone());
+    let xds_server = xds::SimpleXdsServer::new(store.clone())
+        .with_strict_resource_types(config.control_plane.server.strict_resource_types)
+        .with_node_match_mode(config.control_plane.server.node_match_mode.clone())
+        .with_last_known_good_grace(
+            config
+                .control_plane
+                .server
+                .last_known_good_grace_seconds
+                .map(std::time::Duration::from_secs),
+        );
 
     // Initialize authentication components
     let jwt_keys = JwtKeys::new(config.control_plane.authentication.clone());
@@ -36,7 +80,10 @@ async fn main() -> anyhow::Result<()> {
     let rbac = RbacEnforcer::new_simple().await?;
 
     // Create API router with all components
-    let app = api::create_router(store.clone(), xds_server.clone(), jwt_keys, rbac, config.clone());
+    let app = api::create_router(store.clone(), xds_server.clone(), jwt_keys, rbac, shared_config.clone());
+
+    // Re-read config.yaml on SIGHUP and hot-swap the fields that are safe to change
+    spawn_config_reload_on_sighup(shared_config, log_filter_handle);
 
     // Start both servers concurrently
     let rest_addr = format!(
@@ -68,25 +115,43 @@ async fn main() -> anyhow::Result<()> {
     println!("  - AggregatedDiscoveryService (ADS)");
 
     // Create server with optional TLS based on configuration
-    let xds_service = if config.control_plane.tls.enabled {
+    let tls = &config.control_plane.tls;
+    let keepalive = &config.control_plane.server.xds_keepalive;
+    let xds_service: XdsServeFuture = if tls.enabled && tls.min_tls_version == "1.3" {
+        println!("🔒 TLS enabled (TLS 1.3 only) - creating secure gRPC server");
+
+        // tonic's built-in TLS can't pin the protocol version, so accept connections through rustls directly
+        let xds_listener = TcpListener::bind(xds_server_addr).await?;
+        let incoming = security::create_tls_incoming(xds_listener, tls)?;
+
+        Box::pin(
+            xds::with_xds_keepalive(Server::builder(), keepalive)
+                .add_service(xds::AggregatedDiscoveryServiceServer::new(xds_server))
+                .serve_with_incoming(incoming),
+        )
+    } else if tls.enabled {
         println!("🔒 TLS enabled - creating secure gRPC server");
 
         // Load TLS identity from configuration
-        let identity = security::load_tls_identity(&config.control_plane.tls)?;
+        let identity = security::load_tls_identity(tls)?;
 
         // Create TLS-enabled server
-        let mut tls_server = security::create_tls_server(identity)?;
+        let tls_server = security::create_tls_server(identity)?;
 
-        tls_server
-            .add_service(xds::AggregatedDiscoveryServiceServer::new(xds_server))
-            .serve(xds_server_addr)
+        Box::pin(
+            xds::with_xds_keepalive(tls_server, keepalive)
+                .add_service(xds::AggregatedDiscoveryServiceServer::new(xds_server))
+                .serve(xds_server_addr),
+        )
     } else {
         println!("🔓 TLS disabled - creating plain gRPC server");
 
         // Create plain gRPC server
-        Server::builder()
-            .add_service(xds::AggregatedDiscoveryServiceServer::new(xds_server))
-            .serve(xds_server_addr)
+        Box::pin(
+            xds::with_xds_keepalive(Server::builder(), keepalive)
+                .add_service(xds::AggregatedDiscoveryServiceServer::new(xds_server))
+                .serve(xds_server_addr),
+        )
     };
 
     // Run both servers concurrently
@@ -105,3 +170,60 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn set_log_level(handle: &LogFilterHandle, level: &str) {
+    match EnvFilter::try_new(level) {
+        Ok(filter) => {
+            if let Err(e) = handle.reload(filter) {
+                tracing::warn!("Failed to apply log level '{}': {}", level, e);
+            }
+        }
+        Err(e) => tracing::warn!("Ignoring invalid log level '{}': {}", level, e),
+    }
+}
+
+#[cfg(unix)]
+fn spawn_config_reload_on_sighup(shared_config: SharedConfig, log_filter_handle: LogFilterHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                tracing::warn!("Config reload on SIGHUP unavailable: {}", e);
+                return;
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            tracing::info!("SIGHUP received, reloading configuration");
+            let reloaded = match AppConfig::load() {
+                Ok(reloaded) => reloaded,
+                Err(e) => {
+                    tracing::error!("Config reload failed, keeping current configuration: {:#}", e);
+                    continue;
+                }
+            };
+
+            let report = shared_config.apply_reload(&reloaded);
+            if report.applied.iter().any(|field| field == "control_plane.logging.level") {
+                set_log_level(&log_filter_handle, &reloaded.control_plane.logging.level);
+            }
+
+            if report.is_empty() {
+                tracing::info!("Config reload found no changes");
+            }
+            for field in &report.applied {
+                tracing::info!("Config reload applied '{}'", field);
+            }
+            for field in &report.requires_restart {
+                tracing::warn!("Config field '{}' changed but requires a restart to take effect", field);
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn spawn_config_reload_on_sighup(_shared_config: SharedConfig, _log_filter_handle: LogFilterHandle) {
+    tracing::info!("Config reload on SIGHUP is only supported on Unix platforms");
+}
@@ -0,0 +1,65 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+use crate::config::AuthenticationConfig;
+
+/// Algorithms accepted for `authentication.password_hash_algorithm`
+pub const SUPPORTED_ALGORITHMS: &[&str] = &["bcrypt", "argon2"];
+
+/// Hashes new passwords and verifies passwords against hashes it produced
+pub trait PasswordHashScheme {
+    fn hash(&self, password: &str) -> Result<String, String>;
+    fn verify(&self, password: &str, hash: &str) -> bool;
+}
+
+/// bcrypt with a configurable cost
+pub struct BcryptScheme {
+    pub cost: u32,
+}
+
+impl PasswordHashScheme for BcryptScheme {
+    fn hash(&self, password: &str) -> Result<String, String> {
+        bcrypt::hash(password, self.cost).map_err(|e| format!("bcrypt hashing failed: {}", e))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> bool {
+        bcrypt::verify(password, hash).unwrap_or(false)
+    }
+}
+
+/// Argon2id with the crate's default parameters, stored as a PHC string (`$argon2id$...`)
+pub struct Argon2Scheme;
+
+impl PasswordHashScheme for Argon2Scheme {
+    fn hash(&self, password: &str) -> Result<String, String> {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| format!("argon2 hashing failed: {}", e))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> bool {
+        PasswordHash::new(hash)
+            .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+            .unwrap_or(false)
+    }
+}
+
+/// Scheme used to hash new passwords, as selected by the authentication config
+pub fn configured_scheme(config: &AuthenticationConfig) -> Box<dyn PasswordHashScheme> {
+    match config.password_hash_algorithm.as_str() {
+        "argon2" => Box::new(Argon2Scheme),
+        _ => Box::new(BcryptScheme { cost: config.password_hash_cost }),
+    }
+}
+
+/// Verify a password with the scheme that produced `hash`, whatever the configured algorithm,
+/// so existing bcrypt hashes keep verifying after switching to argon2
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    if hash.starts_with("$argon2") {
+        Argon2Scheme.verify(password, hash)
+    } else {
+        BcryptScheme { cost: 0 }.verify(password, hash)
+    }
+}
@@ -63,6 +63,7 @@ m = g(r.sub, p.sub) && r.obj == p.obj && r.act == p.act
         enforcer.add_policy(vec!["admin".to_string(), "clusters".to_string(), "delete".to_string()]).await?;
         enforcer.add_policy(vec!["admin".to_string(), "config".to_string(), "generate".to_string()]).await?;
         enforcer.add_policy(vec!["admin".to_string(), "system".to_string(), "read".to_string()]).await?;
+        enforcer.add_policy(vec!["admin".to_string(), "system".to_string(), "write".to_string()]).await?;
 
         enforcer.add_policy(vec!["api_developer".to_string(), "routes".to_string(), "read".to_string()]).await?;
         enforcer.add_policy(vec!["api_developer".to_string(), "routes".to_string(), "write".to_string()]).await?;
@@ -165,18 +166,34 @@ pub fn extract_resource_and_action(method: &str, path: &str) -> (String, String)
         // Routes endpoints
         ("GET", p) if p.starts_with("/routes") => ("routes".to_string(), "read".to_string()),
         ("POST", "/routes") => ("routes".to_string(), "write".to_string()),
+        ("POST", p) if p.starts_with("/routes/") && p.ends_with("/filters") => ("routes".to_string(), "write".to_string()),
         ("PUT", p) if p.starts_with("/routes/") => ("routes".to_string(), "write".to_string()),
+        ("PATCH", p) if p.starts_with("/routes/") => ("routes".to_string(), "write".to_string()),
         ("DELETE", p) if p.starts_with("/routes/") => ("routes".to_string(), "delete".to_string()),
         
         // Clusters endpoints  
         ("GET", p) if p.starts_with("/clusters") => ("clusters".to_string(), "read".to_string()),
         ("POST", "/clusters") => ("clusters".to_string(), "write".to_string()),
+        ("POST", p) if p.starts_with("/clusters/") && p.ends_with("/endpoints") => ("clusters".to_string(), "write".to_string()),
         ("PUT", p) if p.starts_with("/clusters/") => ("clusters".to_string(), "write".to_string()),
+        ("DELETE", p) if p.starts_with("/clusters/") && p.contains("/endpoints/") => ("clusters".to_string(), "write".to_string()),
         ("DELETE", p) if p.starts_with("/clusters/") => ("clusters".to_string(), "delete".to_string()),
         
+        // Filter dry-run (no state change)
+        ("POST", p) if p.starts_with("/http-filters/") && p.ends_with("/simulate") => ("system".to_string(), "read".to_string()),
+        
         // Config generation
         ("POST", "/generate-config") => ("config".to_string(), "generate".to_string()),
         ("GET", "/generate-bootstrap") => ("config".to_string(), "generate".to_string()),
+        ("POST", "/config/diff") => ("config".to_string(), "generate".to_string()),
+        
+        // Admin inspection endpoints; the effective config reveals deployment details, so admins only
+        ("GET", "/admin/config") => ("system".to_string(), "write".to_string()),
+        ("GET", p) if p.starts_with("/admin/") => ("system".to_string(), "read".to_string()),
+        ("POST", p) if p.starts_with("/admin/") => ("system".to_string(), "write".to_string()),
+        
+        // Live change stream
+        ("GET", "/events") => ("system".to_string(), "read".to_string()),
         
         // HTTP methods endpoint (public read access)
         ("GET", "/supported-http-methods") => ("system".to_string(), "read".to_string()),
@@ -202,11 +219,26 @@ mod tests {
         let (resource, action) = extract_resource_and_action("DELETE", "/clusters/test-cluster");
         assert_eq!(resource, "clusters");
         assert_eq!(action, "delete");
+
+        // Removing a single endpoint only modifies the cluster
+        let (resource, action) = extract_resource_and_action("DELETE", "/clusters/test-cluster/endpoints/10.0.0.1/8080");
+        assert_eq!(resource, "clusters");
+        assert_eq!(action, "write");
         
         // Test config generation
         let (resource, action) = extract_resource_and_action("POST", "/generate-config");
         assert_eq!(resource, "config");
         assert_eq!(action, "generate");
+        
+        // Test admin operations
+        let (resource, action) = extract_resource_and_action("POST", "/admin/push");
+        assert_eq!(resource, "system");
+        assert_eq!(action, "write");
+
+        // Effective config is restricted to roles with system write
+        let (resource, action) = extract_resource_and_action("GET", "/admin/config");
+        assert_eq!(resource, "system");
+        assert_eq!(action, "write");
     }
     
     #[tokio::test]
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::models::{Cluster, HttpFilter, Route, RouteFilters};
+
+/// Full desired state of the store, as submitted for diffing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    #[serde(default)]
+    pub routes: Vec<Route>,
+    #[serde(default)]
+    pub clusters: Vec<Cluster>,
+    #[serde(default)]
+    pub http_filters: Vec<HttpFilter>,
+    #[serde(default)]
+    pub route_filters: Vec<RouteFilters>,
+}
+
+/// Identifies a resource that would be created or deleted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceRef {
+    pub resource_type: String,
+    pub name: String,
+}
+
+/// A single top-level field whose value would change
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub current: serde_json::Value,
+    pub desired: serde_json::Value,
+}
+
+/// A resource present on both sides with differing fields
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceUpdate {
+    pub resource_type: String,
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Changes needed to move the current store to the desired state
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigDiff {
+    pub created: Vec<ResourceRef>,
+    pub updated: Vec<ResourceUpdate>,
+    pub deleted: Vec<ResourceRef>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Compare two snapshots resource by resource without applying anything
+pub fn diff_snapshots(current: &ConfigSnapshot, desired: &ConfigSnapshot) -> ConfigDiff {
+    let mut diff = ConfigDiff::default();
+    diff_resources(&mut diff, "Route", &current.routes, &desired.routes, |r| r.name.clone());
+    diff_resources(&mut diff, "Cluster", &current.clusters, &desired.clusters, |c| c.name.clone());
+    diff_resources(&mut diff, "HttpFilter", &current.http_filters, &desired.http_filters, |f| f.name.clone());
+    diff_resources(&mut diff, "RouteFilters", &current.route_filters, &desired.route_filters, |rf| {
+        rf.route_name.clone()
+    });
+    diff
+}
+
+fn diff_resources<T: Serialize>(
+    diff: &mut ConfigDiff,
+    resource_type: &str,
+    current: &[T],
+    desired: &[T],
+    key: impl Fn(&T) -> String,
+) {
+    let current: BTreeMap<String, serde_json::Value> = current.iter().map(|r| (key(r), to_value(r))).collect();
+    let desired: BTreeMap<String, serde_json::Value> = desired.iter().map(|r| (key(r), to_value(r))).collect();
+
+    for (name, desired_value) in &desired {
+        match current.get(name) {
+            None => diff.created.push(ResourceRef {
+                resource_type: resource_type.to_string(),
+                name: name.clone(),
+            }),
+            Some(current_value) => {
+                let changes = diff_fields(current_value, desired_value);
+                if !changes.is_empty() {
+                    diff.updated.push(ResourceUpdate {
+                        resource_type: resource_type.to_string(),
+                        name: name.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+
+    for name in current.keys().filter(|name| !desired.contains_key(*name)) {
+        diff.deleted.push(ResourceRef {
+            resource_type: resource_type.to_string(),
+            name: name.clone(),
+        });
+    }
+}
+
+fn to_value<T: Serialize>(resource: &T) -> serde_json::Value {
+    serde_json::to_value(resource).unwrap_or(serde_json::Value::Null)
+}
+
+/// Field-level comparison of two serialized resources (top-level fields only)
+fn diff_fields(current: &serde_json::Value, desired: &serde_json::Value) -> Vec<FieldChange> {
+    let empty = serde_json::Map::new();
+    let current_fields = current.as_object().unwrap_or(&empty);
+    let desired_fields = desired.as_object().unwrap_or(&empty);
+
+    let mut fields: Vec<&String> = current_fields.keys().chain(desired_fields.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let current = current_fields.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            let desired = desired_fields.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            (current != desired).then(|| FieldChange {
+                field: field.clone(),
+                current,
+                desired,
+            })
+        })
+        .collect()
+}
@@ -1,9 +1,13 @@
 use thiserror::Error;
 
+pub mod diff;
 pub mod models;
+pub mod patch;
 pub mod store;
 
+pub use diff::*;
 pub use models::*;
+pub use patch::*;
 pub use store::*;
 
 #[derive(Error, Debug, Clone)]
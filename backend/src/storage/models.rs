@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 /// Load balancing policy for clusters
@@ -56,26 +57,146 @@ impl LoadBalancingPolicy {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Route {
     pub name: String,         // Primary identifier aligned with Envoy conventions
     pub path: String,
     pub cluster_name: String,
     pub prefix_rewrite: Option<String>,
     pub http_methods: Option<Vec<String>>, // GET, POST, PUT, DELETE, etc.
+    pub regex_rewrite: Option<RegexRewrite>, // Mutually exclusive with prefix_rewrite
+    pub idle_timeout_seconds: Option<u64>, // Per-route stream idle timeout
+    #[serde(default)]
+    pub maintenance_mode: bool, // Serve a 503 direct response instead of proxying to cluster_name
+    pub maintenance_body: Option<String>, // Body returned while in maintenance mode
+    pub retry_policy: Option<RetryPolicy>, // Retries with optional exponential backoff
+    pub timeout_seconds: Option<u64>, // Per-route request timeout; overrides the global default
+    pub labels: Option<HashMap<String, String>>, // Free-form key/value labels for grouping and filtering
+    pub response_headers_to_add: Option<HashMap<String, String>>, // Added to responses, including maintenance direct responses
+    pub scheme_match: Option<String>, // Restrict matching to "http" or "https" requests
+    pub weighted_clusters: Option<WeightedClusters>, // Splits traffic across clusters instead of cluster_name
+    pub routing_priority: Option<String>, // "default" or "high" upstream connection pool priority
+    pub description: Option<String>, // Human-readable label; not used for matching
+    pub content_type_match: Option<ContentTypeMatch>, // Match on the accept or content-type media type
+    pub listener: Option<String>, // Listener whose route configuration serves this route; the main listener when unset
+    pub case_sensitive: Option<bool>, // Path prefix matching is case-sensitive unless set to false
+    pub request_headers_to_remove: Option<Vec<String>>, // Stripped from the request before it is forwarded upstream
+    pub disabled_filters: Option<Vec<String>>, // HTTP filters (by name) switched off for this route, e.g. auth on public paths
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Regex-based path rewrite applied before forwarding upstream
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RegexRewrite {
+    pub pattern: String,
+    pub substitution: String,
+}
+
+/// Route-level retry policy mapped onto Envoy's RetryPolicy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    #[serde(default)]
+    pub retry_on: String, // Envoy retry conditions, e.g. "5xx,retriable-status-codes"
+    pub num_retries: Option<u32>,
+    #[serde(default)]
+    pub retriable_status_codes: Vec<u32>,
+    pub retry_back_off: Option<RetryBackOff>,
+    #[serde(default)]
+    pub grpc_retry_on: Vec<String>, // gRPC status conditions, e.g. ["cancelled", "unavailable"]
+}
+
+impl RetryPolicy {
+    /// Envoy `retry_on` value combining the HTTP conditions with the gRPC ones
+    pub fn combined_retry_on(&self) -> String {
+        Some(self.retry_on.trim())
+            .filter(|conditions| !conditions.is_empty())
+            .into_iter()
+            .chain(self.grpc_retry_on.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Route on the media type a client sends (`content-type`) or asks for (`accept`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContentTypeMatch {
+    pub header: String, // "accept" or "content-type"
+    pub media_type: String, // e.g. "application/json"; parameters like charset are not part of the match
+}
+
+/// Traffic split across several clusters, adjustable at runtime when a key prefix is set
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeightedClusters {
+    pub clusters: Vec<ClusterWeight>,
+    pub runtime_key_prefix: Option<String>, // Envoy reads `<prefix>.<cluster>` runtime keys to override weights
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterWeight {
+    pub name: String,
+    pub weight: u32,
+}
+
+/// Exponential backoff between retry attempts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryBackOff {
+    pub base_interval_ms: u64,
+    pub max_interval_ms: u64,
+}
+
+/// Active HTTP health check probing every endpoint of a cluster
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthCheck {
+    pub path: String,
+    pub interval_seconds: u32,
+    pub timeout_seconds: u32,
+    pub healthy_threshold: Option<u32>, // Consecutive passes before a host is healthy (default 2)
+    pub unhealthy_threshold: Option<u32>, // Consecutive failures before a host is unhealthy (default 3)
+    pub initial_health_check_delay_seconds: Option<u32>, // Startup grace before the first probe; new hosts get no traffic until it passes
+}
+
+/// Retry budget applied through the cluster's default-priority circuit breaker
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryBudget {
+    pub budget_percent: f64, // Active retries allowed as a percentage of active requests (Envoy default 20)
+    pub min_retry_concurrency: Option<u32>, // Retries always allowed regardless of the budget (Envoy default 3)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Cluster {
     pub name: String,
     pub endpoints: Vec<Endpoint>,
     pub lb_policy: Option<LoadBalancingPolicy>, // Optional: falls back to config default
+    pub choice_count: Option<u32>, // LEAST_REQUEST power-of-N choices
+    #[serde(default)]
+    pub locality_weighted_lb: bool, // Enables locality_weighted_lb_config
+    pub locality_weights: Option<HashMap<String, u32>>, // Region -> load_balancing_weight
+    pub labels: Option<HashMap<String, String>>, // Free-form key/value labels for grouping and filtering
+    pub per_connection_buffer_limit_bytes: Option<u32>, // Upper bound on buffered bytes per upstream connection
+    pub http_protocol: Option<String>, // auto (follow downstream), http1 or http2
+    pub ignore_health_on_host_removal: Option<bool>, // Drain hosts removed by service discovery even if health checks still pass
+    pub wait_for_warm_on_init: Option<bool>, // Hold Envoy init until the cluster has warmed (Envoy default true)
+    pub respect_dns_ttl: Option<bool>, // Honor DNS record TTLs instead of the fixed refresh rate (DNS discovery only)
+    pub health_check: Option<HealthCheck>, // Active HTTP health check; None disables probing
+    pub alt_stat_name: Option<String>, // Stable stats prefix that survives cluster renames
+    pub source_address: Option<String>, // Local IP upstream connections are bound to
+    pub retry_budget: Option<RetryBudget>, // Caps concurrent retries to a share of active requests
+    pub metadata: Option<HashMap<String, serde_json::Value>>, // Filter metadata keyed by namespace; each value is a JSON object
+    pub alpn_protocols: Option<Vec<String>>, // ALPN offered to TLS endpoints; defaults to h2 then http/1.1
+    pub dns_resolvers: Option<Vec<String>>, // Resolver IP:port list used instead of the system resolver (DNS discovery only)
+    pub headers_with_underscores_action: Option<String>, // ALLOW, REJECT_REQUEST or DROP_HEADER for upstream response headers
+    pub dns_failure_refresh_rate_seconds: Option<u64>, // Retry interval after a failed DNS resolution; DNS discovery only
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Endpoint {
     pub host: String,
     pub port: u16,
+    pub health_status: Option<String>, // HEALTHY, DEGRADED, UNHEALTHY
+    pub tls: Option<bool>, // Tagged endpoints use the upstream TLS transport socket match
+    pub locality: Option<String>, // Region used to group endpoints into localities
+    pub hostname: Option<String>, // Name used for SNI / Host header when it differs from host
+    pub weight: Option<u32>, // load_balancing_weight; set on every endpoint of a cluster or none
+    pub additional_addresses: Option<Vec<String>>, // Extra IPs (e.g. the IPv6 side of a dual-stack host) on the same port
 }
 
 impl Route {
@@ -86,6 +207,23 @@ impl Route {
             cluster_name,
             prefix_rewrite,
             http_methods: None,
+            regex_rewrite: None,
+            idle_timeout_seconds: None,
+            maintenance_mode: false,
+            maintenance_body: None,
+            retry_policy: None,
+            timeout_seconds: None,
+            labels: None,
+            response_headers_to_add: None,
+            scheme_match: None,
+            weighted_clusters: None,
+            routing_priority: None,
+            description: None,
+            content_type_match: None,
+            listener: None,
+            case_sensitive: None,
+            request_headers_to_remove: None,
+            disabled_filters: None,
         }
     }
 
@@ -102,6 +240,23 @@ impl Route {
             cluster_name,
             prefix_rewrite,
             http_methods,
+            regex_rewrite: None,
+            idle_timeout_seconds: None,
+            maintenance_mode: false,
+            maintenance_body: None,
+            retry_policy: None,
+            timeout_seconds: None,
+            labels: None,
+            response_headers_to_add: None,
+            scheme_match: None,
+            weighted_clusters: None,
+            routing_priority: None,
+            description: None,
+            content_type_match: None,
+            listener: None,
+            case_sensitive: None,
+            request_headers_to_remove: None,
+            disabled_filters: None,
         }
     }
 }
@@ -112,6 +267,24 @@ impl Cluster {
             name,
             endpoints,
             lb_policy: None, // No specific policy - will use system default
+            choice_count: None,
+            locality_weighted_lb: false,
+            locality_weights: None,
+            labels: None,
+            per_connection_buffer_limit_bytes: None,
+            http_protocol: None,
+            ignore_health_on_host_removal: None,
+            wait_for_warm_on_init: None,
+            respect_dns_ttl: None,
+            health_check: None,
+            alt_stat_name: None,
+            source_address: None,
+            retry_budget: None,
+            metadata: None,
+            alpn_protocols: None,
+            dns_resolvers: None,
+            headers_with_underscores_action: None,
+            dns_failure_refresh_rate_seconds: None,
         }
     }
 
@@ -124,13 +297,49 @@ impl Cluster {
             name,
             endpoints,
             lb_policy: Some(lb_policy),
+            choice_count: None,
+            locality_weighted_lb: false,
+            locality_weights: None,
+            labels: None,
+            per_connection_buffer_limit_bytes: None,
+            http_protocol: None,
+            ignore_health_on_host_removal: None,
+            wait_for_warm_on_init: None,
+            respect_dns_ttl: None,
+            health_check: None,
+            alt_stat_name: None,
+            source_address: None,
+            retry_budget: None,
+            metadata: None,
+            alpn_protocols: None,
+            dns_resolvers: None,
+            headers_with_underscores_action: None,
+            dns_failure_refresh_rate_seconds: None,
         }
     }
+
+    /// The cluster's policy, or the configured default when none is set
+    ///
+    /// An unparseable default falls back to ROUND_ROBIN, matching Envoy's own default.
+    pub fn effective_lb_policy(&self, default_policy: &str) -> LoadBalancingPolicy {
+        self.lb_policy
+            .clone()
+            .unwrap_or_else(|| default_policy.parse().unwrap_or(LoadBalancingPolicy::RoundRobin))
+    }
 }
 
 impl Endpoint {
     pub fn new(host: String, port: u16) -> Self {
-        Self { host, port }
+        Self {
+            host,
+            port,
+            health_status: None,
+            tls: None,
+            locality: None,
+            hostname: None,
+            weight: None,
+            additional_addresses: None,
+        }
     }
 }
 
@@ -141,6 +350,23 @@ pub struct HttpFilter {
     pub filter_type: String, // "rate_limit", "cors", etc.
     pub config: serde_json::Value, // Flexible JSON config
     pub enabled: bool,
+    #[serde(default)]
+    pub disabled_by_default: bool, // Installed on the listener but only active on routes that opt in
+    #[serde(default)]
+    pub path_prefix: Option<String>, // Only active on routes whose path starts with this prefix
+}
+
+impl Default for HttpFilter {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            filter_type: String::new(),
+            config: serde_json::Value::Null,
+            enabled: true,
+            disabled_by_default: false,
+            path_prefix: None,
+        }
+    }
 }
 
 /// Route-Filter association
@@ -151,6 +377,24 @@ pub struct RouteFilters {
     pub custom_order: Option<Vec<String>>, // Override global order
 }
 
+/// Kind of mutation recorded in the store changelog
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Single entry in the in-memory changelog of recent store mutations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub resource_type: String,
+    pub resource_name: String,
+    pub action: ChangeAction,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 impl HttpFilter {
     pub fn new(name: String, filter_type: String, config: serde_json::Value) -> Self {
         Self {
@@ -158,6 +402,8 @@ impl HttpFilter {
             filter_type,
             config,
             enabled: true,
+            disabled_by_default: false,
+            path_prefix: None,
         }
     }
 
@@ -166,6 +412,33 @@ impl HttpFilter {
         self
     }
 
+    pub fn with_disabled_by_default(mut self, disabled_by_default: bool) -> Self {
+        self.disabled_by_default = disabled_by_default;
+        self
+    }
+
+    pub fn with_path_prefix(mut self, path_prefix: Option<String>) -> Self {
+        self.path_prefix = path_prefix;
+        self
+    }
+
+    /// Whether the filter is installed disabled on the listener and switched on per route
+    pub fn is_route_scoped(&self) -> bool {
+        self.disabled_by_default || self.path_prefix.is_some()
+    }
+
+    /// Whether a path_prefix selector enables this filter for the given route path
+    ///
+    /// Matches whole path segments only, so `/admin` covers `/admin` and `/admin/users`
+    /// but not `/administrator`.
+    pub fn applies_to_path(&self, route_path: &str) -> bool {
+        self.path_prefix.as_deref().is_some_and(|prefix| {
+            route_path.strip_prefix(prefix).is_some_and(|rest| {
+                rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+            })
+        })
+    }
+
     /// Basic validation for the filter
     pub fn validate(&self, supported_filters: &[String]) -> Result<(), String> {
         // Validate name
@@ -192,6 +465,12 @@ impl HttpFilter {
             return Err("Filter config cannot be null".to_string());
         }
 
+        if let Some(ref prefix) = self.path_prefix {
+            if !prefix.starts_with('/') {
+                return Err(format!("Filter path_prefix '{}' must start with '/'", prefix));
+            }
+        }
+
         Ok(())
     }
 }
@@ -363,6 +642,12 @@ impl HttpFilter {
             }
         }
 
+        if let Some(max_age) = config.get("max_age") {
+            if !max_age.is_u64() {
+                return Err("max_age must be a non-negative integer (seconds)".to_string());
+            }
+        }
+
         Ok(())
     }
 
@@ -526,6 +811,11 @@ impl HttpFilter {
                     return Err("request_validation config must be an object".to_string());
                 }
             },
+            "header_to_metadata" => {
+                if !self.config.get("request_rules").is_some_and(|rules| rules.is_array()) {
+                    return Err("header_to_metadata config must contain a request_rules array".to_string());
+                }
+            },
             _ => {
                 // Unknown filter type should have been caught by basic validation
                 return Err(format!("Unknown filter type: {}", self.filter_type));
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A single RFC 6902 JSON Patch operation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+/// Errors raised while applying a JSON Patch document
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum PatchError {
+    #[error("Invalid JSON pointer '{0}'")]
+    InvalidPointer(String),
+
+    #[error("Path '{0}' does not exist")]
+    PathNotFound(String),
+
+    #[error("Test operation failed at '{0}'")]
+    TestFailed(String),
+}
+
+/// Apply a patch document to a serialized resource
+///
+/// Operations apply in order against a copy, so a failing operation leaves the
+/// original untouched.
+pub fn apply_patch(document: &Value, operations: &[PatchOperation]) -> Result<Value, PatchError> {
+    let mut patched = document.clone();
+    for operation in operations {
+        match operation {
+            PatchOperation::Add { path, value } => add(&mut patched, path, value.clone())?,
+            PatchOperation::Remove { path } => {
+                remove(&mut patched, path)?;
+            }
+            PatchOperation::Replace { path, value } => {
+                *lookup_mut(&mut patched, path)? = value.clone();
+            }
+            PatchOperation::Move { from, path } => {
+                let value = remove(&mut patched, from)?;
+                add(&mut patched, path, value)?;
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = lookup_mut(&mut patched, from)?.clone();
+                add(&mut patched, path, value)?;
+            }
+            PatchOperation::Test { path, value } => {
+                if lookup_mut(&mut patched, path)? != value {
+                    return Err(PatchError::TestFailed(path.clone()));
+                }
+            }
+        }
+    }
+    Ok(patched)
+}
+
+/// Split a JSON pointer into its parent pointer and unescaped last token
+fn split_pointer(path: &str) -> Result<(&str, String), PatchError> {
+    if !path.starts_with('/') {
+        return Err(PatchError::InvalidPointer(path.to_string()));
+    }
+    let (parent, token) = path.rsplit_once('/').unwrap_or(("", path));
+    Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+fn lookup_mut<'a>(document: &'a mut Value, path: &str) -> Result<&'a mut Value, PatchError> {
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(PatchError::InvalidPointer(path.to_string()));
+    }
+    document.pointer_mut(path).ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match lookup_mut(document, parent)? {
+        Value::Object(map) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Value::Array(items) if token == "-" => {
+            items.push(value);
+            Ok(())
+        }
+        Value::Array(items) => match token.parse::<usize>() {
+            Ok(index) if index <= items.len() => {
+                items.insert(index, value);
+                Ok(())
+            }
+            _ => Err(PatchError::PathNotFound(path.to_string())),
+        },
+        _ => Err(PatchError::PathNotFound(path.to_string())),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value, PatchError> {
+    let (parent, token) = split_pointer(path)?;
+    let removed = match lookup_mut(document, parent)? {
+        Value::Object(map) => map.remove(&token),
+        Value::Array(items) => token
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < items.len())
+            .map(|index| items.remove(index)),
+        _ => None,
+    };
+    removed.ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
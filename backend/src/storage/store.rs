@@ -1,25 +1,67 @@
 use dashmap::DashMap;
-use std::sync::Arc;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
 
-use super::models::{Cluster, Route, HttpFilter, RouteFilters};
+use super::models::{ChangeAction, ChangelogEntry, Cluster, HttpFilter, LoadBalancingPolicy, Route, RouteFilters};
+use super::diff::ConfigSnapshot;
 use super::StorageError;
 
+/// Number of recent mutations kept in the changelog ring buffer
+pub const CHANGELOG_CAPACITY: usize = 100;
+
+/// Buffered change notifications per subscriber before it starts lagging
+const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// Endpoint counts of one cluster, kept current on every cluster mutation for dashboards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EndpointGauge {
+    /// Endpoints configured on the cluster; zero means traffic to it will fail
+    pub endpoints: usize,
+    /// Endpoints not marked UNHEALTHY, tracked only for health-checked clusters
+    pub expected_healthy: Option<usize>,
+}
+
+impl EndpointGauge {
+    fn for_cluster(cluster: &Cluster) -> Self {
+        let expected_healthy = cluster.health_check.as_ref().map(|_| {
+            cluster
+                .endpoints
+                .iter()
+                .filter(|e| e.health_status.as_deref() != Some("UNHEALTHY"))
+                .count()
+        });
+        Self {
+            endpoints: cluster.endpoints.len(),
+            expected_healthy,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfigStore {
     routes: Arc<DashMap<String, Arc<Route>>>,
     clusters: Arc<DashMap<String, Arc<Cluster>>>,
     http_filters: Arc<DashMap<String, Arc<HttpFilter>>>,
     route_filters: Arc<DashMap<String, RouteFilters>>,
+    changelog: Arc<Mutex<VecDeque<ChangelogEntry>>>,
+    change_sender: broadcast::Sender<ChangelogEntry>,
+    endpoint_gauges: Arc<DashMap<String, EndpointGauge>>,
     config: crate::config::StorageConfig,
 }
 
 impl ConfigStore {
     pub fn with_config(config: crate::config::StorageConfig) -> Self {
+        let (change_sender, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
         Self {
             routes: Arc::new(DashMap::new()),
             clusters: Arc::new(DashMap::new()),
             http_filters: Arc::new(DashMap::new()),
             route_filters: Arc::new(DashMap::new()),
+            changelog: Arc::new(Mutex::new(VecDeque::with_capacity(CHANGELOG_CAPACITY))),
+            change_sender,
+            endpoint_gauges: Arc::new(DashMap::new()),
             config,
         }
     }
@@ -36,6 +78,7 @@ impl ConfigStore {
             behavior: crate::config::StorageBehaviorConfig {
                 reject_on_capacity: true,
                 enable_metrics: true,
+                abort_on_conversion_error: false,
             },
         };
         Self::with_config(default_config)
@@ -80,6 +123,7 @@ impl ConfigStore {
         self.validate_route(&route)?;
 
         self.routes.insert(name.clone(), Arc::new(route));
+        self.record_change("Route", &name, ChangeAction::Created);
         Ok(name)
     }
 
@@ -100,12 +144,14 @@ impl ConfigStore {
     }
 
     pub fn remove_route(&self, id: &str) -> Result<Arc<Route>, StorageError> {
-        self.routes.remove(id).map(|(_, route)| route).ok_or_else(|| {
+        let route = self.routes.remove(id).map(|(_, route)| route).ok_or_else(|| {
             StorageError::ResourceNotFound {
                 resource_type: "Route".to_string(),
                 resource_id: id.to_string(),
             }
-        })
+        })?;
+        self.record_change("Route", id, ChangeAction::Deleted);
+        Ok(route)
     }
 
     pub fn update_route(&self, id: &str, updated_route: Route) -> Result<Arc<Route>, StorageError> {
@@ -122,6 +168,7 @@ impl ConfigStore {
 
         let arc_route = Arc::new(updated_route);
         self.routes.insert(id.to_string(), arc_route.clone());
+        self.record_change("Route", id, ChangeAction::Updated);
         Ok(arc_route)
     }
 
@@ -165,7 +212,9 @@ impl ConfigStore {
         // Validate cluster before storing
         self.validate_cluster(&cluster)?;
 
+        self.update_endpoint_gauge(&name, &cluster);
         self.clusters.insert(name.clone(), Arc::new(cluster));
+        self.record_change("Cluster", &name, ChangeAction::Created);
         Ok(name)
     }
 
@@ -186,12 +235,15 @@ impl ConfigStore {
     }
 
     pub fn remove_cluster(&self, name: &str) -> Result<Arc<Cluster>, StorageError> {
-        self.clusters.remove(name).map(|(_, cluster)| cluster).ok_or_else(|| {
+        let cluster = self.clusters.remove(name).map(|(_, cluster)| cluster).ok_or_else(|| {
             StorageError::ResourceNotFound {
                 resource_type: "Cluster".to_string(),
                 resource_id: name.to_string(),
             }
-        })
+        })?;
+        self.endpoint_gauges.remove(name);
+        self.record_change("Cluster", name, ChangeAction::Deleted);
+        Ok(cluster)
     }
 
     /// Update existing cluster with validation
@@ -218,11 +270,27 @@ impl ConfigStore {
         // Validate updated cluster
         self.validate_cluster(&updated_cluster)?;
 
+        self.update_endpoint_gauge(name, &updated_cluster);
         let arc_cluster = Arc::new(updated_cluster);
         self.clusters.insert(name.to_string(), arc_cluster.clone());
+        self.record_change("Cluster", name, ChangeAction::Updated);
         Ok(arc_cluster)
     }
 
+    /// Endpoint gauge of every cluster, empty when `enable_metrics` is off
+    pub fn endpoint_gauges(&self) -> std::collections::BTreeMap<String, EndpointGauge> {
+        self.endpoint_gauges
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect()
+    }
+
+    fn update_endpoint_gauge(&self, name: &str, cluster: &Cluster) {
+        if self.config.behavior.enable_metrics {
+            self.endpoint_gauges.insert(name.to_string(), EndpointGauge::for_cluster(cluster));
+        }
+    }
+
     // Capacity reporting methods for observability
     pub fn get_route_capacity_info(&self) -> (usize, usize, f64) {
         let current = self.routes.len();
@@ -265,6 +333,14 @@ impl ConfigStore {
             });
         }
 
+        if route.prefix_rewrite.is_some() && route.regex_rewrite.is_some() {
+            return Err(StorageError::ValidationFailed {
+                resource_type: "Route".to_string(),
+                resource_id: route.name.clone(),
+                reason: "prefix_rewrite and regex_rewrite are mutually exclusive".to_string(),
+            });
+        }
+
         // Check if referenced cluster exists
         if !self.clusters.contains_key(&route.cluster_name) {
             return Err(StorageError::DependencyMissing {
@@ -314,6 +390,38 @@ impl ConfigStore {
             }
         }
 
+        if cluster.locality_weighted_lb {
+            let weights = cluster.locality_weights.as_ref();
+            for (i, endpoint) in cluster.endpoints.iter().enumerate() {
+                let weight = endpoint
+                    .locality
+                    .as_ref()
+                    .and_then(|locality| weights.and_then(|w| w.get(locality)));
+                if !matches!(weight, Some(weight) if *weight > 0) {
+                    return Err(StorageError::ValidationFailed {
+                        resource_type: "Cluster".to_string(),
+                        resource_id: cluster.name.clone(),
+                        reason: format!(
+                            "Endpoint {} needs a locality with a positive weight when locality_weighted_lb is enabled",
+                            i + 1
+                        ),
+                    });
+                }
+            }
+        }
+
+        // An unset policy resolves against the configured default, which the store does not know;
+        // the API and the xDS conversion check choice_count against the resolved policy
+        if cluster.choice_count.is_some()
+            && cluster.lb_policy.as_ref().is_some_and(|policy| *policy != LoadBalancingPolicy::LeastRequest)
+        {
+            return Err(StorageError::ValidationFailed {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: "choice_count is only valid with the LEAST_REQUEST load balancing policy".to_string(),
+            });
+        }
+
         Ok(())
     }
 
@@ -334,23 +442,26 @@ impl ConfigStore {
         }
 
         let name = filter.name.clone();
-        
-        // Check for conflicts
-        if self.http_filters.contains_key(&name) {
-            return Err(StorageError::ResourceConflict {
-                resource_type: "HttpFilter".to_string(),
-                resource_id: name,
-            });
-        }
-
-        // Validate filter before storing
-        filter.validate(supported_filters).map_err(|reason| StorageError::ValidationFailed {
-            resource_type: "HttpFilter".to_string(),
-            resource_id: filter.name.clone(),
-            reason,
-        })?;
 
-        self.http_filters.insert(name.clone(), Arc::new(filter));
+        // Check for conflicts under the entry lock so concurrent creates can't overwrite each other
+        match self.http_filters.entry(name.clone()) {
+            dashmap::mapref::entry::Entry::Occupied(_) => {
+                return Err(StorageError::ResourceConflict {
+                    resource_type: "HttpFilter".to_string(),
+                    resource_id: name,
+                });
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                // Validate filter before storing
+                filter.validate(supported_filters).map_err(|reason| StorageError::ValidationFailed {
+                    resource_type: "HttpFilter".to_string(),
+                    resource_id: filter.name.clone(),
+                    reason,
+                })?;
+                entry.insert(Arc::new(filter));
+            }
+        }
+        self.record_change("HttpFilter", &name, ChangeAction::Created);
         Ok(name)
     }
 
@@ -382,12 +493,14 @@ impl ConfigStore {
             }
         }
 
-        self.http_filters.remove(name).map(|(_, filter)| filter).ok_or_else(|| {
+        let filter = self.http_filters.remove(name).map(|(_, filter)| filter).ok_or_else(|| {
             StorageError::ResourceNotFound {
                 resource_type: "HttpFilter".to_string(),
                 resource_id: name.to_string(),
             }
-        })
+        })?;
+        self.record_change("HttpFilter", name, ChangeAction::Deleted);
+        Ok(filter)
     }
 
     pub fn update_http_filter(&self, name: &str, updated_filter: HttpFilter, supported_filters: &[String]) -> Result<Arc<HttpFilter>, StorageError> {
@@ -408,6 +521,7 @@ impl ConfigStore {
 
         let arc_filter = Arc::new(updated_filter);
         self.http_filters.insert(name.to_string(), arc_filter.clone());
+        self.record_change("HttpFilter", name, ChangeAction::Updated);
         Ok(arc_filter)
     }
 
@@ -436,7 +550,13 @@ impl ConfigStore {
             reason,
         })?;
 
+        let action = if self.route_filters.contains_key(&route_name) {
+            ChangeAction::Updated
+        } else {
+            ChangeAction::Created
+        };
         self.route_filters.insert(route_name.clone(), route_filters);
+        self.record_change("RouteFilters", &route_name, action);
         Ok(route_name)
     }
 
@@ -444,13 +564,19 @@ impl ConfigStore {
         self.route_filters.get(route_name).map(|rf| rf.clone())
     }
 
+    pub fn list_route_filters(&self) -> Vec<RouteFilters> {
+        self.route_filters.iter().map(|entry| entry.value().clone()).collect()
+    }
+
     pub fn remove_route_filters(&self, route_name: &str) -> Result<RouteFilters, StorageError> {
-        self.route_filters.remove(route_name).map(|(_, rf)| rf).ok_or_else(|| {
+        let route_filters = self.route_filters.remove(route_name).map(|(_, rf)| rf).ok_or_else(|| {
             StorageError::ResourceNotFound {
                 resource_type: "RouteFilters".to_string(),
                 resource_id: route_name.to_string(),
             }
-        })
+        })?;
+        self.record_change("RouteFilters", route_name, ChangeAction::Deleted);
+        Ok(route_filters)
     }
 
     // Capacity reporting for HTTP filters
@@ -460,4 +586,43 @@ impl ConfigStore {
         let utilization = (current as f64) / (limit as f64) * 100.0;
         (current, limit, utilization)
     }
+
+    // Changelog of recent mutations for quick inspection
+    fn record_change(&self, resource_type: &str, resource_name: &str, action: ChangeAction) {
+        let mut changelog = self.changelog.lock().unwrap_or_else(|e| e.into_inner());
+        if changelog.len() >= CHANGELOG_CAPACITY {
+            changelog.pop_front();
+        }
+        let entry = ChangelogEntry {
+            resource_type: resource_type.to_string(),
+            resource_name: resource_name.to_string(),
+            action,
+            timestamp: chrono::Utc::now(),
+        };
+        changelog.push_back(entry.clone());
+
+        // No subscribers is the common case; a send error just means nobody is listening
+        let _ = self.change_sender.send(entry);
+    }
+
+    /// Subscribe to mutations as they are recorded
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<ChangelogEntry> {
+        self.change_sender.subscribe()
+    }
+
+    /// Capture every stored resource as a point-in-time snapshot
+    pub fn snapshot(&self) -> ConfigSnapshot {
+        ConfigSnapshot {
+            routes: self.list_routes().iter().map(|r| (**r).clone()).collect(),
+            clusters: self.list_clusters().iter().map(|c| (**c).clone()).collect(),
+            http_filters: self.list_http_filters().iter().map(|f| (**f).clone()).collect(),
+            route_filters: self.list_route_filters(),
+        }
+    }
+
+    /// Return the most recent mutations, oldest first
+    pub fn list_changelog(&self) -> Vec<ChangelogEntry> {
+        let changelog = self.changelog.lock().unwrap_or_else(|e| e.into_inner());
+        changelog.iter().cloned().collect()
+    }
 }
@@ -0,0 +1,53 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use crate::storage::Endpoint;
+
+/// Resolve each endpoint hostname and return the ones that fail to resolve
+/// IP-literal hosts are skipped since Envoy never resolves them
+pub async fn find_unresolvable_hosts(endpoints: &[Endpoint], timeout: Duration) -> Vec<String> {
+    let mut unresolvable = Vec::new();
+
+    for endpoint in endpoints {
+        if endpoint.host.parse::<IpAddr>().is_ok() {
+            continue;
+        }
+
+        let lookup = tokio::net::lookup_host((endpoint.host.as_str(), endpoint.port));
+        let resolved = match tokio::time::timeout(timeout, lookup).await {
+            Ok(Ok(mut addrs)) => addrs.next().is_some(),
+            Ok(Err(_)) | Err(_) => false,
+        };
+
+        if !resolved && !unresolvable.contains(&endpoint.host) {
+            unresolvable.push(endpoint.host.clone());
+        }
+    }
+
+    unresolvable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ip_literals_skip_resolution() {
+        let endpoints = vec![
+            Endpoint::new("127.0.0.1".to_string(), 8080),
+            Endpoint::new("::1".to_string(), 8080),
+        ];
+
+        let unresolvable = find_unresolvable_hosts(&endpoints, Duration::from_millis(500)).await;
+        assert!(unresolvable.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_hostname_is_unresolvable() {
+        // The .invalid TLD is reserved and never resolves
+        let endpoints = vec![Endpoint::new("no-such-host.invalid".to_string(), 8080)];
+
+        let unresolvable = find_unresolvable_hosts(&endpoints, Duration::from_millis(500)).await;
+        assert_eq!(unresolvable, vec!["no-such-host.invalid".to_string()]);
+    }
+}
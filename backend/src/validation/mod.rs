@@ -1,8 +1,10 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use validator::{Validate, ValidationError};
 
+pub mod dns;
 pub mod security;
 
 /// Validation patterns for different input types
@@ -15,6 +17,14 @@ lazy_static! {
     
     /// Host validation: alphanumeric, period, hyphen (for domains and IPs)
     static ref HOST_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9.-]+$").unwrap();
+    static ref LABEL_KEY_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9][a-zA-Z0-9._/-]{0,62}$").unwrap();
+    static ref LABEL_VALUE_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9._-]{0,63}$").unwrap();
+    static ref RUNTIME_KEY_PREFIX_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9_]+(\.[a-zA-Z0-9_-]+)*$").unwrap();
+    static ref STAT_NAME_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9_-]+)*$").unwrap();
+    static ref MEDIA_TYPE_REGEX: Regex =
+        Regex::new(r"^[a-zA-Z0-9][a-zA-Z0-9!#$&^_.+-]{0,126}/[a-zA-Z0-9][a-zA-Z0-9!#$&^_.+-]{0,126}$").unwrap();
+    static ref HOSTNAME_REGEX: Regex =
+        Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(\.[a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$").unwrap();
     
     /// Path validation: starts with /, contains safe URL characters
     static ref PATH_REGEX: Regex = Regex::new(r"^/[a-zA-Z0-9/_.-]*$").unwrap();
@@ -24,6 +34,9 @@ lazy_static! {
     
     /// Load balancing policy validation
     static ref LB_POLICY_REGEX: Regex = Regex::new(r"^(ROUND_ROBIN|LEAST_REQUEST|RANDOM|RING_HASH)$").unwrap();
+    
+    /// Endpoint health status validation
+    static ref HEALTH_STATUS_REGEX: Regex = Regex::new(r"^(HEALTHY|DEGRADED|UNHEALTHY)$").unwrap();
 }
 
 /// Custom validation functions
@@ -48,6 +61,14 @@ pub fn validate_host(host: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Validation helper for DNS hostnames (RFC 1123 labels)
+pub fn validate_hostname(hostname: &str) -> Result<(), ValidationError> {
+    if hostname.len() > 253 || !HOSTNAME_REGEX.is_match(hostname) {
+        return Err(ValidationError::new("invalid_hostname"));
+    }
+    Ok(())
+}
+
 pub fn validate_path(path: &str) -> Result<(), ValidationError> {
     // Check for path traversal attempts
     if path.contains("..") || path.contains("//") {
@@ -74,6 +95,301 @@ pub fn validate_lb_policy(policy: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+pub fn validate_health_status(status: &str) -> Result<(), ValidationError> {
+    if !HEALTH_STATUS_REGEX.is_match(status) {
+        return Err(ValidationError::new("invalid_health_status"));
+    }
+    Ok(())
+}
+
+/// Validation helper for regex path rewrites
+pub fn validate_regex_rewrite(rewrite: &crate::storage::RegexRewrite) -> Result<(), ValidationError> {
+    if rewrite.pattern.is_empty() || rewrite.pattern.len() > 200 {
+        return Err(ValidationError::new("invalid_regex_rewrite"));
+    }
+
+    if rewrite.substitution.len() > 200 {
+        return Err(ValidationError::new("invalid_regex_rewrite"));
+    }
+
+    if Regex::new(&rewrite.pattern).is_err() {
+        return Err(ValidationError::new("invalid_regex_rewrite"));
+    }
+    Ok(())
+}
+
+/// Envoy `retry_on` conditions for HTTP responses and connection failures
+const HTTP_RETRY_ON_CONDITIONS: &[&str] = &[
+    "5xx",
+    "gateway-error",
+    "reset",
+    "reset-before-request",
+    "connect-failure",
+    "envoy-ratelimited",
+    "retriable-4xx",
+    "refused-stream",
+    "retriable-status-codes",
+    "retriable-headers",
+    "http3-post-connect-failure",
+];
+
+/// Envoy `retry_on` conditions matched against the gRPC status of a response
+const GRPC_RETRY_ON_CONDITIONS: &[&str] = &[
+    "cancelled",
+    "deadline-exceeded",
+    "internal",
+    "resource-exhausted",
+    "unavailable",
+];
+
+/// Validation helper for route retry policies
+pub fn validate_retry_policy(policy: &crate::storage::RetryPolicy) -> Result<(), ValidationError> {
+    if policy.retry_on.trim().is_empty() && policy.grpc_retry_on.is_empty() {
+        return Err(ValidationError::new("invalid_retry_policy"));
+    }
+
+    // gRPC conditions are also accepted inline in retry_on, as Envoy does
+    let unknown_condition = policy
+        .retry_on
+        .split(',')
+        .map(str::trim)
+        .filter(|condition| !condition.is_empty())
+        .any(|condition| {
+            !HTTP_RETRY_ON_CONDITIONS.contains(&condition) && !GRPC_RETRY_ON_CONDITIONS.contains(&condition)
+        });
+    if unknown_condition {
+        return Err(ValidationError::new("invalid_retry_policy"));
+    }
+
+    if policy
+        .grpc_retry_on
+        .iter()
+        .any(|condition| !GRPC_RETRY_ON_CONDITIONS.contains(&condition.as_str()))
+    {
+        return Err(ValidationError::new("invalid_grpc_retry_on"));
+    }
+
+    if policy.retriable_status_codes.iter().any(|code| !(100..=599).contains(code)) {
+        return Err(ValidationError::new("invalid_retry_policy"));
+    }
+
+    if let Some(ref back_off) = policy.retry_back_off {
+        if back_off.base_interval_ms == 0 || back_off.max_interval_ms == 0 {
+            return Err(ValidationError::new("invalid_retry_back_off"));
+        }
+        if back_off.base_interval_ms > back_off.max_interval_ms {
+            return Err(ValidationError::new("invalid_retry_back_off"));
+        }
+    }
+    Ok(())
+}
+
+/// Validation helper for per-locality load balancing weights
+pub fn validate_locality_weights(weights: &HashMap<String, u32>) -> Result<(), ValidationError> {
+    if weights.iter().any(|(locality, weight)| locality.is_empty() || *weight == 0) {
+        return Err(ValidationError::new("invalid_locality_weights"));
+    }
+    Ok(())
+}
+
+/// Validation helper for resource labels
+pub fn validate_labels(labels: &HashMap<String, String>) -> Result<(), ValidationError> {
+    if labels.len() > 20 {
+        return Err(ValidationError::new("too_many_labels"));
+    }
+
+    for (key, value) in labels {
+        if !LABEL_KEY_REGEX.is_match(key) || !LABEL_VALUE_REGEX.is_match(value) {
+            return Err(ValidationError::new("invalid_label"));
+        }
+    }
+    Ok(())
+}
+
+/// Validation helper for headers a route adds to its responses
+pub fn validate_response_headers(headers: &HashMap<String, String>) -> Result<(), ValidationError> {
+    for (name, value) in headers {
+        if security::Validator::validate_http_header_name(name).is_err()
+            || security::Validator::validate_http_header_value(value).is_err()
+        {
+            return Err(ValidationError::new("invalid_response_header"));
+        }
+    }
+    Ok(())
+}
+
+/// Validation helper for request headers stripped before forwarding; Envoy won't remove pseudo-headers or host
+pub fn validate_request_headers_to_remove(headers: &Vec<String>) -> Result<(), ValidationError> {
+    for name in headers {
+        if security::Validator::validate_http_header_name(name).is_err()
+            || name.starts_with(':')
+            || name.eq_ignore_ascii_case("host")
+        {
+            return Err(ValidationError::new("invalid_header_to_remove"));
+        }
+    }
+    Ok(())
+}
+
+/// Validation helper for a route's disabled filters: distinct, non-empty filter names
+pub fn validate_disabled_filters(names: &Vec<String>) -> Result<(), ValidationError> {
+    let mut seen = HashSet::new();
+    if names.is_empty() || names.iter().any(|name| name.is_empty() || name.len() > 100 || !seen.insert(name)) {
+        return Err(ValidationError::new("invalid_disabled_filters"));
+    }
+    Ok(())
+}
+
+/// Validation helper for weighted cluster traffic splits
+pub fn validate_weighted_clusters(weighted: &crate::storage::WeightedClusters) -> Result<(), ValidationError> {
+    if weighted.clusters.is_empty()
+        || weighted
+            .clusters
+            .iter()
+            .any(|cluster| cluster.weight == 0 || cluster.name.len() > 50 || validate_cluster_name(&cluster.name).is_err())
+    {
+        return Err(ValidationError::new("invalid_weighted_clusters"));
+    }
+
+    if let Some(prefix) = &weighted.runtime_key_prefix {
+        if prefix.len() > 100 || !RUNTIME_KEY_PREFIX_REGEX.is_match(prefix) {
+            return Err(ValidationError::new("invalid_runtime_key_prefix"));
+        }
+    }
+    Ok(())
+}
+
+/// Validation helper for active cluster health checks
+pub fn validate_health_check(health_check: &crate::storage::HealthCheck) -> Result<(), ValidationError> {
+    let thresholds_valid = [health_check.healthy_threshold, health_check.unhealthy_threshold]
+        .iter()
+        .flatten()
+        .all(|threshold| (1..=10).contains(threshold));
+
+    if !health_check.path.starts_with('/')
+        || health_check.path.len() > 200
+        || !(1..=300).contains(&health_check.interval_seconds)
+        || health_check.timeout_seconds == 0
+        || health_check.timeout_seconds > health_check.interval_seconds
+        || !thresholds_valid
+    {
+        return Err(ValidationError::new("invalid_health_check"));
+    }
+
+    if health_check.initial_health_check_delay_seconds.is_some_and(|delay| delay > 300) {
+        return Err(ValidationError::new("invalid_health_check_delay"));
+    }
+    Ok(())
+}
+
+/// Validation helper for cluster filter metadata: namespace keys of 1-100 characters mapping to JSON objects
+pub fn validate_cluster_metadata(metadata: &HashMap<String, serde_json::Value>) -> Result<(), ValidationError> {
+    for (namespace, value) in metadata {
+        if namespace.is_empty() || namespace.len() > 100 || !value.is_object() {
+            return Err(ValidationError::new("invalid_cluster_metadata"));
+        }
+    }
+    Ok(())
+}
+
+/// Validation helper for upstream TLS ALPN protocol ids: a non-empty list of 1-255 visible ASCII characters each
+pub fn validate_alpn_protocols(protocols: &Vec<String>) -> Result<(), ValidationError> {
+    let valid = |protocol: &String| {
+        (1..=255).contains(&protocol.len()) && protocol.bytes().all(|b| b.is_ascii_graphic() && b != b',')
+    };
+    if protocols.is_empty() || !protocols.iter().all(valid) {
+        return Err(ValidationError::new("invalid_alpn_protocols"));
+    }
+    Ok(())
+}
+
+/// Validation helper for custom DNS resolvers: a non-empty list of at most 10 `IP:port` addresses
+pub fn validate_dns_resolvers(resolvers: &Vec<String>) -> Result<(), ValidationError> {
+    let valid = |resolver: &String| {
+        resolver
+            .parse::<std::net::SocketAddr>()
+            .is_ok_and(|address| address.port() != 0)
+    };
+    if resolvers.is_empty() || resolvers.len() > 10 || !resolvers.iter().all(valid) {
+        return Err(ValidationError::new("invalid_dns_resolvers"));
+    }
+    Ok(())
+}
+
+/// Validation helper for cluster retry budgets
+pub fn validate_retry_budget(budget: &crate::storage::RetryBudget) -> Result<(), ValidationError> {
+    if !(budget.budget_percent > 0.0 && budget.budget_percent <= 100.0) {
+        return Err(ValidationError::new("invalid_retry_budget"));
+    }
+    Ok(())
+}
+
+/// Validation helper for Envoy stat names; dots separate stat segments, so no empty segments
+pub fn validate_stat_name(name: &str) -> Result<(), ValidationError> {
+    if !STAT_NAME_REGEX.is_match(name) {
+        return Err(ValidationError::new("invalid_stat_name"));
+    }
+    Ok(())
+}
+
+/// Validation helper for how upstream HTTP headers containing underscores are handled
+pub fn validate_headers_with_underscores_action(action: &str) -> Result<(), ValidationError> {
+    match action {
+        "ALLOW" | "REJECT_REQUEST" | "DROP_HEADER" => Ok(()),
+        _ => Err(ValidationError::new("invalid_headers_with_underscores_action")),
+    }
+}
+
+/// Validation helper for literal IPv4/IPv6 addresses (no hostnames)
+pub fn validate_ip_address(address: &str) -> Result<(), ValidationError> {
+    if address.parse::<std::net::IpAddr>().is_err() {
+        return Err(ValidationError::new("invalid_ip_address"));
+    }
+    Ok(())
+}
+
+/// Validation helper for an endpoint's additional addresses: 1-4 distinct literal IPs
+pub fn validate_additional_addresses(addresses: &[String]) -> Result<(), ValidationError> {
+    let parsed: Result<Vec<std::net::IpAddr>, _> = addresses.iter().map(|address| address.parse()).collect();
+    match parsed {
+        Ok(ips) if (1..=4).contains(&ips.len()) && ips.iter().collect::<HashSet<_>>().len() == ips.len() => Ok(()),
+        _ => Err(ValidationError::new("invalid_additional_addresses")),
+    }
+}
+
+/// Validation helper for a cluster's upstream HTTP protocol
+pub fn validate_http_protocol(protocol: &str) -> Result<(), ValidationError> {
+    match protocol {
+        "auto" | "http1" | "http2" => Ok(()),
+        _ => Err(ValidationError::new("invalid_http_protocol")),
+    }
+}
+
+/// Validation helper for route upstream priority
+pub fn validate_routing_priority(priority: &str) -> Result<(), ValidationError> {
+    match priority {
+        "default" | "high" => Ok(()),
+        _ => Err(ValidationError::new("invalid_routing_priority")),
+    }
+}
+
+/// Validation helper for route scheme restrictions
+pub fn validate_scheme_match(scheme: &str) -> Result<(), ValidationError> {
+    match scheme {
+        "http" | "https" => Ok(()),
+        _ => Err(ValidationError::new("invalid_scheme")),
+    }
+}
+
+/// Validation helper for content-type based route matching
+pub fn validate_content_type_match(content_type: &crate::storage::ContentTypeMatch) -> Result<(), ValidationError> {
+    let header_valid = matches!(content_type.header.as_str(), "accept" | "content-type");
+    if !header_valid || !MEDIA_TYPE_REGEX.is_match(&content_type.media_type) {
+        return Err(ValidationError::new("invalid_content_type_match"));
+    }
+    Ok(())
+}
+
 /// Validation helper for HTTP methods list
 pub fn validate_http_methods(methods: &Vec<String>) -> Result<(), ValidationError> {
     if methods.is_empty() {
@@ -108,6 +424,55 @@ pub struct ValidatedCreateRouteRequest {
     
     #[validate(custom(function = "validate_http_methods"))]
     pub http_methods: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_regex_rewrite"))]
+    pub regex_rewrite: Option<crate::storage::RegexRewrite>,
+    
+    #[validate(range(min = 1, max = 3600))]
+    pub idle_timeout_seconds: Option<u64>,
+    
+    pub maintenance_mode: Option<bool>,
+    
+    #[validate(length(min = 1, max = 4096))]
+    pub maintenance_body: Option<String>,
+    
+    #[validate(custom(function = "validate_retry_policy"))]
+    pub retry_policy: Option<crate::storage::RetryPolicy>,
+    
+    #[validate(range(min = 1, max = 3600))]
+    pub timeout_seconds: Option<u64>,
+    
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Option<HashMap<String, String>>,
+    
+    #[validate(custom(function = "validate_response_headers"))]
+    pub response_headers_to_add: Option<HashMap<String, String>>,
+    
+    #[validate(custom(function = "validate_scheme_match"))]
+    pub scheme_match: Option<String>,
+    
+    #[validate(custom(function = "validate_weighted_clusters"))]
+    pub weighted_clusters: Option<crate::storage::WeightedClusters>,
+    
+    #[validate(custom(function = "validate_routing_priority"))]
+    pub routing_priority: Option<String>,
+    
+    #[validate(length(min = 1, max = 500))]
+    pub description: Option<String>,
+    
+    #[validate(custom(function = "validate_content_type_match"))]
+    pub content_type_match: Option<crate::storage::ContentTypeMatch>,
+    
+    #[validate(length(min = 1, max = 100))]
+    pub listener: Option<String>,
+    
+    pub case_sensitive: Option<bool>,
+    
+    #[validate(custom(function = "validate_request_headers_to_remove"))]
+    pub request_headers_to_remove: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_disabled_filters"))]
+    pub disabled_filters: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -123,6 +488,55 @@ pub struct ValidatedUpdateRouteRequest {
     
     #[validate(custom(function = "validate_http_methods"))]
     pub http_methods: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_regex_rewrite"))]
+    pub regex_rewrite: Option<crate::storage::RegexRewrite>,
+    
+    #[validate(range(min = 1, max = 3600))]
+    pub idle_timeout_seconds: Option<u64>,
+    
+    pub maintenance_mode: Option<bool>,
+    
+    #[validate(length(min = 1, max = 4096))]
+    pub maintenance_body: Option<String>,
+    
+    #[validate(custom(function = "validate_retry_policy"))]
+    pub retry_policy: Option<crate::storage::RetryPolicy>,
+    
+    #[validate(range(min = 1, max = 3600))]
+    pub timeout_seconds: Option<u64>,
+    
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Option<HashMap<String, String>>,
+    
+    #[validate(custom(function = "validate_response_headers"))]
+    pub response_headers_to_add: Option<HashMap<String, String>>,
+    
+    #[validate(custom(function = "validate_scheme_match"))]
+    pub scheme_match: Option<String>,
+    
+    #[validate(custom(function = "validate_weighted_clusters"))]
+    pub weighted_clusters: Option<crate::storage::WeightedClusters>,
+    
+    #[validate(custom(function = "validate_routing_priority"))]
+    pub routing_priority: Option<String>,
+    
+    #[validate(length(min = 1, max = 500))]
+    pub description: Option<String>,
+    
+    #[validate(custom(function = "validate_content_type_match"))]
+    pub content_type_match: Option<crate::storage::ContentTypeMatch>,
+    
+    #[validate(length(min = 1, max = 100))]
+    pub listener: Option<String>,
+    
+    pub case_sensitive: Option<bool>,
+    
+    #[validate(custom(function = "validate_request_headers_to_remove"))]
+    pub request_headers_to_remove: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_disabled_filters"))]
+    pub disabled_filters: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -135,6 +549,56 @@ pub struct ValidatedCreateClusterRequest {
     
     #[validate(custom(function = "validate_lb_policy"))]
     pub lb_policy: Option<String>,
+    
+    #[validate(range(min = 2))]
+    pub choice_count: Option<u32>,
+    
+    pub locality_weighted_lb: Option<bool>,
+    
+    #[validate(custom(function = "validate_locality_weights"))]
+    pub locality_weights: Option<HashMap<String, u32>>,
+    
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Option<HashMap<String, String>>,
+    
+    #[validate(range(min = 1))]
+    pub per_connection_buffer_limit_bytes: Option<u32>,
+    
+    #[validate(custom(function = "validate_http_protocol"))]
+    pub http_protocol: Option<String>,
+    
+    pub ignore_health_on_host_removal: Option<bool>,
+    
+    pub wait_for_warm_on_init: Option<bool>,
+    
+    pub respect_dns_ttl: Option<bool>,
+    
+    #[validate(custom(function = "validate_health_check"))]
+    pub health_check: Option<crate::storage::HealthCheck>,
+    
+    #[validate(length(min = 1, max = 100), custom(function = "validate_stat_name"))]
+    pub alt_stat_name: Option<String>,
+    
+    #[validate(custom(function = "validate_ip_address"))]
+    pub source_address: Option<String>,
+    
+    #[validate(custom(function = "validate_retry_budget"))]
+    pub retry_budget: Option<crate::storage::RetryBudget>,
+    
+    #[validate(custom(function = "validate_cluster_metadata"))]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    
+    #[validate(custom(function = "validate_alpn_protocols"))]
+    pub alpn_protocols: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_dns_resolvers"))]
+    pub dns_resolvers: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_headers_with_underscores_action"))]
+    pub headers_with_underscores_action: Option<String>,
+    
+    #[validate(range(min = 1, max = 300))]
+    pub dns_failure_refresh_rate_seconds: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -144,6 +608,56 @@ pub struct ValidatedUpdateClusterRequest {
     
     #[validate(custom(function = "validate_lb_policy"))]
     pub lb_policy: Option<String>,
+    
+    #[validate(range(min = 2))]
+    pub choice_count: Option<u32>,
+    
+    pub locality_weighted_lb: Option<bool>,
+    
+    #[validate(custom(function = "validate_locality_weights"))]
+    pub locality_weights: Option<HashMap<String, u32>>,
+    
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Option<HashMap<String, String>>,
+    
+    #[validate(range(min = 1))]
+    pub per_connection_buffer_limit_bytes: Option<u32>,
+    
+    #[validate(custom(function = "validate_http_protocol"))]
+    pub http_protocol: Option<String>,
+    
+    pub ignore_health_on_host_removal: Option<bool>,
+    
+    pub wait_for_warm_on_init: Option<bool>,
+    
+    pub respect_dns_ttl: Option<bool>,
+    
+    #[validate(custom(function = "validate_health_check"))]
+    pub health_check: Option<crate::storage::HealthCheck>,
+    
+    #[validate(length(min = 1, max = 100), custom(function = "validate_stat_name"))]
+    pub alt_stat_name: Option<String>,
+    
+    #[validate(custom(function = "validate_ip_address"))]
+    pub source_address: Option<String>,
+    
+    #[validate(custom(function = "validate_retry_budget"))]
+    pub retry_budget: Option<crate::storage::RetryBudget>,
+    
+    #[validate(custom(function = "validate_cluster_metadata"))]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    
+    #[validate(custom(function = "validate_alpn_protocols"))]
+    pub alpn_protocols: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_dns_resolvers"))]
+    pub dns_resolvers: Option<Vec<String>>,
+    
+    #[validate(custom(function = "validate_headers_with_underscores_action"))]
+    pub headers_with_underscores_action: Option<String>,
+    
+    #[validate(range(min = 1, max = 300))]
+    pub dns_failure_refresh_rate_seconds: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -153,6 +667,23 @@ pub struct ValidatedCreateEndpointRequest {
     
     #[validate(range(min = 1, max = 65535))]
     pub port: u16,
+    
+    #[validate(custom(function = "validate_health_status"))]
+    pub health_status: Option<String>,
+
+    pub tls: Option<bool>,
+
+    #[validate(length(min = 1, max = 100))]
+    pub locality: Option<String>,
+
+    #[validate(length(min = 1, max = 253), custom(function = "validate_hostname"))]
+    pub hostname: Option<String>,
+
+    #[validate(range(min = 1, max = 1000))]
+    pub weight: Option<u32>,
+
+    #[validate(custom(function = "validate_additional_addresses"))]
+    pub additional_addresses: Option<Vec<String>>,
 }
 
 /// Conversion functions from validated to internal types
@@ -164,6 +695,23 @@ impl From<ValidatedCreateRouteRequest> for crate::api::handlers::CreateRouteRequ
             cluster_name: validated.cluster_name,
             prefix_rewrite: validated.prefix_rewrite,
             http_methods: validated.http_methods,
+            regex_rewrite: validated.regex_rewrite,
+            idle_timeout_seconds: validated.idle_timeout_seconds,
+            maintenance_mode: validated.maintenance_mode,
+            maintenance_body: validated.maintenance_body,
+            retry_policy: validated.retry_policy,
+            timeout_seconds: validated.timeout_seconds,
+            labels: validated.labels,
+            response_headers_to_add: validated.response_headers_to_add,
+            scheme_match: validated.scheme_match,
+            weighted_clusters: validated.weighted_clusters,
+            routing_priority: validated.routing_priority,
+            description: validated.description,
+            content_type_match: validated.content_type_match,
+            listener: validated.listener,
+            case_sensitive: validated.case_sensitive,
+            request_headers_to_remove: validated.request_headers_to_remove,
+            disabled_filters: validated.disabled_filters,
         }
     }
 }
@@ -175,6 +723,23 @@ impl From<ValidatedUpdateRouteRequest> for crate::api::handlers::UpdateRouteRequ
             cluster_name: validated.cluster_name,
             prefix_rewrite: validated.prefix_rewrite,
             http_methods: validated.http_methods,
+            regex_rewrite: validated.regex_rewrite,
+            idle_timeout_seconds: validated.idle_timeout_seconds,
+            maintenance_mode: validated.maintenance_mode,
+            maintenance_body: validated.maintenance_body,
+            retry_policy: validated.retry_policy,
+            timeout_seconds: validated.timeout_seconds,
+            labels: validated.labels,
+            response_headers_to_add: validated.response_headers_to_add,
+            scheme_match: validated.scheme_match,
+            weighted_clusters: validated.weighted_clusters,
+            routing_priority: validated.routing_priority,
+            description: validated.description,
+            content_type_match: validated.content_type_match,
+            listener: validated.listener,
+            case_sensitive: validated.case_sensitive,
+            request_headers_to_remove: validated.request_headers_to_remove,
+            disabled_filters: validated.disabled_filters,
         }
     }
 }
@@ -185,6 +750,24 @@ impl From<ValidatedCreateClusterRequest> for crate::api::handlers::CreateCluster
             name: validated.name,
             endpoints: validated.endpoints.into_iter().map(Into::into).collect(),
             lb_policy: validated.lb_policy,
+            choice_count: validated.choice_count,
+            locality_weighted_lb: validated.locality_weighted_lb,
+            locality_weights: validated.locality_weights,
+            labels: validated.labels,
+            per_connection_buffer_limit_bytes: validated.per_connection_buffer_limit_bytes,
+            http_protocol: validated.http_protocol,
+            ignore_health_on_host_removal: validated.ignore_health_on_host_removal,
+            wait_for_warm_on_init: validated.wait_for_warm_on_init,
+            respect_dns_ttl: validated.respect_dns_ttl,
+            health_check: validated.health_check,
+            alt_stat_name: validated.alt_stat_name,
+            source_address: validated.source_address,
+            retry_budget: validated.retry_budget,
+            metadata: validated.metadata,
+            alpn_protocols: validated.alpn_protocols,
+            dns_resolvers: validated.dns_resolvers,
+            headers_with_underscores_action: validated.headers_with_underscores_action,
+            dns_failure_refresh_rate_seconds: validated.dns_failure_refresh_rate_seconds,
         }
     }
 }
@@ -194,6 +777,24 @@ impl From<ValidatedUpdateClusterRequest> for crate::api::handlers::UpdateCluster
         Self {
             endpoints: validated.endpoints.into_iter().map(Into::into).collect(),
             lb_policy: validated.lb_policy,
+            choice_count: validated.choice_count,
+            locality_weighted_lb: validated.locality_weighted_lb,
+            locality_weights: validated.locality_weights,
+            labels: validated.labels,
+            per_connection_buffer_limit_bytes: validated.per_connection_buffer_limit_bytes,
+            http_protocol: validated.http_protocol,
+            ignore_health_on_host_removal: validated.ignore_health_on_host_removal,
+            wait_for_warm_on_init: validated.wait_for_warm_on_init,
+            respect_dns_ttl: validated.respect_dns_ttl,
+            health_check: validated.health_check,
+            alt_stat_name: validated.alt_stat_name,
+            source_address: validated.source_address,
+            retry_budget: validated.retry_budget,
+            metadata: validated.metadata,
+            alpn_protocols: validated.alpn_protocols,
+            dns_resolvers: validated.dns_resolvers,
+            headers_with_underscores_action: validated.headers_with_underscores_action,
+            dns_failure_refresh_rate_seconds: validated.dns_failure_refresh_rate_seconds,
         }
     }
 }
@@ -203,6 +804,12 @@ impl From<ValidatedCreateEndpointRequest> for crate::api::handlers::CreateEndpoi
         Self {
             host: validated.host,
             port: validated.port,
+            health_status: validated.health_status,
+            tls: validated.tls,
+            locality: validated.locality,
+            hostname: validated.hostname,
+            weight: validated.weight,
+            additional_addresses: validated.additional_addresses,
         }
     }
 }
@@ -256,4 +863,52 @@ mod tests {
         assert!(validate_lb_policy("LEAST_REQUEST").is_ok());
         assert!(validate_lb_policy("INVALID_POLICY").is_err());
     }
+
+    #[test]
+    fn test_regex_rewrite_validation() {
+        let valid = crate::storage::RegexRewrite {
+            pattern: r"^/service/([^/]+)(/.*)$".to_string(),
+            substitution: r"\2/instance/\1".to_string(),
+        };
+        assert!(validate_regex_rewrite(&valid).is_ok());
+
+        let invalid = crate::storage::RegexRewrite {
+            pattern: "^/service/(unclosed".to_string(),
+            substitution: "/".to_string(),
+        };
+        assert!(validate_regex_rewrite(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_labels_validation() {
+        let valid = HashMap::from([
+            ("team".to_string(), "payments".to_string()),
+            ("app.kubernetes.io/env".to_string(), "prod-eu".to_string()),
+        ]);
+        assert!(validate_labels(&valid).is_ok());
+
+        let invalid_key = HashMap::from([("team name".to_string(), "payments".to_string())]);
+        assert!(validate_labels(&invalid_key).is_err());
+
+        let invalid_value = HashMap::from([("team".to_string(), "pay;ments".to_string())]);
+        assert!(validate_labels(&invalid_value).is_err());
+    }
+
+    #[test]
+    fn test_hostname_validation() {
+        assert!(validate_hostname("api.internal.example.com").is_ok());
+        assert!(validate_hostname("localhost").is_ok());
+        assert!(validate_hostname("-leading-hyphen.example.com").is_err());
+        assert!(validate_hostname("double..dot.example.com").is_err());
+        assert!(validate_hostname("under_score.example.com").is_err());
+    }
+
+    #[test]
+    fn test_health_status_validation() {
+        assert!(validate_health_status("HEALTHY").is_ok());
+        assert!(validate_health_status("DEGRADED").is_ok());
+        assert!(validate_health_status("UNHEALTHY").is_ok());
+        assert!(validate_health_status("UNKNOWN").is_err());
+        assert!(validate_health_status("healthy").is_err()); // Case sensitive
+    }
 }
\ No newline at end of file
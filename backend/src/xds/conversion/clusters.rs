@@ -6,11 +6,37 @@ use prost_types::Any;
 use tracing::{info, warn};
 
 // Import Envoy protobuf types for clusters
-use envoy_types::pb::envoy::config::cluster::v3::Cluster;
-use envoy_types::pb::envoy::config::core::v3::{Address, SocketAddress};
+use envoy_types::pb::envoy::config::cluster::v3::cluster::{
+    self, common_lb_config, CommonLbConfig, LeastRequestLbConfig, RefreshRate, TransportSocketMatch,
+};
+use envoy_types::pb::envoy::config::cluster::v3::{circuit_breakers, CircuitBreakers, Cluster};
+use envoy_types::pb::envoy::config::core::v3::{
+    health_check::{HealthChecker, HttpHealthCheck},
+    Address, BindConfig, HealthCheck, Http1ProtocolOptions, Http2ProtocolOptions, Locality, Metadata, SocketAddress,
+    TransportSocket, TypedExtensionConfig,
+};
 use envoy_types::pb::envoy::config::endpoint::v3::{
-    ClusterLoadAssignment, Endpoint, LbEndpoint, LocalityLbEndpoints,
+    endpoint::AdditionalAddress, ClusterLoadAssignment, Endpoint, LbEndpoint, LocalityLbEndpoints,
+};
+use envoy_types::pb::envoy::extensions::network::dns_resolver::cares::v3::CaresDnsResolverConfig;
+use envoy_types::pb::envoy::extensions::transport_sockets::tls::v3::{CommonTlsContext, UpstreamTlsContext};
+use envoy_types::pb::envoy::extensions::upstreams::http::v3::http_protocol_options::{
+    explicit_http_config, ExplicitHttpConfig, UpstreamProtocolOptions, UseDownstreamHttpConfig,
 };
+use envoy_types::pb::envoy::extensions::upstreams::http::v3::HttpProtocolOptions;
+use envoy_types::pb::envoy::config::core::v3::http_protocol_options::HeadersWithUnderscoresAction;
+use envoy_types::pb::envoy::config::core::v3::HttpProtocolOptions as CoreHttpProtocolOptions;
+use envoy_types::pb::google::protobuf::{value::Kind, BoolValue, ListValue, Struct, UInt32Value, Value};
+use std::collections::HashMap;
+
+/// ALPN offered to TLS endpoints when a cluster sets no `alpn_protocols`
+const DEFAULT_UPSTREAM_ALPN_PROTOCOLS: [&str; 2] = ["h2", "http/1.1"];
+
+/// Metadata namespace Envoy consults when selecting a transport socket match
+const TRANSPORT_SOCKET_MATCH_KEY: &str = "envoy.transport_socket_match";
+
+/// Extension key Envoy reads upstream HTTP protocol options from
+const HTTP_PROTOCOL_OPTIONS_KEY: &str = "envoy.extensions.upstreams.http.v3.HttpProtocolOptions";
 
 /// Convert internal clusters to Envoy protobuf format
 pub fn clusters_to_proto(clusters: Vec<InternalCluster>) -> Result<Vec<Any>, ConversionError> {
@@ -29,52 +55,149 @@ pub fn clusters_to_proto(clusters: Vec<InternalCluster>) -> Result<Vec<Any>, Con
     let mut proto_clusters = Vec::new();
 
     for cluster in clusters {
+        let resolved_lb_policy =
+            cluster.effective_lb_policy(&app_config.control_plane.load_balancing.default_policy);
+
         // Validate cluster before conversion
-        validate_cluster(&cluster)?;
+        validate_cluster(&cluster, &resolved_lb_policy)?;
         
         let cluster_name = cluster.name.clone(); // Clone before moving
+        if let Some(limit) = cluster.per_connection_buffer_limit_bytes {
+            let ceiling = app_config.envoy_generation.cluster.max_buffer_limit_bytes;
+            if limit > ceiling {
+                return Err(ConversionError::InvalidResource {
+                    resource_type: "Cluster".to_string(),
+                    resource_id: cluster_name,
+                    reason: format!("per_connection_buffer_limit_bytes {limit} exceeds maximum {ceiling}"),
+                });
+            }
+        }
+        if cluster.respect_dns_ttl.is_some() && !app_config.envoy_generation.cluster.is_dns_discovery() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster_name,
+                reason: format!(
+                    "respect_dns_ttl is only valid for DNS discovery, not {}",
+                    app_config.envoy_generation.cluster.discovery_type
+                ),
+            });
+        }
+        if cluster.dns_resolvers.is_some() && !app_config.envoy_generation.cluster.is_dns_discovery() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster_name,
+                reason: format!(
+                    "dns_resolvers is only valid for DNS discovery, not {}",
+                    app_config.envoy_generation.cluster.discovery_type
+                ),
+            });
+        }
+        if cluster.dns_failure_refresh_rate_seconds.is_some()
+            && !app_config.envoy_generation.cluster.is_dns_discovery()
+        {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster_name,
+                reason: format!(
+                    "dns_failure_refresh_rate_seconds is only valid for DNS discovery, not {}",
+                    app_config.envoy_generation.cluster.discovery_type
+                ),
+            });
+        }
         info!(
             "  - Cluster: {} ({} endpoints)",
             cluster_name,
             cluster.endpoints.len()
         );
 
+        // Mixed TLS/plaintext clusters need per-endpoint transport socket matches
+        let has_tls_endpoints = cluster.endpoints.iter().any(|e| e.tls.is_some());
+        let endpoint_localities: Vec<Option<String>> = cluster.endpoints.iter().map(|e| e.locality.clone()).collect();
+
         // Create endpoints following the Go control plane pattern
         let lb_endpoints: Vec<LbEndpoint> = cluster.endpoints.into_iter().map(|endpoint| {
             info!("    - Endpoint: {}:{}", endpoint.host, endpoint.port);
 
+            let protocol = protocol_to_proto(&app_config.envoy_generation.cluster.default_protocol);
+            let additional_addresses = endpoint
+                .additional_addresses
+                .unwrap_or_default()
+                .into_iter()
+                .map(|address| AdditionalAddress { address: Some(socket_address(protocol, address, endpoint.port)) })
+                .collect();
+
             LbEndpoint {
                 host_identifier: Some(envoy_types::pb::envoy::config::endpoint::v3::lb_endpoint::HostIdentifier::Endpoint(
                     Endpoint {
-                        address: Some(Address {
-                            address: Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(
-                                SocketAddress {
-                                    protocol: protocol_to_proto(&app_config.envoy_generation.cluster.default_protocol),
-                                    address: endpoint.host,
-                                    port_specifier: Some(envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(endpoint.port as u32)),
-                                    ..Default::default()
-                                }
-                            )),
-                        }),
+                        address: Some(socket_address(protocol, endpoint.host, endpoint.port)),
+                        hostname: endpoint.hostname.unwrap_or_default(),
+                        additional_addresses,
                         ..Default::default()
                     }
                 )),
+                health_status: endpoint.health_status.as_deref().map_or(0, health_status_to_proto),
+                // In a mixed cluster, endpoints that don't say otherwise are plaintext
+                metadata: has_tls_endpoints.then(|| tls_match_metadata(endpoint.tls.unwrap_or(false))),
+                load_balancing_weight: endpoint.weight.map(|value| UInt32Value { value }),
                 ..Default::default()
             }
         }).collect();
 
+        let transport_socket_matches = if has_tls_endpoints {
+            let alpn_protocols = cluster
+                .alpn_protocols
+                .clone()
+                .unwrap_or_else(|| DEFAULT_UPSTREAM_ALPN_PROTOCOLS.into_iter().map(String::from).collect());
+            let matches = build_transport_socket_matches(alpn_protocols)?;
+            validate_transport_socket_coverage(&cluster_name, &lb_endpoints, &matches)?;
+            matches
+        } else {
+            vec![]
+        };
+
         // Create load assignment
         let load_assignment = ClusterLoadAssignment {
             cluster_name: cluster_name.clone(),
-            endpoints: vec![LocalityLbEndpoints {
-                lb_endpoints,
-                ..Default::default()
-            }],
+            endpoints: group_by_locality(lb_endpoints, endpoint_localities, cluster.locality_weights.as_ref()),
             ..Default::default()
         };
 
+        let startup_probe = cluster
+            .health_check
+            .as_ref()
+            .is_some_and(|health_check| health_check.initial_health_check_delay_seconds.is_some());
+
+        let common_lb_config = (cluster.locality_weighted_lb || startup_probe).then(|| CommonLbConfig {
+            locality_config_specifier: cluster.locality_weighted_lb.then_some(
+                common_lb_config::LocalityConfigSpecifier::LocalityWeightedLbConfig(
+                    common_lb_config::LocalityWeightedLbConfig {},
+                ),
+            ),
+            // Keep freshly added hosts out of rotation until their first probe passes
+            ignore_new_hosts_until_first_hc: startup_probe,
+            ..Default::default()
+        });
+
+        let health_checks = cluster.health_check.as_ref().map(health_check_to_proto).into_iter().collect();
+        let typed_dns_resolver_config = cluster
+            .dns_resolvers
+            .as_deref()
+            .map(|resolvers| dns_resolver_config(&cluster_name, resolvers))
+            .transpose()?;
+
+        // LEAST_REQUEST clusters may tune how many hosts are sampled per pick
+        let lb_config = match (&resolved_lb_policy, cluster.choice_count) {
+            (LoadBalancingPolicy::LeastRequest, Some(choice_count)) => {
+                Some(cluster::LbConfig::LeastRequestLbConfig(LeastRequestLbConfig {
+                    choice_count: Some(UInt32Value { value: choice_count }),
+                    ..Default::default()
+                }))
+            }
+            _ => None,
+        };
+
         // Determine load balancing policy
-        let lb_policy = match cluster.lb_policy.unwrap_or(LoadBalancingPolicy::RoundRobin) {
+        let lb_policy = match resolved_lb_policy {
             crate::storage::models::LoadBalancingPolicy::RoundRobin => {
                 envoy_types::pb::envoy::config::cluster::v3::cluster::LbPolicy::RoundRobin as i32
             }
@@ -93,6 +216,20 @@ pub fn clusters_to_proto(clusters: Vec<InternalCluster>) -> Result<Vec<Any>, Con
             }
         };
 
+        // Header validation alone still needs a codec; http1 keeps Envoy's default upstream protocol
+        let typed_extension_protocol_options =
+            if cluster.http_protocol.is_some() || cluster.headers_with_underscores_action.is_some() {
+                HashMap::from([(
+                    HTTP_PROTOCOL_OPTIONS_KEY.to_string(),
+                    http_protocol_options(
+                        cluster.http_protocol.as_deref().unwrap_or("http1"),
+                        cluster.headers_with_underscores_action.as_deref(),
+                    )?,
+                )])
+            } else {
+                HashMap::new()
+            };
+
         // Create the Envoy cluster
         let envoy_cluster = Cluster {
             name: cluster_name,
@@ -102,12 +239,46 @@ pub fn clusters_to_proto(clusters: Vec<InternalCluster>) -> Result<Vec<Any>, Con
                 ),
             ),
             lb_policy,
+            lb_config,
+            common_lb_config,
             load_assignment: Some(load_assignment),
             connect_timeout: Some(envoy_types::pb::google::protobuf::Duration {
                 seconds: app_config.envoy_generation.cluster.connect_timeout_seconds as i64,
                 nanos: 0,
             }),
             dns_lookup_family: dns_lookup_family_to_proto(&app_config.envoy_generation.cluster.dns_lookup_family),
+            transport_socket_matches,
+            per_connection_buffer_limit_bytes: cluster
+                .per_connection_buffer_limit_bytes
+                .map(|value| UInt32Value { value }),
+            typed_extension_protocol_options,
+            ignore_health_on_host_removal: cluster.ignore_health_on_host_removal.unwrap_or(false),
+            wait_for_warm_on_init: cluster.wait_for_warm_on_init.map(|value| BoolValue { value }),
+            respect_dns_ttl: cluster.respect_dns_ttl.unwrap_or(false),
+            // Envoy backs off from this base interval up to 10x while resolution keeps failing
+            dns_failure_refresh_rate: cluster.dns_failure_refresh_rate_seconds.map(|seconds| RefreshRate {
+                base_interval: Some(envoy_types::pb::google::protobuf::Duration {
+                    seconds: seconds as i64,
+                    nanos: 0,
+                }),
+                max_interval: None,
+            }),
+            typed_dns_resolver_config,
+            health_checks,
+            alt_stat_name: cluster.alt_stat_name.unwrap_or_default(),
+            circuit_breakers: cluster.retry_budget.as_ref().map(retry_budget_to_proto),
+            metadata: cluster.metadata.as_ref().map(cluster_metadata_to_proto),
+            // Egress from a fixed local IP, e.g. for upstream allow-lists
+            upstream_bind_config: cluster.source_address.map(|source_address| BindConfig {
+                source_address: Some(SocketAddress {
+                    address: source_address,
+                    port_specifier: Some(
+                        envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(0),
+                    ),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
             ..Default::default()
         };
 
@@ -127,6 +298,267 @@ pub fn clusters_to_proto(clusters: Vec<InternalCluster>) -> Result<Vec<Any>, Con
     Ok(proto_clusters)
 }
 
+/// Group endpoints into one LocalityLbEndpoints per locality, in first-seen order.
+/// Endpoints without a locality share a single unnamed group.
+fn group_by_locality(
+    lb_endpoints: Vec<LbEndpoint>,
+    localities: Vec<Option<String>>,
+    weights: Option<&HashMap<String, u32>>,
+) -> Vec<LocalityLbEndpoints> {
+    let mut groups: Vec<(Option<String>, Vec<LbEndpoint>)> = Vec::new();
+    for (lb_endpoint, locality) in lb_endpoints.into_iter().zip(localities) {
+        match groups.iter_mut().find(|(existing, _)| *existing == locality) {
+            Some((_, members)) => members.push(lb_endpoint),
+            None => groups.push((locality, vec![lb_endpoint])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(locality, lb_endpoints)| LocalityLbEndpoints {
+            load_balancing_weight: locality
+                .as_ref()
+                .and_then(|region| weights.and_then(|w| w.get(region)))
+                .map(|weight| UInt32Value { value: *weight }),
+            locality: locality.map(|region| Locality {
+                region,
+                ..Default::default()
+            }),
+            lb_endpoints,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Build a `{tls: <bool>}` criterion struct used by both endpoint metadata and socket matches
+fn tls_match_struct(tls: bool) -> Struct {
+    Struct {
+        fields: HashMap::from([(
+            "tls".to_string(),
+            Value {
+                kind: Some(Kind::BoolValue(tls)),
+            },
+        )]),
+    }
+}
+
+/// Cluster filter metadata; validation guarantees each namespace holds a JSON object
+fn cluster_metadata_to_proto(metadata: &HashMap<String, serde_json::Value>) -> Metadata {
+    let filter_metadata = metadata
+        .iter()
+        .filter_map(|(namespace, value)| match json_to_proto_value(value).kind {
+            Some(Kind::StructValue(fields)) => Some((namespace.clone(), fields)),
+            _ => None,
+        })
+        .collect();
+
+    Metadata {
+        filter_metadata,
+        ..Default::default()
+    }
+}
+
+pub(super) fn json_to_proto_value(value: &serde_json::Value) -> Value {
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(0),
+        serde_json::Value::Bool(value) => Kind::BoolValue(*value),
+        serde_json::Value::Number(number) => Kind::NumberValue(number.as_f64().unwrap_or_default()),
+        serde_json::Value::String(value) => Kind::StringValue(value.clone()),
+        serde_json::Value::Array(values) => Kind::ListValue(ListValue {
+            values: values.iter().map(json_to_proto_value).collect(),
+        }),
+        serde_json::Value::Object(fields) => Kind::StructValue(Struct {
+            fields: fields
+                .iter()
+                .map(|(key, value)| (key.clone(), json_to_proto_value(value)))
+                .collect(),
+        }),
+    };
+    Value { kind: Some(kind) }
+}
+
+/// Tag an endpoint so Envoy selects the matching transport socket for it
+fn tls_match_metadata(tls: bool) -> Metadata {
+    Metadata {
+        filter_metadata: HashMap::from([(TRANSPORT_SOCKET_MATCH_KEY.to_string(), tls_match_struct(tls))]),
+        ..Default::default()
+    }
+}
+
+/// Build transport socket matches: TLS for endpoints tagged `tls: true`, plaintext otherwise
+fn build_transport_socket_matches(alpn_protocols: Vec<String>) -> Result<Vec<TransportSocketMatch>, ConversionError> {
+    let tls_context = UpstreamTlsContext {
+        common_tls_context: Some(CommonTlsContext {
+            alpn_protocols,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut tls_buf = Vec::new();
+    tls_context
+        .encode(&mut tls_buf)
+        .map_err(|e| ConversionError::ProtobufEncoding {
+            resource_type: "UpstreamTlsContext".to_string(),
+            source: e,
+        })?;
+
+    Ok(vec![
+        TransportSocketMatch {
+            name: "tls".to_string(),
+            r#match: Some(tls_match_struct(true)),
+            transport_socket: Some(TransportSocket {
+                name: "envoy.transport_sockets.tls".to_string(),
+                config_type: Some(envoy_types::pb::envoy::config::core::v3::transport_socket::ConfigType::TypedConfig(
+                    envoy_types::pb::google::protobuf::Any {
+                        type_url: "type.googleapis.com/envoy.extensions.transport_sockets.tls.v3.UpstreamTlsContext".to_string(),
+                        value: tls_buf,
+                    }
+                )),
+            }),
+        },
+        TransportSocketMatch {
+            name: "plaintext".to_string(),
+            r#match: Some(tls_match_struct(false)),
+            transport_socket: Some(TransportSocket {
+                name: "envoy.transport_sockets.raw_buffer".to_string(),
+                config_type: None,
+            }),
+        },
+    ])
+}
+
+/// c-ares resolver config pointing the cluster's DNS lookups at specific `IP:port` resolvers
+fn dns_resolver_config(cluster_name: &str, resolvers: &[String]) -> Result<TypedExtensionConfig, ConversionError> {
+    let resolvers = resolvers
+        .iter()
+        .map(|resolver| {
+            let address = resolver
+                .parse::<std::net::SocketAddr>()
+                .map_err(|_| ConversionError::InvalidResource {
+                    resource_type: "Cluster".to_string(),
+                    resource_id: cluster_name.to_string(),
+                    reason: format!("dns_resolvers entry '{resolver}' is not an IP:port address"),
+                })?;
+            Ok(Address {
+                address: Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(
+                    SocketAddress {
+                        address: address.ip().to_string(),
+                        port_specifier: Some(
+                            envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(
+                                address.port() as u32,
+                            ),
+                        ),
+                        ..Default::default()
+                    },
+                )),
+            })
+        })
+        .collect::<Result<Vec<_>, ConversionError>>()?;
+
+    let config = CaresDnsResolverConfig {
+        resolvers,
+        ..Default::default()
+    };
+
+    let mut buf = Vec::new();
+    config.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "CaresDnsResolverConfig".to_string(),
+        source: e,
+    })?;
+
+    Ok(TypedExtensionConfig {
+        name: "envoy.network.dns_resolver.cares".to_string(),
+        typed_config: Some(envoy_types::pb::google::protobuf::Any {
+            type_url: "type.googleapis.com/envoy.extensions.network.dns_resolver.cares.v3.CaresDnsResolverConfig"
+                .to_string(),
+            value: buf,
+        }),
+    })
+}
+
+/// Build upstream HttpProtocolOptions: "auto" mirrors the downstream protocol,
+/// "http1"/"http2" pin the upstream codec
+fn http_protocol_options(
+    protocol: &str,
+    headers_with_underscores_action: Option<&str>,
+) -> Result<envoy_types::pb::google::protobuf::Any, ConversionError> {
+    let upstream_protocol_options = match protocol {
+        "auto" => UpstreamProtocolOptions::UseDownstreamProtocolConfig(UseDownstreamHttpConfig {
+            http_protocol_options: Some(Http1ProtocolOptions::default()),
+            http2_protocol_options: Some(Http2ProtocolOptions::default()),
+            ..Default::default()
+        }),
+        "http2" => UpstreamProtocolOptions::ExplicitHttpConfig(ExplicitHttpConfig {
+            protocol_config: Some(explicit_http_config::ProtocolConfig::Http2ProtocolOptions(
+                Http2ProtocolOptions::default(),
+            )),
+        }),
+        _ => UpstreamProtocolOptions::ExplicitHttpConfig(ExplicitHttpConfig {
+            protocol_config: Some(explicit_http_config::ProtocolConfig::HttpProtocolOptions(
+                Http1ProtocolOptions::default(),
+            )),
+        }),
+    };
+
+    let common_http_protocol_options = headers_with_underscores_action.map(|action| CoreHttpProtocolOptions {
+        headers_with_underscores_action: headers_with_underscores_action_to_proto(action),
+        ..Default::default()
+    });
+
+    let options = HttpProtocolOptions {
+        upstream_protocol_options: Some(upstream_protocol_options),
+        common_http_protocol_options,
+        ..Default::default()
+    };
+
+    let mut buf = Vec::new();
+    options.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "HttpProtocolOptions".to_string(),
+        source: e,
+    })?;
+
+    Ok(envoy_types::pb::google::protobuf::Any {
+        type_url: format!("type.googleapis.com/{}", HTTP_PROTOCOL_OPTIONS_KEY),
+        value: buf,
+    })
+}
+
+/// Ensure every endpoint is selected by at least one transport socket match
+fn validate_transport_socket_coverage(
+    cluster_name: &str,
+    lb_endpoints: &[LbEndpoint],
+    matches: &[TransportSocketMatch],
+) -> Result<(), ConversionError> {
+    for (i, lb_endpoint) in lb_endpoints.iter().enumerate() {
+        let endpoint_fields = lb_endpoint
+            .metadata
+            .as_ref()
+            .and_then(|m| m.filter_metadata.get(TRANSPORT_SOCKET_MATCH_KEY))
+            .map(|s| &s.fields);
+
+        let covered = matches.iter().any(|m| {
+            let criteria = m.r#match.as_ref().map(|s| &s.fields);
+            match criteria {
+                None => true,
+                Some(criteria) => criteria.iter().all(|(key, value)| {
+                    endpoint_fields.and_then(|fields| fields.get(key)) == Some(value)
+                }),
+            }
+        });
+
+        if !covered {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster_name.to_string(),
+                reason: format!("Endpoint {} is not covered by any transport socket match", i),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Convert discovery type string to Envoy protobuf enum
 fn discovery_type_to_proto(discovery_type: &str) -> i32 {
     use envoy_types::pb::envoy::config::cluster::v3::cluster::DiscoveryType;
@@ -157,6 +589,85 @@ fn dns_lookup_family_to_proto(dns_family: &str) -> i32 {
     }
 }
 
+/// Default-priority circuit breaker carrying only the retry budget; other limits keep Envoy's defaults
+fn retry_budget_to_proto(budget: &crate::storage::models::RetryBudget) -> CircuitBreakers {
+    CircuitBreakers {
+        thresholds: vec![circuit_breakers::Thresholds {
+            priority: envoy_types::pb::envoy::config::core::v3::RoutingPriority::Default as i32,
+            retry_budget: Some(circuit_breakers::thresholds::RetryBudget {
+                budget_percent: Some(envoy_types::pb::envoy::r#type::v3::Percent {
+                    value: budget.budget_percent,
+                }),
+                min_retry_concurrency: budget.min_retry_concurrency.map(|value| UInt32Value { value }),
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// Build a socket `Address` for an endpoint host (or one of its additional addresses)
+fn socket_address(protocol: i32, host: String, port: u16) -> Address {
+    Address {
+        address: Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(SocketAddress {
+            protocol,
+            address: host,
+            port_specifier: Some(envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(
+                port as u32,
+            )),
+            ..Default::default()
+        })),
+    }
+}
+
+/// Build an HTTP health check; the startup delay maps to Envoy's `initial_jitter`
+fn health_check_to_proto(health_check: &crate::storage::models::HealthCheck) -> HealthCheck {
+    let seconds = |value: u32| envoy_types::pb::google::protobuf::Duration {
+        seconds: value as i64,
+        nanos: 0,
+    };
+
+    HealthCheck {
+        timeout: Some(seconds(health_check.timeout_seconds)),
+        interval: Some(seconds(health_check.interval_seconds)),
+        initial_jitter: health_check.initial_health_check_delay_seconds.map(seconds),
+        healthy_threshold: Some(UInt32Value {
+            value: health_check.healthy_threshold.unwrap_or(2),
+        }),
+        unhealthy_threshold: Some(UInt32Value {
+            value: health_check.unhealthy_threshold.unwrap_or(3),
+        }),
+        health_checker: Some(HealthChecker::HttpHealthCheck(HttpHealthCheck {
+            path: health_check.path.clone(),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+}
+
+/// Convert a headers_with_underscores_action string to the Envoy protobuf enum
+fn headers_with_underscores_action_to_proto(action: &str) -> i32 {
+    match action {
+        "REJECT_REQUEST" => HeadersWithUnderscoresAction::RejectRequest as i32,
+        "DROP_HEADER" => HeadersWithUnderscoresAction::DropHeader as i32,
+        _ => HeadersWithUnderscoresAction::Allow as i32,
+    }
+}
+
+/// Convert endpoint health status string to Envoy protobuf enum
+fn health_status_to_proto(health_status: &str) -> i32 {
+    use envoy_types::pb::envoy::config::core::v3::HealthStatus;
+    match health_status {
+        "HEALTHY" => HealthStatus::Healthy as i32,
+        "DEGRADED" => HealthStatus::Degraded as i32,
+        "UNHEALTHY" => HealthStatus::Unhealthy as i32,
+        _ => {
+            warn!("Unknown endpoint health status '{}', defaulting to UNKNOWN", health_status);
+            HealthStatus::Unknown as i32
+        }
+    }
+}
+
 /// Convert protocol string to Envoy protobuf enum
 fn protocol_to_proto(protocol: &str) -> i32 {
     use envoy_types::pb::envoy::config::core::v3::socket_address::Protocol;
@@ -168,4 +679,646 @@ fn protocol_to_proto(protocol: &str) -> i32 {
             Protocol::Tcp as i32
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::Endpoint as InternalEndpoint;
+
+    #[test]
+    fn test_endpoint_health_status_conversion() {
+        let cluster = InternalCluster {
+            name: "health-status-cluster".to_string(),
+            endpoints: vec![
+                InternalEndpoint {
+                    host: "127.0.0.1".to_string(),
+                    port: 8080,
+                    health_status: Some("DEGRADED".to_string()),
+                    ..Default::default()
+                },
+                InternalEndpoint::new("127.0.0.1".to_string(), 8081),
+            ],
+            lb_policy: None,
+            ..Default::default()
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let lb_endpoints = &decoded.load_assignment.unwrap().endpoints[0].lb_endpoints;
+        assert_eq!(
+            lb_endpoints[0].health_status,
+            envoy_types::pb::envoy::config::core::v3::HealthStatus::Degraded as i32
+        );
+        assert_eq!(
+            lb_endpoints[1].health_status,
+            envoy_types::pb::envoy::config::core::v3::HealthStatus::Unknown as i32
+        );
+    }
+
+    #[test]
+    fn test_mixed_tls_endpoints_produce_transport_socket_matches() {
+        let cluster = InternalCluster {
+            name: "mixed-tls-cluster".to_string(),
+            endpoints: vec![
+                InternalEndpoint {
+                    host: "secure.internal".to_string(),
+                    port: 443,
+                    tls: Some(true),
+                    ..Default::default()
+                },
+                InternalEndpoint {
+                    host: "plain.internal".to_string(),
+                    port: 80,
+                    tls: Some(false),
+                    ..Default::default()
+                },
+            ],
+            lb_policy: None,
+            ..Default::default()
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let matches = &decoded.transport_socket_matches;
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].name, "tls");
+        assert_eq!(
+            matches[0].transport_socket.as_ref().unwrap().name,
+            "envoy.transport_sockets.tls"
+        );
+        assert_eq!(matches[1].name, "plaintext");
+        assert_eq!(
+            matches[1].transport_socket.as_ref().unwrap().name,
+            "envoy.transport_sockets.raw_buffer"
+        );
+
+        // Each endpoint carries the metadata its transport socket match selects on
+        let lb_endpoints = &decoded.load_assignment.unwrap().endpoints[0].lb_endpoints;
+        let tls_metadata = &lb_endpoints[0].metadata.as_ref().unwrap().filter_metadata[TRANSPORT_SOCKET_MATCH_KEY];
+        assert_eq!(tls_metadata.fields["tls"].kind, Some(Kind::BoolValue(true)));
+        let plain_metadata = &lb_endpoints[1].metadata.as_ref().unwrap().filter_metadata[TRANSPORT_SOCKET_MATCH_KEY];
+        assert_eq!(plain_metadata.fields["tls"].kind, Some(Kind::BoolValue(false)));
+    }
+
+    #[test]
+    fn test_plaintext_cluster_has_no_transport_socket_matches() {
+        let cluster = InternalCluster::new(
+            "plain-cluster".to_string(),
+            vec![InternalEndpoint::new("127.0.0.1".to_string(), 8080)],
+        );
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        assert!(decoded.transport_socket_matches.is_empty());
+    }
+
+    #[test]
+    fn test_least_request_choice_count_sets_lb_config() {
+        let cluster = InternalCluster {
+            choice_count: Some(5),
+            ..InternalCluster::with_lb_policy(
+                "least-request-cluster".to_string(),
+                vec![InternalEndpoint::new("127.0.0.1".to_string(), 8080)],
+                LoadBalancingPolicy::LeastRequest,
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        match decoded.lb_config {
+            Some(cluster::LbConfig::LeastRequestLbConfig(config)) => {
+                assert_eq!(config.choice_count, Some(UInt32Value { value: 5 }));
+            }
+            other => panic!("expected LeastRequestLbConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_choice_count_rejected_for_non_least_request_policy() {
+        let cluster = InternalCluster {
+            choice_count: Some(3),
+            ..InternalCluster::with_lb_policy(
+                "round-robin-cluster".to_string(),
+                vec![InternalEndpoint::new("127.0.0.1".to_string(), 8080)],
+                LoadBalancingPolicy::RoundRobin,
+            )
+        };
+
+        assert!(clusters_to_proto(vec![cluster]).is_err());
+    }
+
+    #[test]
+    fn test_locality_weighted_cluster_sets_weights_per_locality() {
+        let endpoint = |host: &str, locality: &str| InternalEndpoint {
+            locality: Some(locality.to_string()),
+            ..InternalEndpoint::new(host.to_string(), 8080)
+        };
+        let cluster = InternalCluster {
+            name: "regional-cluster".to_string(),
+            endpoints: vec![
+                endpoint("10.0.0.1", "us-east-1"),
+                endpoint("10.1.0.1", "eu-west-1"),
+                endpoint("10.0.0.2", "us-east-1"),
+            ],
+            locality_weighted_lb: true,
+            locality_weights: Some(HashMap::from([
+                ("us-east-1".to_string(), 80),
+                ("eu-west-1".to_string(), 20),
+            ])),
+            ..Default::default()
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        assert!(matches!(
+            decoded.common_lb_config.unwrap().locality_config_specifier,
+            Some(common_lb_config::LocalityConfigSpecifier::LocalityWeightedLbConfig(_))
+        ));
+
+        let localities = decoded.load_assignment.unwrap().endpoints;
+        assert_eq!(localities.len(), 2);
+        assert_eq!(localities[0].locality.as_ref().unwrap().region, "us-east-1");
+        assert_eq!(localities[0].load_balancing_weight, Some(UInt32Value { value: 80 }));
+        assert_eq!(localities[0].lb_endpoints.len(), 2);
+        assert_eq!(localities[1].locality.as_ref().unwrap().region, "eu-west-1");
+        assert_eq!(localities[1].load_balancing_weight, Some(UInt32Value { value: 20 }));
+        assert_eq!(localities[1].lb_endpoints.len(), 1);
+    }
+
+    #[test]
+    fn test_locality_weighted_cluster_requires_weight_for_every_locality() {
+        let cluster = InternalCluster {
+            name: "unweighted-regional-cluster".to_string(),
+            endpoints: vec![InternalEndpoint {
+                locality: Some("ap-south-1".to_string()),
+                ..InternalEndpoint::new("10.2.0.1".to_string(), 8080)
+            }],
+            locality_weighted_lb: true,
+            locality_weights: Some(HashMap::from([("us-east-1".to_string(), 100)])),
+            ..Default::default()
+        };
+
+        assert!(clusters_to_proto(vec![cluster]).is_err());
+    }
+
+    #[test]
+    fn test_endpoint_hostname_is_carried_on_endpoint() {
+        let cluster = InternalCluster::new(
+            "sni-cluster".to_string(),
+            vec![InternalEndpoint {
+                hostname: Some("api.internal.example.com".to_string()),
+                ..InternalEndpoint::new("10.0.0.5".to_string(), 443)
+            }],
+        );
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let lb_endpoint = &decoded.load_assignment.unwrap().endpoints[0].lb_endpoints[0];
+        match &lb_endpoint.host_identifier {
+            Some(envoy_types::pb::envoy::config::endpoint::v3::lb_endpoint::HostIdentifier::Endpoint(endpoint)) => {
+                assert_eq!(endpoint.hostname, "api.internal.example.com");
+            }
+            other => panic!("expected endpoint host identifier, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_per_connection_buffer_limit_is_set_on_cluster() {
+        let cluster = InternalCluster {
+            per_connection_buffer_limit_bytes: Some(32768),
+            ..InternalCluster::new(
+                "buffered-cluster".to_string(),
+                vec![InternalEndpoint::new("10.0.0.6".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        assert_eq!(decoded.per_connection_buffer_limit_bytes, Some(UInt32Value { value: 32768 }));
+    }
+
+    #[test]
+    fn test_per_connection_buffer_limit_above_ceiling_rejected() {
+        let cluster = InternalCluster {
+            per_connection_buffer_limit_bytes: Some(u32::MAX),
+            ..InternalCluster::new(
+                "oversized-cluster".to_string(),
+                vec![InternalEndpoint::new("10.0.0.7".to_string(), 8080)],
+            )
+        };
+
+        assert!(clusters_to_proto(vec![cluster]).is_err());
+    }
+
+    #[test]
+    fn test_auto_http_protocol_uses_downstream_protocol() {
+        let cluster = InternalCluster {
+            http_protocol: Some("auto".to_string()),
+            ..InternalCluster::new(
+                "mixed-backend".to_string(),
+                vec![InternalEndpoint::new("10.0.0.8".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let options_any = decoded
+            .typed_extension_protocol_options
+            .get(HTTP_PROTOCOL_OPTIONS_KEY)
+            .expect("HttpProtocolOptions should be set");
+        let options = HttpProtocolOptions::decode(&options_any.value[..]).unwrap();
+        match options.upstream_protocol_options {
+            Some(UpstreamProtocolOptions::UseDownstreamProtocolConfig(config)) => {
+                assert!(config.http_protocol_options.is_some());
+                assert!(config.http2_protocol_options.is_some());
+            }
+            other => panic!("expected downstream protocol passthrough, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_warm_up_flags_set_on_cluster() {
+        let cluster = InternalCluster {
+            ignore_health_on_host_removal: Some(true),
+            wait_for_warm_on_init: Some(false),
+            ..InternalCluster::new(
+                "warming-cluster".to_string(),
+                vec![InternalEndpoint::new("10.0.0.8".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        assert!(decoded.ignore_health_on_host_removal);
+        assert_eq!(decoded.wait_for_warm_on_init, Some(BoolValue { value: false }));
+    }
+
+    #[test]
+    fn test_respect_dns_ttl_set_on_dns_cluster() {
+        let cluster = InternalCluster {
+            respect_dns_ttl: Some(true),
+            ..InternalCluster::new(
+                "dns-ttl-cluster".to_string(),
+                vec![InternalEndpoint::new("api.internal.example.com".to_string(), 443)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        // config.yaml generates STRICT_DNS clusters
+        assert!(matches!(
+            decoded.cluster_discovery_type,
+            Some(cluster::ClusterDiscoveryType::Type(t)) if t == cluster::DiscoveryType::StrictDns as i32
+        ));
+        assert!(decoded.respect_dns_ttl);
+    }
+
+    #[test]
+    fn test_dns_failure_refresh_rate_set_on_dns_cluster() {
+        let cluster = InternalCluster {
+            dns_failure_refresh_rate_seconds: Some(2),
+            ..InternalCluster::new(
+                "dns-retry-cluster".to_string(),
+                vec![InternalEndpoint::new("api.internal.example.com".to_string(), 443)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let refresh_rate = decoded.dns_failure_refresh_rate.unwrap();
+        assert_eq!(refresh_rate.base_interval.unwrap().seconds, 2);
+        assert!(refresh_rate.max_interval.is_none());
+    }
+
+    #[test]
+    fn test_initial_health_check_delay_set_on_health_check() {
+        let cluster = InternalCluster {
+            health_check: Some(crate::storage::models::HealthCheck {
+                path: "/ready".to_string(),
+                interval_seconds: 10,
+                timeout_seconds: 2,
+                healthy_threshold: None,
+                unhealthy_threshold: None,
+                initial_health_check_delay_seconds: Some(30),
+            }),
+            ..InternalCluster::new(
+                "probed-cluster".to_string(),
+                vec![InternalEndpoint::new("10.0.0.1".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let health_check = &decoded.health_checks[0];
+        assert_eq!(health_check.initial_jitter.as_ref().map(|d| d.seconds), Some(30));
+        assert_eq!(health_check.interval.as_ref().map(|d| d.seconds), Some(10));
+        assert!(matches!(
+            &health_check.health_checker,
+            Some(HealthChecker::HttpHealthCheck(http)) if http.path == "/ready"
+        ));
+        assert!(decoded.common_lb_config.unwrap().ignore_new_hosts_until_first_hc);
+    }
+
+    #[test]
+    fn test_alt_stat_name_set_on_cluster() {
+        let cluster = InternalCluster {
+            alt_stat_name: Some("payments".to_string()),
+            ..InternalCluster::new(
+                "payments-v2".to_string(),
+                vec![InternalEndpoint::new("10.0.0.5".to_string(), 8443)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        assert_eq!(decoded.name, "payments-v2");
+        assert_eq!(decoded.alt_stat_name, "payments");
+    }
+
+    #[test]
+    fn test_source_address_sets_upstream_bind_config() {
+        let cluster = InternalCluster {
+            source_address: Some("10.0.0.5".to_string()),
+            ..InternalCluster::new(
+                "egress-cluster".to_string(),
+                vec![InternalEndpoint::new("partner.example.com".to_string(), 443)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let source_address = decoded.upstream_bind_config.unwrap().source_address.unwrap();
+        assert_eq!(source_address.address, "10.0.0.5");
+
+        let invalid = InternalCluster {
+            source_address: Some("not-an-ip".to_string()),
+            ..InternalCluster::new(
+                "egress-cluster".to_string(),
+                vec![InternalEndpoint::new("partner.example.com".to_string(), 443)],
+            )
+        };
+        assert!(clusters_to_proto(vec![invalid]).is_err());
+    }
+
+    #[test]
+    fn test_retry_budget_set_on_circuit_breaker() {
+        let cluster = InternalCluster {
+            retry_budget: Some(crate::storage::models::RetryBudget {
+                budget_percent: 25.0,
+                min_retry_concurrency: Some(5),
+            }),
+            ..InternalCluster::new(
+                "orders".to_string(),
+                vec![InternalEndpoint::new("orders.internal".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let thresholds = &decoded.circuit_breakers.unwrap().thresholds[0];
+        let retry_budget = thresholds.retry_budget.as_ref().unwrap();
+        assert_eq!(retry_budget.budget_percent.as_ref().unwrap().value, 25.0);
+        assert_eq!(retry_budget.min_retry_concurrency, Some(UInt32Value { value: 5 }));
+        // Only the budget is set; connection and request limits keep Envoy's defaults
+        assert!(thresholds.max_retries.is_none());
+    }
+
+    #[test]
+    fn test_cluster_metadata_set_as_filter_metadata() {
+        let cluster = InternalCluster {
+            metadata: Some(HashMap::from([(
+                "envoy.lb".to_string(),
+                serde_json::json!({"canary": true, "version": "v2"}),
+            )])),
+            ..InternalCluster::new(
+                "orders".to_string(),
+                vec![InternalEndpoint::new("orders.internal".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let lb_metadata = &decoded.metadata.unwrap().filter_metadata["envoy.lb"];
+        assert_eq!(lb_metadata.fields["canary"].kind, Some(Kind::BoolValue(true)));
+        assert_eq!(lb_metadata.fields["version"].kind, Some(Kind::StringValue("v2".to_string())));
+
+        let not_an_object = InternalCluster {
+            metadata: Some(HashMap::from([("envoy.lb".to_string(), serde_json::json!("canary"))])),
+            ..InternalCluster::new(
+                "orders".to_string(),
+                vec![InternalEndpoint::new("orders.internal".to_string(), 8080)],
+            )
+        };
+        assert!(clusters_to_proto(vec![not_an_object]).is_err());
+    }
+
+    #[test]
+    fn test_tls_transport_socket_carries_alpn_protocols() {
+        let tls_alpn = |cluster: InternalCluster| {
+            let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+            let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+            let transport_socket = decoded.transport_socket_matches[0].transport_socket.clone().unwrap();
+            let Some(envoy_types::pb::envoy::config::core::v3::transport_socket::ConfigType::TypedConfig(any)) =
+                transport_socket.config_type
+            else {
+                panic!("Expected typed TLS config");
+            };
+            UpstreamTlsContext::decode(&any.value[..]).unwrap().common_tls_context.unwrap().alpn_protocols
+        };
+        let tls_cluster = || {
+            InternalCluster::new(
+                "grpc-backend".to_string(),
+                vec![InternalEndpoint {
+                    host: "grpc.internal".to_string(),
+                    port: 443,
+                    tls: Some(true),
+                    ..Default::default()
+                }],
+            )
+        };
+
+        assert_eq!(tls_alpn(tls_cluster()), vec!["h2", "http/1.1"]);
+
+        let h2_only = InternalCluster {
+            alpn_protocols: Some(vec!["h2".to_string()]),
+            ..tls_cluster()
+        };
+        assert_eq!(tls_alpn(h2_only), vec!["h2"]);
+    }
+
+    #[test]
+    fn test_dns_resolvers_set_on_dns_cluster() {
+        let cluster = InternalCluster {
+            dns_resolvers: Some(vec!["10.0.0.2:53".to_string(), "[fd00::2]:5353".to_string()]),
+            ..InternalCluster::new(
+                "internal-dns-cluster".to_string(),
+                vec![InternalEndpoint::new("api.corp.internal".to_string(), 443)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let resolver_config = decoded.typed_dns_resolver_config.unwrap();
+        assert_eq!(resolver_config.name, "envoy.network.dns_resolver.cares");
+
+        let cares = CaresDnsResolverConfig::decode(&resolver_config.typed_config.unwrap().value[..]).unwrap();
+        let resolvers: Vec<(String, u32)> = cares
+            .resolvers
+            .into_iter()
+            .map(|resolver| match resolver.address {
+                Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(socket)) => {
+                    let port = match socket.port_specifier {
+                        Some(envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(port)) => port,
+                        _ => 0,
+                    };
+                    (socket.address, port)
+                }
+                _ => panic!("resolver is not a socket address"),
+            })
+            .collect();
+        assert_eq!(resolvers, vec![("10.0.0.2".to_string(), 53), ("fd00::2".to_string(), 5353)]);
+
+        let invalid = InternalCluster {
+            dns_resolvers: Some(vec!["10.0.0.2".to_string()]),
+            ..InternalCluster::new(
+                "internal-dns-cluster".to_string(),
+                vec![InternalEndpoint::new("api.corp.internal".to_string(), 443)],
+            )
+        };
+        assert!(clusters_to_proto(vec![invalid]).is_err());
+    }
+
+    #[test]
+    fn test_endpoint_weights_set_load_balancing_weight() {
+        let weighted = |host: &str, weight| InternalEndpoint {
+            weight: Some(weight),
+            ..InternalEndpoint::new(host.to_string(), 8080)
+        };
+        let cluster = InternalCluster::new(
+            "weighted-cluster".to_string(),
+            vec![weighted("10.0.0.1", 3), weighted("10.0.0.2", 1)],
+        );
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let weights: Vec<Option<u32>> = decoded.load_assignment.unwrap().endpoints[0]
+            .lb_endpoints
+            .iter()
+            .map(|lb_endpoint| lb_endpoint.load_balancing_weight.as_ref().map(|w| w.value))
+            .collect();
+        assert_eq!(weights, vec![Some(3), Some(1)]);
+
+        // Weights on only some endpoints are rejected
+        let partial = InternalCluster::new(
+            "weighted-cluster".to_string(),
+            vec![weighted("10.0.0.1", 3), InternalEndpoint::new("10.0.0.2".to_string(), 8080)],
+        );
+        assert!(clusters_to_proto(vec![partial]).is_err());
+    }
+
+    #[test]
+    fn test_headers_with_underscores_action_set_on_protocol_options() {
+        let cluster = InternalCluster {
+            headers_with_underscores_action: Some("REJECT_REQUEST".to_string()),
+            ..InternalCluster::new(
+                "strict-upstream".to_string(),
+                vec![InternalEndpoint::new("127.0.0.1".to_string(), 8080)],
+            )
+        };
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let options_any = decoded
+            .typed_extension_protocol_options
+            .get(HTTP_PROTOCOL_OPTIONS_KEY)
+            .expect("HttpProtocolOptions should be set");
+        let options = HttpProtocolOptions::decode(&options_any.value[..]).unwrap();
+
+        assert_eq!(
+            options.common_http_protocol_options.unwrap().headers_with_underscores_action,
+            HeadersWithUnderscoresAction::RejectRequest as i32
+        );
+        // Without http_protocol the upstream codec stays HTTP/1.1
+        assert!(matches!(
+            options.upstream_protocol_options,
+            Some(UpstreamProtocolOptions::ExplicitHttpConfig(ExplicitHttpConfig {
+                protocol_config: Some(explicit_http_config::ProtocolConfig::HttpProtocolOptions(_)),
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_endpoint_additional_addresses_are_carried_on_endpoint() {
+        let cluster = InternalCluster::new(
+            "dual-stack-cluster".to_string(),
+            vec![InternalEndpoint {
+                additional_addresses: Some(vec!["2001:db8::5".to_string()]),
+                ..InternalEndpoint::new("10.0.0.5".to_string(), 8080)
+            }],
+        );
+
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+
+        let lb_endpoint = &decoded.load_assignment.unwrap().endpoints[0].lb_endpoints[0];
+        let Some(envoy_types::pb::envoy::config::endpoint::v3::lb_endpoint::HostIdentifier::Endpoint(endpoint)) =
+            &lb_endpoint.host_identifier
+        else {
+            panic!("expected endpoint host identifier, got {:?}", lb_endpoint.host_identifier);
+        };
+        assert_eq!(endpoint.additional_addresses.len(), 1);
+        match endpoint.additional_addresses[0].address.as_ref().and_then(|a| a.address.as_ref()) {
+            Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(socket)) => {
+                assert_eq!(socket.address, "2001:db8::5");
+                assert_eq!(
+                    socket.port_specifier,
+                    Some(envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(8080))
+                );
+            }
+            other => panic!("expected socket address, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_without_matching_transport_socket_rejected() {
+        let cluster = InternalCluster {
+            name: "mixed-tls-cluster".to_string(),
+            endpoints: vec![
+                InternalEndpoint {
+                    host: "secure.internal".to_string(),
+                    port: 443,
+                    tls: Some(true),
+                    ..Default::default()
+                },
+                InternalEndpoint::new("legacy.internal".to_string(), 80),
+            ],
+            ..Default::default()
+        };
+
+        // An endpoint that doesn't set tls is tagged plaintext, so the plaintext match covers it
+        let proto_clusters = clusters_to_proto(vec![cluster]).unwrap();
+        let decoded = Cluster::decode(&proto_clusters[0].value[..]).unwrap();
+        let lb_endpoints = &decoded.load_assignment.unwrap().endpoints[0].lb_endpoints;
+        let legacy_metadata = &lb_endpoints[1].metadata.as_ref().unwrap().filter_metadata[TRANSPORT_SOCKET_MATCH_KEY];
+        assert_eq!(legacy_metadata.fields["tls"].kind, Some(Kind::BoolValue(false)));
+
+        let matches = build_transport_socket_matches(vec![]).unwrap();
+        let untagged = LbEndpoint::default();
+        let result = validate_transport_socket_coverage("mixed-tls-cluster", &[untagged], &matches);
+        assert!(
+            matches!(result, Err(ConversionError::InvalidResource { reason, .. }) if reason.contains("Endpoint 0 is not covered"))
+        );
+    }
+}
@@ -1,18 +1,44 @@
+use super::clusters::json_to_proto_value;
 use super::errors::ConversionError;
+use super::routes::route_config_name_for;
 use super::utils::{load_config_with_fallback, get_envoy_filter_name};
 use crate::storage::models::HttpFilter as InternalHttpFilter;
+use crate::xds::filters::request_validation::denied_response_mapper;
 use crate::xds::filters::FilterStrategyRegistry;
 use prost::Message;
 use prost_types::Any;
 use tracing::info;
 
 // Import Envoy protobuf types for listeners and HTTP filters
-use envoy_types::pb::envoy::config::core::v3::{Address, SocketAddress};
-use envoy_types::pb::envoy::config::listener::v3::{Filter, FilterChain, Listener};
+use envoy_types::pb::envoy::config::accesslog::v3::{
+    access_log, access_log_filter::FilterSpecifier, comparison_filter, AccessLog, AccessLogFilter, AndFilter,
+    ComparisonFilter, RuntimeFilter, StatusCodeFilter,
+};
+use envoy_types::pb::envoy::config::core::v3::{
+    data_source, substitution_format_string, Address, DataSource, Http3ProtocolOptions, RuntimeUInt32, SocketAddress,
+    SubstitutionFormatString, TransportSocket, TypedExtensionConfig,
+};
+use envoy_types::pb::envoy::extensions::access_loggers::file::v3::FileAccessLog;
+use envoy_types::pb::envoy::r#type::v3::{fractional_percent::DenominatorType, FractionalPercent};
+use envoy_types::pb::envoy::config::listener::v3::{
+    listener::{connection_balance_config, ConnectionBalanceConfig},
+    Filter, FilterChain, Listener, QuicProtocolOptions, UdpListenerConfig,
+};
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::{
-    HttpConnectionManager, HttpFilter, Rds,
+    http_connection_manager::{CodecType, RouteSpecifier, ServerHeaderTransformation},
+    HttpConnectionManager, HttpFilter, LocalReplyConfig, Rds, ResponseMapper,
+};
+use envoy_types::pb::envoy::extensions::transport_sockets::quic::v3::QuicDownstreamTransport;
+use envoy_types::pb::envoy::extensions::transport_sockets::tls::v3::{
+    CommonTlsContext, DownstreamTlsContext, TlsCertificate,
+};
+use envoy_types::pb::envoy::extensions::compression::gzip::compressor::v3::Gzip;
+use envoy_types::pb::envoy::extensions::filters::http::compressor::v3::{
+    compressor::{CommonDirectionConfig, ResponseDirectionConfig},
+    Compressor,
 };
 use envoy_types::pb::envoy::extensions::filters::http::router::v3::Router;
+use envoy_types::pb::google::protobuf::{Struct, UInt32Value};
 
 /// Convert listeners with HTTP filters to Envoy protobuf format
 /// This function integrates with the FilterStrategyRegistry for HTTP filter conversion
@@ -25,6 +51,7 @@ pub fn listeners_to_proto(store: &crate::storage::ConfigStore) -> Result<Vec<Any
     // Get all HTTP filters from store
     let http_filters = store.list_http_filters();
     let http_filters: Vec<InternalHttpFilter> = http_filters.iter().map(|f| (**f).clone()).collect();
+    let local_reply_mappers = request_validation_reply_mappers(&http_filters)?;
 
     // Convert HTTP filters to Envoy format using FilterStrategyRegistry
     let envoy_http_filters = convert_http_filters(
@@ -33,58 +60,246 @@ pub fn listeners_to_proto(store: &crate::storage::ConfigStore) -> Result<Vec<Any
         &app_config,
     )?;
 
+    // QUIC listener shares the converted filter chain with the TCP listener
+    let quic_listener = if app_config.envoy_generation.listener.http3 {
+        Some(build_quic_listener(&app_config, envoy_http_filters.clone(), &local_reply_mappers)?)
+    } else {
+        None
+    };
+
+    // Additional listeners share the filter chain but route through their own RouteConfiguration
+    let additional_listeners = app_config
+        .envoy_generation
+        .listener
+        .additional_listeners
+        .iter()
+        .map(|additional| {
+            build_additional_listener(&app_config, additional, envoy_http_filters.clone(), &local_reply_mappers)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
     // Create HTTP Connection Manager with filters
+    let http_conn_manager = build_http_connection_manager(&app_config, envoy_http_filters, &local_reply_mappers)?;
+
+    // Encode HTTP Connection Manager
+    let mut hcm_buf = Vec::new();
+    http_conn_manager.encode(&mut hcm_buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "HttpConnectionManager".to_string(),
+        source: e,
+    })?;
+
+    // Create main listener
+    let listener = build_main_listener(&app_config, hcm_buf);
+
+    // Encode listener
+    let mut listener_buf = Vec::new();
+    listener.encode(&mut listener_buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "Listener".to_string(),
+        source: e,
+    })?;
+
+    info!("✅ Listener conversion complete with {} HTTP filters integrated", 
+          http_conn_manager.http_filters.len());
+
+    let mut listeners = vec![Any {
+        type_url: "type.googleapis.com/envoy.config.listener.v3.Listener".to_string(),
+        value: listener_buf,
+    }];
+
+    for additional_listener in additional_listeners {
+        let mut additional_buf = Vec::new();
+        additional_listener.encode(&mut additional_buf).map_err(|e| ConversionError::ProtobufEncoding {
+            resource_type: "Listener".to_string(),
+            source: e,
+        })?;
+
+        info!("✅ Additional listener '{}' added", additional_listener.name);
+        listeners.push(Any {
+            type_url: "type.googleapis.com/envoy.config.listener.v3.Listener".to_string(),
+            value: additional_buf,
+        });
+    }
+
+    if let Some(quic_listener) = quic_listener {
+        let mut quic_buf = Vec::new();
+        quic_listener.encode(&mut quic_buf).map_err(|e| ConversionError::ProtobufEncoding {
+            resource_type: "Listener".to_string(),
+            source: e,
+        })?;
+
+        info!("✅ QUIC listener '{}' added for HTTP/3", quic_listener.name);
+        listeners.push(Any {
+            type_url: "type.googleapis.com/envoy.config.listener.v3.Listener".to_string(),
+            value: quic_buf,
+        });
+    }
+
+    Ok(listeners)
+}
+
+/// Build the main TCP listener around an encoded HTTP connection manager
+fn build_main_listener(app_config: &crate::config::AppConfig, hcm_buf: Vec<u8>) -> Listener {
+    build_tcp_listener(
+        app_config,
+        app_config.envoy_generation.bootstrap.main_listener_name.clone(),
+        app_config.envoy_generation.listener.default_port,
+        hcm_buf,
+    )
+}
+
+/// Build an additional TCP listener whose HCM fetches the route configuration named after it
+fn build_additional_listener(
+    app_config: &crate::config::AppConfig,
+    additional: &crate::config::AdditionalListenerConfig,
+    http_filters: Vec<HttpFilter>,
+    local_reply_mappers: &[ResponseMapper],
+) -> Result<Listener, ConversionError> {
+    let route_config_name =
+        route_config_name_for(app_config, Some(&additional.name)).ok_or_else(|| ConversionError::InvalidResource {
+            resource_type: "Listener".to_string(),
+            resource_id: additional.name.clone(),
+            reason: "Additional listener is not configured".to_string(),
+        })?;
+
     let http_conn_manager = HttpConnectionManager {
-        stat_prefix: app_config.envoy_generation.http_filters.stat_prefix.clone(),
-        route_specifier: Some(
-            envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_connection_manager::RouteSpecifier::Rds(
-                Rds {
-                    config_source: Some(
-                        envoy_types::pb::envoy::config::core::v3::ConfigSource {
-                            config_source_specifier: Some(
-                                envoy_types::pb::envoy::config::core::v3::config_source::ConfigSourceSpecifier::Ads(
-                                    envoy_types::pb::envoy::config::core::v3::AggregatedConfigSource {
-                                        ..Default::default()
-                                    }
-                                )
-                            ),
-                            resource_api_version: envoy_types::pb::envoy::config::core::v3::ApiVersion::V3 as i32,
-                            ..Default::default()
-                        }
+        route_specifier: Some(rds_route_specifier(route_config_name)),
+        ..build_http_connection_manager(app_config, http_filters, local_reply_mappers)?
+    };
+
+    let mut hcm_buf = Vec::new();
+    http_conn_manager.encode(&mut hcm_buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "HttpConnectionManager".to_string(),
+        source: e,
+    })?;
+
+    Ok(build_tcp_listener(app_config, additional.name.clone(), additional.port, hcm_buf))
+}
+
+/// Build a TCP listener on the binding address around an encoded HTTP connection manager
+fn build_tcp_listener(app_config: &crate::config::AppConfig, name: String, port: u16, hcm_buf: Vec<u8>) -> Listener {
+    let listener_config = &app_config.envoy_generation.listener;
+
+    Listener {
+        name,
+        address: Some(Address {
+            address: Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(
+                SocketAddress {
+                    protocol: protocol_to_proto(&app_config.envoy_generation.cluster.default_protocol),
+                    address: listener_config.binding_address.clone(),
+                    port_specifier: Some(
+                        envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(
+                            port as u32
+                        )
                     ),
-                    route_config_name: app_config.envoy_generation.naming.route_config_name.clone(),
                     ..Default::default()
                 }
-            )
-        ),
-        http_filters: envoy_http_filters,
+            )),
+        }),
+        filter_chains: vec![FilterChain {
+            filters: vec![Filter {
+                name: app_config.envoy_generation.http_filters.hcm_filter_name.clone(),
+                config_type: Some(
+                    envoy_types::pb::envoy::config::listener::v3::filter::ConfigType::TypedConfig(
+                        envoy_types::pb::google::protobuf::Any {
+                            type_url: "type.googleapis.com/envoy.extensions.filters.network.http_connection_manager.v3.HttpConnectionManager".to_string(),
+                            value: hcm_buf,
+                        }
+                    )
+                ),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        per_connection_buffer_limit_bytes: listener_config
+            .per_connection_buffer_limit_bytes
+            .map(|value| UInt32Value { value }),
+        drain_type: drain_type_to_proto(&listener_config.drain_type),
+        connection_balance_config: listener_config.exact_connection_balance.then(|| ConnectionBalanceConfig {
+            balance_type: Some(connection_balance_config::BalanceType::ExactBalance(
+                connection_balance_config::ExactBalance {},
+            )),
+        }),
         ..Default::default()
+    }
+}
+
+/// Build a UDP listener terminating QUIC with the HTTP/3 codec
+fn build_quic_listener(
+    app_config: &crate::config::AppConfig,
+    http_filters: Vec<HttpFilter>,
+    local_reply_mappers: &[ResponseMapper],
+) -> Result<Listener, ConversionError> {
+    let listener_config = &app_config.envoy_generation.listener;
+    let listener_name = format!("{}_quic", app_config.envoy_generation.bootstrap.main_listener_name);
+
+    let tls = listener_config.downstream_tls.as_ref().ok_or_else(|| ConversionError::InvalidResource {
+        resource_type: "Listener".to_string(),
+        resource_id: listener_name.clone(),
+        reason: "HTTP/3 listeners require downstream TLS".to_string(),
+    })?;
+
+    let http_conn_manager = HttpConnectionManager {
+        codec_type: CodecType::Http3 as i32,
+        http3_protocol_options: Some(Http3ProtocolOptions::default()),
+        ..build_http_connection_manager(app_config, http_filters, local_reply_mappers)?
     };
 
-    // Encode HTTP Connection Manager
     let mut hcm_buf = Vec::new();
     http_conn_manager.encode(&mut hcm_buf).map_err(|e| ConversionError::ProtobufEncoding {
         resource_type: "HttpConnectionManager".to_string(),
         source: e,
     })?;
 
-    // Create main listener
-    let listener = Listener {
-        name: app_config.envoy_generation.bootstrap.main_listener_name.clone(),
+    let quic_transport = QuicDownstreamTransport {
+        downstream_tls_context: Some(DownstreamTlsContext {
+            common_tls_context: Some(CommonTlsContext {
+                tls_certificates: vec![TlsCertificate {
+                    certificate_chain: Some(DataSource {
+                        specifier: Some(data_source::Specifier::Filename(tls.cert_path.clone())),
+                        ..Default::default()
+                    }),
+                    private_key: Some(DataSource {
+                        specifier: Some(data_source::Specifier::Filename(tls.key_path.clone())),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        enable_early_data: listener_config
+            .allow_early_data
+            .map(|value| envoy_types::pb::google::protobuf::BoolValue { value }),
+        ..Default::default()
+    };
+
+    let mut transport_buf = Vec::new();
+    quic_transport.encode(&mut transport_buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "QuicDownstreamTransport".to_string(),
+        source: e,
+    })?;
+
+    Ok(Listener {
+        name: listener_name,
         address: Some(Address {
             address: Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(
                 SocketAddress {
-                    protocol: protocol_to_proto(&app_config.envoy_generation.cluster.default_protocol),
-                    address: app_config.envoy_generation.listener.binding_address.clone(),
+                    protocol: envoy_types::pb::envoy::config::core::v3::socket_address::Protocol::Udp as i32,
+                    address: listener_config.binding_address.clone(),
                     port_specifier: Some(
                         envoy_types::pb::envoy::config::core::v3::socket_address::PortSpecifier::PortValue(
-                            app_config.envoy_generation.listener.default_port as u32
+                            listener_config.default_port as u32
                         )
                     ),
                     ..Default::default()
                 }
             )),
         }),
+        udp_listener_config: Some(UdpListenerConfig {
+            quic_options: Some(QuicProtocolOptions::default()),
+            ..Default::default()
+        }),
         filter_chains: vec![FilterChain {
             filters: vec![Filter {
                 name: app_config.envoy_generation.http_filters.hcm_filter_name.clone(),
@@ -96,27 +311,177 @@ pub fn listeners_to_proto(store: &crate::storage::ConfigStore) -> Result<Vec<Any
                         }
                     )
                 ),
-                ..Default::default()
             }],
+            transport_socket: Some(TransportSocket {
+                name: "envoy.transport_sockets.quic".to_string(),
+                config_type: Some(envoy_types::pb::envoy::config::core::v3::transport_socket::ConfigType::TypedConfig(
+                    envoy_types::pb::google::protobuf::Any {
+                        type_url: "type.googleapis.com/envoy.extensions.transport_sockets.quic.v3.QuicDownstreamTransport".to_string(),
+                        value: transport_buf,
+                    }
+                )),
+            }),
             ..Default::default()
         }],
         ..Default::default()
+    })
+}
+
+/// Build the HTTP connection manager for the main listener
+fn build_http_connection_manager(
+    app_config: &crate::config::AppConfig,
+    http_filters: Vec<HttpFilter>,
+    local_reply_mappers: &[ResponseMapper],
+) -> Result<HttpConnectionManager, ConversionError> {
+    let listener_config = &app_config.envoy_generation.listener;
+    let forwarded_headers = &listener_config.forwarded_headers;
+
+    let access_log = listener_config.access_log.as_ref().map(build_access_log).transpose()?.into_iter().collect();
+
+    Ok(HttpConnectionManager {
+        stat_prefix: app_config.envoy_generation.http_filters.stat_prefix.clone(),
+        route_specifier: Some(rds_route_specifier(app_config.envoy_generation.naming.route_config_name.clone())),
+        http_filters,
+        // X-Forwarded-For trust settings for backends that rely on client addresses
+        use_remote_address: Some(envoy_types::pb::google::protobuf::BoolValue {
+            value: forwarded_headers.use_remote_address,
+        }),
+        xff_num_trusted_hops: forwarded_headers.xff_num_trusted_hops,
+        // Hide or rename the `server` header to avoid fingerprinting the proxy
+        server_header_transformation: server_header_transformation_to_proto(&listener_config.server_header_transformation),
+        server_name: listener_config.server_name.clone().unwrap_or_default(),
+        drain_timeout: listener_config.drain_timeout_seconds.map(|seconds| {
+            envoy_types::pb::google::protobuf::Duration {
+                seconds: seconds as i64,
+                nanos: 0,
+            }
+        }),
+        access_log,
+        local_reply_config: local_reply_config(listener_config.local_reply.as_ref(), local_reply_mappers),
+        ..Default::default()
+    })
+}
+
+/// Local reply config combining the global JSON body format with the per-filter mappers;
+/// `None` keeps Envoy's default local replies
+fn local_reply_config(
+    format: Option<&crate::config::LocalReplyFormatConfig>,
+    mappers: &[ResponseMapper],
+) -> Option<LocalReplyConfig> {
+    if format.is_none() && mappers.is_empty() {
+        return None;
+    }
+
+    Some(LocalReplyConfig {
+        mappers: mappers.to_vec(),
+        // Mappers that set their own body_format_override still win over this format
+        body_format: format.map(|format| SubstitutionFormatString {
+            format: Some(substitution_format_string::Format::JsonFormat(Struct {
+                fields: format
+                    .json_format
+                    .iter()
+                    .map(|(key, value)| (key.clone(), json_to_proto_value(value)))
+                    .collect(),
+            })),
+            ..Default::default()
+        }),
+    })
+}
+
+/// Local reply mappers for enabled request_validation filters with a `denied_response`
+///
+/// Envoy applies the first matching mapper, so filters are taken in name order to keep the
+/// generated config stable when more than one sets a denied response.
+fn request_validation_reply_mappers(http_filters: &[InternalHttpFilter]) -> Result<Vec<ResponseMapper>, ConversionError> {
+    let mut filters: Vec<&InternalHttpFilter> = http_filters
+        .iter()
+        .filter(|f| f.enabled && f.filter_type == "request_validation")
+        .collect();
+    filters.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut mappers = Vec::new();
+    for filter in filters {
+        mappers.extend(denied_response_mapper(filter)?);
+    }
+    Ok(mappers)
+}
+
+/// Fetch the named RouteConfiguration over ADS
+fn rds_route_specifier(route_config_name: String) -> RouteSpecifier {
+    RouteSpecifier::Rds(Rds {
+        config_source: Some(envoy_types::pb::envoy::config::core::v3::ConfigSource {
+            config_source_specifier: Some(
+                envoy_types::pb::envoy::config::core::v3::config_source::ConfigSourceSpecifier::Ads(
+                    envoy_types::pb::envoy::config::core::v3::AggregatedConfigSource {
+                        ..Default::default()
+                    },
+                ),
+            ),
+            resource_api_version: envoy_types::pb::envoy::config::core::v3::ApiVersion::V3 as i32,
+            ..Default::default()
+        }),
+        route_config_name,
+        ..Default::default()
+    })
+}
+
+/// File access log with the configured status-code and sampling filters (ANDed when both are set)
+fn build_access_log(access_log: &crate::config::AccessLogConfig) -> Result<AccessLog, ConversionError> {
+    let mut filters = Vec::new();
+
+    if let Some(min_status_code) = access_log.min_status_code {
+        filters.push(AccessLogFilter {
+            filter_specifier: Some(FilterSpecifier::StatusCodeFilter(StatusCodeFilter {
+                comparison: Some(ComparisonFilter {
+                    op: comparison_filter::Op::Ge as i32,
+                    value: Some(RuntimeUInt32 {
+                        default_value: min_status_code,
+                        runtime_key: "access_log.min_status_code".to_string(),
+                    }),
+                }),
+            })),
+        });
+    }
+
+    if let Some(sample_fraction) = access_log.sample_fraction {
+        filters.push(AccessLogFilter {
+            filter_specifier: Some(FilterSpecifier::RuntimeFilter(RuntimeFilter {
+                runtime_key: "access_log.sample_fraction".to_string(),
+                percent_sampled: Some(FractionalPercent {
+                    numerator: (sample_fraction * 1_000_000.0).round() as u32,
+                    denominator: DenominatorType::Million as i32,
+                }),
+                use_independent_randomness: true,
+            })),
+        });
+    }
+
+    let filter = match filters.len() {
+        0 => None,
+        1 => filters.pop(),
+        _ => Some(AccessLogFilter {
+            filter_specifier: Some(FilterSpecifier::AndFilter(AndFilter { filters })),
+        }),
     };
 
-    // Encode listener
-    let mut listener_buf = Vec::new();
-    listener.encode(&mut listener_buf).map_err(|e| ConversionError::ProtobufEncoding {
-        resource_type: "Listener".to_string(),
+    let file_access_log = FileAccessLog {
+        path: access_log.path.clone(),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    file_access_log.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "FileAccessLog".to_string(),
         source: e,
     })?;
 
-    info!("✅ Listener conversion complete with {} HTTP filters integrated", 
-          http_conn_manager.http_filters.len());
-
-    Ok(vec![Any {
-        type_url: "type.googleapis.com/envoy.config.listener.v3.Listener".to_string(),
-        value: listener_buf,
-    }])
+    Ok(AccessLog {
+        name: "envoy.access_loggers.file".to_string(),
+        filter,
+        config_type: Some(access_log::ConfigType::TypedConfig(envoy_types::pb::google::protobuf::Any {
+            type_url: "type.googleapis.com/envoy.extensions.access_loggers.file.v3.FileAccessLog".to_string(),
+            value: buf,
+        })),
+    })
 }
 
 /// Convert internal HTTP filters to Envoy protobuf HTTP filters using FilterStrategyRegistry
@@ -128,13 +493,19 @@ pub fn convert_http_filters(
 ) -> Result<Vec<HttpFilter>, ConversionError> {
     let mut envoy_filters = Vec::new();
 
+    // First in the chain so it compresses the final response body on the way out
+    if app_config.control_plane.http_filters.enable_compression {
+        envoy_filters.push(build_gzip_compressor_filter()?);
+    }
+
     // Create strategy registry with app config for configuration-dependent strategies
     let registry = FilterStrategyRegistry::new(app_config);
     info!("🔧 Using FilterStrategyRegistry with {} supported filter types", 
           registry.supported_filter_types().len());
 
-    // Apply filters in the configured order
-    for filter_type in default_order {
+    // Apply filters in the configured order, adjusted for strategy ordering hints
+    let filter_order = registry.ordered_filter_types(default_order)?;
+    for filter_type in &filter_order {
         // Find filters of this type
         let filters_of_type: Vec<&InternalHttpFilter> = http_filters
             .iter()
@@ -153,7 +524,8 @@ pub fn convert_http_filters(
                     envoy_filters.push(HttpFilter {
                         name: filter_name,
                         config_type: Some(config_type),
-                        disabled: false,
+                        // Route-scoped filters are enabled per route via typed_per_filter_config
+                        disabled: filter.is_route_scoped(),
                         is_optional: false,
                     });
                     
@@ -167,7 +539,10 @@ pub fn convert_http_filters(
     }
 
     // Always add router filter last
-    let router_config = Router::default();
+    let router_config = Router {
+        suppress_envoy_headers: app_config.envoy_generation.listener.suppress_envoy_headers,
+        ..Default::default()
+    };
     let mut buf = Vec::new();
     router_config.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
         resource_type: "Router".to_string(),
@@ -192,6 +567,77 @@ pub fn convert_http_filters(
     Ok(envoy_filters)
 }
 
+/// Response size below which compression is skipped; tiny bodies don't benefit from gzip
+const MIN_COMPRESSED_CONTENT_LENGTH: u32 = 1024;
+
+/// Gzip compressor inserted by `http_filters.enable_compression`, using Envoy's default
+/// compression level and content types
+fn build_gzip_compressor_filter() -> Result<HttpFilter, ConversionError> {
+    let mut gzip = Vec::new();
+    Gzip::default().encode(&mut gzip).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "Gzip".to_string(),
+        source: e,
+    })?;
+
+    let compressor = Compressor {
+        compressor_library: Some(TypedExtensionConfig {
+            name: "envoy.compression.gzip.compressor".to_string(),
+            typed_config: Some(envoy_types::pb::google::protobuf::Any {
+                type_url: "type.googleapis.com/envoy.extensions.compression.gzip.compressor.v3.Gzip".to_string(),
+                value: gzip,
+            }),
+        }),
+        response_direction_config: Some(ResponseDirectionConfig {
+            common_config: Some(CommonDirectionConfig {
+                min_content_length: Some(UInt32Value {
+                    value: MIN_COMPRESSED_CONTENT_LENGTH,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut buf = Vec::new();
+    compressor.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "Compressor".to_string(),
+        source: e,
+    })?;
+
+    Ok(HttpFilter {
+        name: "envoy.filters.http.compressor".to_string(),
+        config_type: Some(
+            envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType::TypedConfig(
+                envoy_types::pb::google::protobuf::Any {
+                    type_url: "type.googleapis.com/envoy.extensions.filters.http.compressor.v3.Compressor".to_string(),
+                    value: buf,
+                }
+            )
+        ),
+        disabled: false,
+        is_optional: false,
+    })
+}
+
+/// Convert drain type string to Envoy protobuf enum
+fn drain_type_to_proto(drain_type: &str) -> i32 {
+    use envoy_types::pb::envoy::config::listener::v3::listener::DrainType;
+    match drain_type {
+        "MODIFY_ONLY" => DrainType::ModifyOnly as i32,
+        _ => DrainType::Default as i32,
+    }
+}
+
+/// Convert server header transformation string to Envoy protobuf enum
+fn server_header_transformation_to_proto(transformation: &str) -> i32 {
+    match transformation {
+        "APPEND_IF_ABSENT" => ServerHeaderTransformation::AppendIfAbsent as i32,
+        "PASS_THROUGH" => ServerHeaderTransformation::PassThrough as i32,
+        _ => ServerHeaderTransformation::Overwrite as i32,
+    }
+}
+
 /// Convert protocol string to Envoy protobuf enum
 fn protocol_to_proto(protocol: &str) -> i32 {
     use envoy_types::pb::envoy::config::core::v3::socket_address::Protocol;
@@ -203,4 +649,309 @@ fn protocol_to_proto(protocol: &str) -> i32 {
             Protocol::Tcp as i32
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hcm_forwarded_header_settings() {
+        use envoy_types::pb::envoy::config::route::v3::{route, RouteConfiguration};
+
+        for append_x_forwarded_host in [true, false] {
+            let mut app_config = crate::config::AppConfig::create_test_config();
+            app_config.envoy_generation.listener.forwarded_headers = crate::config::ForwardedHeadersConfig {
+                use_remote_address: true,
+                xff_num_trusted_hops: 2,
+                append_x_forwarded_host,
+            };
+
+            let hcm = build_http_connection_manager(&app_config, vec![], &[]).unwrap();
+            let mut buf = Vec::new();
+            hcm.encode(&mut buf).unwrap();
+            let decoded = HttpConnectionManager::decode(&buf[..]).unwrap();
+
+            assert_eq!(decoded.use_remote_address.map(|v| v.value), Some(true));
+            assert_eq!(decoded.xff_num_trusted_hops, 2);
+
+            // Envoy carries append_x_forwarded_host on the route action, not the HCM
+            let route = crate::storage::Route {
+                name: "forwarded".to_string(),
+                path: "/api".to_string(),
+                cluster_name: "api-service".to_string(),
+                ..Default::default()
+            };
+            let route_configs =
+                crate::xds::conversion::routes::routes_to_proto_with_config(vec![route], &[], &[], &app_config)
+                    .unwrap();
+            let route_config = RouteConfiguration::decode(&route_configs[0].value[..]).unwrap();
+            match &route_config.virtual_hosts[0].routes[0].action {
+                Some(route::Action::Route(action)) => {
+                    assert_eq!(action.append_x_forwarded_host, append_x_forwarded_host);
+                }
+                other => panic!("expected a route action, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_http_filters_follow_configured_order() {
+        let app_config = crate::config::AppConfig::create_test_config();
+        let rate_limit = InternalHttpFilter::new(
+            "user-rate-limit".to_string(),
+            "rate_limit".to_string(),
+            serde_json::json!({
+                "requests_per_unit": 100,
+                "time_unit": "minute"
+            }),
+        );
+        let auth = InternalHttpFilter::new(
+            "user-auth".to_string(),
+            "authentication".to_string(),
+            serde_json::json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com"
+            }),
+        );
+
+        // Filter types are emitted in the configured order, whatever order the filters are stored in
+        let default_order = vec!["authentication".to_string(), "rate_limit".to_string()];
+        let filters = convert_http_filters(vec![rate_limit, auth], &default_order, &app_config).unwrap();
+
+        let names: Vec<&str> = filters.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "envoy.filters.http.jwt_authn",
+                "envoy.filters.http.local_ratelimit",
+                "envoy.filters.http.router"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quic_listener_uses_udp_and_http3_codec() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.http3 = true;
+        app_config.envoy_generation.listener.downstream_tls = Some(crate::config::DownstreamTlsConfig {
+            cert_path: "/etc/envoy/certs/listener.crt".to_string(),
+            key_path: "/etc/envoy/certs/listener.key".to_string(),
+        });
+
+        let listener = build_quic_listener(&app_config, vec![], &[]).unwrap();
+        let mut buf = Vec::new();
+        listener.encode(&mut buf).unwrap();
+        let decoded = Listener::decode(&buf[..]).unwrap();
+
+        match decoded.address.unwrap().address {
+            Some(envoy_types::pb::envoy::config::core::v3::address::Address::SocketAddress(socket)) => {
+                assert_eq!(
+                    socket.protocol,
+                    envoy_types::pb::envoy::config::core::v3::socket_address::Protocol::Udp as i32
+                );
+            }
+            _ => panic!("Expected socket address"),
+        }
+        assert!(decoded.udp_listener_config.unwrap().quic_options.is_some());
+
+        let filter_chain = &decoded.filter_chains[0];
+        assert_eq!(filter_chain.transport_socket.as_ref().unwrap().name, "envoy.transport_sockets.quic");
+
+        let hcm_any = match filter_chain.filters[0].config_type.clone() {
+            Some(envoy_types::pb::envoy::config::listener::v3::filter::ConfigType::TypedConfig(any)) => any,
+            _ => panic!("Expected typed HCM config"),
+        };
+        let hcm = HttpConnectionManager::decode(&hcm_any.value[..]).unwrap();
+        assert_eq!(hcm.codec_type, CodecType::Http3 as i32);
+        assert!(hcm.http3_protocol_options.is_some());
+    }
+
+    #[test]
+    fn test_quic_listener_applies_allow_early_data() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.http3 = true;
+        app_config.envoy_generation.listener.allow_early_data = Some(false);
+        app_config.envoy_generation.listener.downstream_tls = Some(crate::config::DownstreamTlsConfig {
+            cert_path: "/etc/envoy/certs/listener.crt".to_string(),
+            key_path: "/etc/envoy/certs/listener.key".to_string(),
+        });
+
+        let listener = build_quic_listener(&app_config, vec![], &[]).unwrap();
+        let transport_socket = listener.filter_chains[0].transport_socket.clone().unwrap();
+        let transport_any = match transport_socket.config_type {
+            Some(envoy_types::pb::envoy::config::core::v3::transport_socket::ConfigType::TypedConfig(any)) => any,
+            _ => panic!("Expected typed QUIC transport config"),
+        };
+        let quic_transport = QuicDownstreamTransport::decode(&transport_any.value[..]).unwrap();
+        assert_eq!(quic_transport.enable_early_data.map(|v| v.value), Some(false));
+    }
+
+    #[test]
+    fn test_quic_listener_requires_downstream_tls() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.http3 = true;
+
+        assert!(build_quic_listener(&app_config, vec![], &[]).is_err());
+    }
+
+    #[test]
+    fn test_main_listener_buffer_limit_and_exact_balance() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.per_connection_buffer_limit_bytes = Some(65536);
+        app_config.envoy_generation.listener.exact_connection_balance = true;
+
+        let listener = build_main_listener(&app_config, vec![]);
+        let mut buf = Vec::new();
+        listener.encode(&mut buf).unwrap();
+        let decoded = Listener::decode(&buf[..]).unwrap();
+
+        assert_eq!(decoded.per_connection_buffer_limit_bytes, Some(UInt32Value { value: 65536 }));
+        assert!(matches!(
+            decoded.connection_balance_config.and_then(|c| c.balance_type),
+            Some(connection_balance_config::BalanceType::ExactBalance(_))
+        ));
+    }
+
+    #[test]
+    fn test_drain_timeout_and_type_applied() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.drain_type = "MODIFY_ONLY".to_string();
+        app_config.envoy_generation.listener.drain_timeout_seconds = Some(45);
+
+        let hcm = build_http_connection_manager(&app_config, vec![], &[]).unwrap();
+        let mut buf = Vec::new();
+        hcm.encode(&mut buf).unwrap();
+        let decoded = HttpConnectionManager::decode(&buf[..]).unwrap();
+        assert_eq!(decoded.drain_timeout.map(|d| d.seconds), Some(45));
+
+        let listener = build_main_listener(&app_config, buf);
+        assert_eq!(
+            listener.drain_type,
+            envoy_types::pb::envoy::config::listener::v3::listener::DrainType::ModifyOnly as i32
+        );
+    }
+
+    #[test]
+    fn test_envoy_headers_suppressed_and_server_header_overwritten() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.suppress_envoy_headers = true;
+        app_config.envoy_generation.listener.server_name = Some("edge".to_string());
+
+        let filters = convert_http_filters(vec![], &[], &app_config).unwrap();
+        let router = filters.iter().find(|f| f.name == "envoy.filters.http.router").unwrap();
+        let Some(envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType::TypedConfig(any)) = &router.config_type else {
+            panic!("Expected typed router config");
+        };
+        assert!(Router::decode(&any.value[..]).unwrap().suppress_envoy_headers);
+
+        let hcm = build_http_connection_manager(&app_config, filters, &[]).unwrap();
+        assert_eq!(hcm.server_header_transformation, ServerHeaderTransformation::Overwrite as i32);
+        assert_eq!(hcm.server_name, "edge");
+    }
+
+    #[test]
+    fn test_enable_compression_inserts_gzip_compressor() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        let filters = convert_http_filters(vec![], &[], &app_config).unwrap();
+        assert!(!filters.iter().any(|f| f.name == "envoy.filters.http.compressor"));
+
+        app_config.control_plane.http_filters.enable_compression = true;
+        let filters = convert_http_filters(vec![], &[], &app_config).unwrap();
+        assert_eq!(filters.first().map(|f| f.name.as_str()), Some("envoy.filters.http.compressor"));
+        assert_eq!(filters.last().map(|f| f.name.as_str()), Some("envoy.filters.http.router"));
+
+        let Some(envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType::TypedConfig(any)) = &filters[0].config_type else {
+            panic!("Expected typed compressor config");
+        };
+        let compressor = Compressor::decode(&any.value[..]).unwrap();
+        assert_eq!(
+            compressor.compressor_library.unwrap().name,
+            "envoy.compression.gzip.compressor"
+        );
+    }
+
+    #[test]
+    fn test_access_log_status_code_filter() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.access_log = Some(crate::config::AccessLogConfig {
+            path: "/var/log/envoy/access.log".to_string(),
+            min_status_code: Some(400),
+            sample_fraction: None,
+        });
+
+        let hcm = build_http_connection_manager(&app_config, vec![], &[]).unwrap();
+        assert_eq!(hcm.access_log.len(), 1);
+        let log = &hcm.access_log[0];
+        assert_eq!(log.name, "envoy.access_loggers.file");
+
+        let Some(FilterSpecifier::StatusCodeFilter(status_filter)) =
+            log.filter.as_ref().and_then(|f| f.filter_specifier.as_ref())
+        else {
+            panic!("Expected a status code filter");
+        };
+        let comparison = status_filter.comparison.as_ref().unwrap();
+        assert_eq!(comparison.op, comparison_filter::Op::Ge as i32);
+        assert_eq!(comparison.value.as_ref().unwrap().default_value, 400);
+
+        let Some(access_log::ConfigType::TypedConfig(any)) = &log.config_type else {
+            panic!("Expected typed access log config");
+        };
+        assert_eq!(FileAccessLog::decode(&any.value[..]).unwrap().path, "/var/log/envoy/access.log");
+    }
+
+    #[test]
+    fn test_request_validation_denied_response_emits_local_reply_config() {
+        let app_config = crate::config::AppConfig::create_test_config();
+        let filter = InternalHttpFilter::new(
+            "api-validation".to_string(),
+            "request_validation".to_string(),
+            serde_json::json!({
+                "allowed_methods": ["GET"],
+                "denied_response": {"status_code": 422, "body": {"error": "invalid request"}}
+            }),
+        );
+
+        let mappers = request_validation_reply_mappers(&[filter]).unwrap();
+        let hcm = build_http_connection_manager(&app_config, vec![], &mappers).unwrap();
+        let mapper = &hcm.local_reply_config.unwrap().mappers[0];
+
+        assert_eq!(mapper.status_code, Some(UInt32Value { value: 422 }));
+        assert!(matches!(
+            &mapper.filter.as_ref().unwrap().filter_specifier,
+            Some(FilterSpecifier::ResponseFlagFilter(flags)) if flags.flags == vec!["RBAC".to_string()]
+        ));
+        assert_eq!(
+            mapper.body.as_ref().unwrap().specifier,
+            Some(data_source::Specifier::InlineString(r#"{"error":"invalid request"}"#.to_string()))
+        );
+        assert_eq!(mapper.body_format_override.as_ref().unwrap().content_type, "application/json");
+
+        // No denied responses configured: Envoy keeps its default local replies
+        let hcm = build_http_connection_manager(&app_config, vec![], &[]).unwrap();
+        assert!(hcm.local_reply_config.is_none());
+    }
+
+    #[test]
+    fn test_local_reply_format_is_set_on_hcm() {
+        let mut app_config = crate::config::AppConfig::create_test_config();
+        app_config.envoy_generation.listener.local_reply = Some(crate::config::LocalReplyFormatConfig {
+            json_format: serde_json::json!({"status": "%RESPONSE_CODE%", "message": "%LOCAL_REPLY_BODY%"})
+                .as_object()
+                .unwrap()
+                .clone(),
+        });
+
+        let hcm = build_http_connection_manager(&app_config, vec![], &[]).unwrap();
+        let local_reply = hcm.local_reply_config.unwrap();
+        assert!(local_reply.mappers.is_empty());
+
+        let Some(substitution_format_string::Format::JsonFormat(json_format)) =
+            local_reply.body_format.unwrap().format
+        else {
+            panic!("Expected a JSON body format");
+        };
+        assert_eq!(json_format.fields["status"], json_to_proto_value(&serde_json::json!("%RESPONSE_CODE%")));
+        assert_eq!(json_format.fields["message"], json_to_proto_value(&serde_json::json!("%LOCAL_REPLY_BODY%")));
+    }
+}
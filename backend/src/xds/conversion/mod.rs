@@ -7,6 +7,7 @@
 //! - `routes`: Route conversion logic  
 //! - `listeners`: Listener and HTTP filter conversion logic (integrates FilterStrategyRegistry)
 //! - `utils`: Shared utilities and validation functions
+//! - `self_test`: Startup pass that checks every stored resource converts cleanly
 //! - `errors`: Error types for conversion operations
 //!
 //! ## Key Features
@@ -20,16 +21,41 @@ pub mod routes;
 pub mod listeners;
 pub mod utils;
 pub mod errors;
+pub mod self_test;
 
 // Re-export for backward compatibility and easy access
 pub use errors::ConversionError;
 pub use clusters::clusters_to_proto;
-pub use routes::routes_to_proto;
+pub use routes::{
+    explain_route_match, find_shadowed_routes, routes_to_proto, routes_to_proto_with_filters, RouteMatchExplanation,
+    ShadowedRoute,
+};
 pub use listeners::{listeners_to_proto, convert_http_filters};
+pub use self_test::{conversion_status, run_conversion_self_test, ResourceStatus};
 
+use crate::config::node_matches;
 use crate::storage::ConfigStore;
+use envoy_types::pb::envoy::config::cluster::v3::Cluster;
+use envoy_types::pb::envoy::config::listener::v3::Listener;
+use envoy_types::pb::envoy::config::route::v3::RouteConfiguration;
+use prost::Message;
 use prost_types::Any;
-use tracing::info;
+use tracing::{info, warn};
+
+/// Cluster label that limits which Envoy nodes are served the cluster, compared per `server.node_match_mode`
+pub const NODE_ID_LABEL: &str = "node_id";
+
+/// Resource type URLs this control plane can serve
+pub const SUPPORTED_TYPE_URLS: [&str; 3] = [
+    "type.googleapis.com/envoy.config.cluster.v3.Cluster",
+    "type.googleapis.com/envoy.config.route.v3.RouteConfiguration",
+    "type.googleapis.com/envoy.config.listener.v3.Listener",
+];
+
+/// Whether `get_resources_by_type` has a conversion for this type URL
+pub fn is_supported_type_url(type_url: &str) -> bool {
+    SUPPORTED_TYPE_URLS.contains(&type_url)
+}
 
 /// Main conversion entry point for XDS resources
 /// 
@@ -46,7 +72,12 @@ pub fn get_resources_by_type(type_url: &str, store: &ConfigStore) -> Result<Vec<
 
         "type.googleapis.com/envoy.config.route.v3.RouteConfiguration" => {
             let route_list = store.list_routes();
-            routes_to_proto(route_list.iter().map(|r| (**r).clone()).collect())
+            let http_filters = store.list_http_filters();
+            routes_to_proto_with_filters(
+                route_list.iter().map(|r| (**r).clone()).collect(),
+                &store.list_route_filters(),
+                &http_filters.iter().map(|f| (**f).clone()).collect::<Vec<_>>(),
+            )
         }
 
         "type.googleapis.com/envoy.config.listener.v3.Listener" => {
@@ -61,6 +92,70 @@ pub fn get_resources_by_type(type_url: &str, store: &ConfigStore) -> Result<Vec<
     }
 }
 
+/// Keep only the resources a client subscribed to by name; an empty subscription means all
+///
+/// Types without a name lookup are returned unfiltered.
+pub fn filter_by_resource_names(type_url: &str, resources: Vec<Any>, resource_names: &[String]) -> Vec<Any> {
+    if resource_names.is_empty() {
+        return resources;
+    }
+
+    resources
+        .into_iter()
+        .filter(|resource| match resource_name(type_url, resource) {
+            Some(name) => resource_names.contains(&name),
+            None => true,
+        })
+        .collect()
+}
+
+/// Drop clusters whose `node_id` label doesn't match the requesting node; untagged clusters go to every node
+///
+/// A tag that can't be compared (a regex that doesn't compile) withholds the cluster.
+pub fn filter_by_node(
+    type_url: &str,
+    resources: Vec<Any>,
+    store: &ConfigStore,
+    node_match_mode: &str,
+    node_id: &str,
+) -> Vec<Any> {
+    if type_url != "type.googleapis.com/envoy.config.cluster.v3.Cluster" {
+        return resources;
+    }
+
+    resources
+        .into_iter()
+        .filter(|resource| {
+            let tag = resource_name(type_url, resource)
+                .and_then(|name| store.get_cluster(&name).ok())
+                .and_then(|cluster| cluster.labels.as_ref()?.get(NODE_ID_LABEL).cloned());
+            match tag {
+                Some(tag) => node_matches(node_match_mode, &tag, node_id).unwrap_or_else(|e| {
+                    warn!("Withholding cluster tagged with invalid node_id pattern '{}': {}", tag, e);
+                    false
+                }),
+                None => true,
+            }
+        })
+        .collect()
+}
+
+/// Name of an encoded resource, for the types that support subscribing by name
+fn resource_name(type_url: &str, resource: &Any) -> Option<String> {
+    match type_url {
+        "type.googleapis.com/envoy.config.cluster.v3.Cluster" => {
+            Cluster::decode(&resource.value[..]).ok().map(|cluster| cluster.name)
+        }
+        "type.googleapis.com/envoy.config.route.v3.RouteConfiguration" => {
+            RouteConfiguration::decode(&resource.value[..]).ok().map(|route_config| route_config.name)
+        }
+        "type.googleapis.com/envoy.config.listener.v3.Listener" => {
+            Listener::decode(&resource.value[..]).ok().map(|listener| listener.name)
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,5 +177,30 @@ mod tests {
         // Test unsupported type
         let unsupported_result = get_resources_by_type("type.googleapis.com/unsupported.Type", &store);
         assert!(unsupported_result.is_ok()); // Returns empty vec, doesn't error
+        assert!(!is_supported_type_url("type.googleapis.com/unsupported.Type"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_filter_by_resource_names_selects_route_configuration() {
+        let store = ConfigStore::new();
+        store.add_route(crate::storage::Route {
+            name: "orders".to_string(),
+            path: "/orders".to_string(),
+            cluster_name: "orders".to_string(),
+            ..Default::default()
+        });
+        let route_type = "type.googleapis.com/envoy.config.route.v3.RouteConfiguration";
+        let resources = get_resources_by_type(route_type, &store).unwrap();
+        let route_config_name = resource_name(route_type, &resources[0]).unwrap();
+
+        let selected = filter_by_resource_names(route_type, resources.clone(), &[route_config_name]);
+        assert_eq!(selected.len(), 1);
+
+        let unknown =
+            filter_by_resource_names(route_type, resources.clone(), &["other_route".to_string()]);
+        assert!(unknown.is_empty());
+
+        // No names subscribes to every resource
+        assert_eq!(filter_by_resource_names(route_type, resources, &[]).len(), 1);
+    }
+}
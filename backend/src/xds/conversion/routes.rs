@@ -1,41 +1,84 @@
 use super::errors::ConversionError;
-use super::utils::{load_config_with_fallback, validate_route};
-use crate::storage::models::Route as InternalRoute;
+use super::utils::{get_envoy_filter_name, load_config_with_fallback, validate_route};
+use crate::config::AppConfig;
+use crate::storage::models::{HttpFilter as InternalHttpFilter, Route as InternalRoute, RouteFilters};
+use crate::xds::filters::cors::cors_policy;
+use crate::xds::filters::request_validation::SimulatedRequest;
 use prost::Message;
 use prost_types::Any;
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Body served by routes in maintenance mode when no custom body is configured
+const DEFAULT_MAINTENANCE_BODY: &str = "Service temporarily unavailable for maintenance";
 
 // Import Envoy protobuf types for routes
+use envoy_types::pb::envoy::config::core::v3::{
+    data_source, DataSource, HeaderValue, HeaderValueOption, RoutingPriority,
+};
 use envoy_types::pb::envoy::config::route::v3::{
-    HeaderMatcher, Route, RouteAction, RouteConfiguration, RouteMatch, VirtualHost,
+    retry_policy, route_action, weighted_cluster, DirectResponseAction, FilterConfig, HeaderMatcher, RetryPolicy,
+    Route, RouteAction, RouteConfiguration, RouteMatch, VirtualHost, WeightedCluster,
 };
-use envoy_types::pb::envoy::r#type::matcher::v3::{RegexMatcher, StringMatcher};
+use envoy_types::pb::envoy::r#type::matcher::v3::{RegexMatchAndSubstitute, RegexMatcher, StringMatcher};
+use envoy_types::pb::google::protobuf::{BoolValue, Duration, UInt32Value};
 
 /// Convert internal routes to Envoy protobuf format
 pub fn routes_to_proto(routes: Vec<InternalRoute>) -> Result<Vec<Any>, ConversionError> {
+    routes_to_proto_with_filters(routes, &[], &[])
+}
+
+/// Convert internal routes to Envoy protobuf format, enabling disabled-by-default
+/// HTTP filters on the routes that reference them through `RouteFilters`
+pub fn routes_to_proto_with_filters(
+    routes: Vec<InternalRoute>,
+    route_filters: &[RouteFilters],
+    http_filters: &[InternalHttpFilter],
+) -> Result<Vec<Any>, ConversionError> {
     if routes.is_empty() {
         return Ok(vec![]);
     }
 
     // Load config with fallback mechanism
     let app_config = load_config_with_fallback()?;
+    routes_to_proto_with_config(routes, route_filters, http_filters, &app_config)
+}
+
+/// Convert internal routes using an already-loaded application config
+pub fn routes_to_proto_with_config(
+    routes: Vec<InternalRoute>,
+    route_filters: &[RouteFilters],
+    http_filters: &[InternalHttpFilter],
+    app_config: &AppConfig,
+) -> Result<Vec<Any>, ConversionError> {
+    if routes.is_empty() {
+        return Ok(vec![]);
+    }
 
     info!(
         "Routes conversion: Creating RouteConfiguration with {} routes",
         routes.len()
     );
 
-    // Create routes following the Go control plane pattern with validation
-    let mut proto_routes = Vec::new();
-    
+    // Routes grouped by the route configuration of their owning listener, main listener first
+    let mut route_groups: Vec<(String, Vec<Route>)> = Vec::new();
+
     for route in routes {
         // Validate route before conversion
         validate_route(&route)?;
-        
+        let route_config_name = route_config_name_for(app_config, route.listener.as_deref()).ok_or_else(|| {
+            ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.name.clone(),
+                reason: format!("Route references unknown listener '{}'", route.listener.as_deref().unwrap_or_default()),
+            }
+        })?;
+
         info!("  - Route: {} -> {}", route.path, route.cluster_name);
 
         // Create header matchers for HTTP methods if specified
-        let headers = if let Some(ref methods) = route.http_methods {
+        let mut headers = if let Some(ref methods) = route.http_methods {
             if methods.len() == 1 {
                 // Single method - use string match with exact
                 vec![HeaderMatcher {
@@ -73,48 +116,1315 @@ pub fn routes_to_proto(routes: Vec<InternalRoute>) -> Result<Vec<Any>, Conversio
             vec![]
         };
 
+        // Scheme-restricted routes (e.g. https-only) match on the :scheme pseudo-header
+        if let Some(scheme) = route.scheme_match {
+            headers.push(HeaderMatcher {
+                name: ":scheme".to_string(),
+                header_match_specifier: Some(
+                    envoy_types::pb::envoy::config::route::v3::header_matcher::HeaderMatchSpecifier::StringMatch(
+                        StringMatcher {
+                            match_pattern: Some(
+                                envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::Exact(scheme)
+                            ),
+                            ..Default::default()
+                        }
+                    )
+                ),
+                ..Default::default()
+            });
+        }
+
+        // Media types are case-insensitive; accept holds a list, content-type may carry parameters
+        if let Some(content_type) = route.content_type_match {
+            let match_pattern = if content_type.header == "accept" {
+                envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::Contains(content_type.media_type)
+            } else {
+                envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::Prefix(content_type.media_type)
+            };
+            headers.push(HeaderMatcher {
+                name: content_type.header,
+                header_match_specifier: Some(
+                    envoy_types::pb::envoy::config::route::v3::header_matcher::HeaderMatchSpecifier::StringMatch(
+                        StringMatcher {
+                            match_pattern: Some(match_pattern),
+                            ignore_case: true,
+                        }
+                    )
+                ),
+                ..Default::default()
+            });
+        }
+
+        // Regex rewrite takes the place of prefix_rewrite when configured
+        let regex_rewrite = route.regex_rewrite.map(|rewrite| RegexMatchAndSubstitute {
+            pattern: Some(RegexMatcher {
+                regex: rewrite.pattern,
+                ..Default::default()
+            }),
+            substitution: rewrite.substitution,
+        });
+
+        let retry_policy = route.retry_policy.map(|policy| RetryPolicy {
+            retry_on: policy.combined_retry_on(),
+            num_retries: policy.num_retries.map(|value| UInt32Value { value }),
+            retriable_status_codes: policy.retriable_status_codes,
+            retry_back_off: policy.retry_back_off.map(|back_off| retry_policy::RetryBackOff {
+                base_interval: Some(millis_to_duration(back_off.base_interval_ms)),
+                max_interval: Some(millis_to_duration(back_off.max_interval_ms)),
+            }),
+            ..Default::default()
+        });
+
+        // A route's own timeout wins over the global default
+        let timeout = route
+            .timeout_seconds
+            .or(app_config.envoy_generation.listener.default_request_timeout_seconds)
+            .map(|seconds| Duration {
+                seconds: seconds as i64,
+                nanos: 0,
+            });
+
+        // Sorted so the generated config is stable across map iteration order
+        let mut response_headers: Vec<(String, String)> =
+            route.response_headers_to_add.unwrap_or_default().into_iter().collect();
+        response_headers.sort();
+        let response_headers_to_add = response_headers
+            .into_iter()
+            .map(|(key, value)| HeaderValueOption {
+                header: Some(HeaderValue {
+                    key,
+                    value,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .collect();
+
+        let typed_per_filter_config = per_route_filter_overrides(
+            &route.name,
+            &route.path,
+            route.disabled_filters.as_deref().unwrap_or_default(),
+            route_filters,
+            http_filters,
+        )?;
+
+        // Maintenance mode swaps the proxy action for a 503; cluster_name stays on the model for toggle-back
+        let action = if route.maintenance_mode {
+            info!("    - Route '{}' is in maintenance mode, serving 503", route.name);
+            envoy_types::pb::envoy::config::route::v3::route::Action::DirectResponse(DirectResponseAction {
+                status: 503,
+                body: Some(DataSource {
+                    specifier: Some(data_source::Specifier::InlineString(
+                        route.maintenance_body.unwrap_or_else(|| DEFAULT_MAINTENANCE_BODY.to_string()),
+                    )),
+                    ..Default::default()
+                }),
+            })
+        } else {
+            // A traffic split takes precedence over the single cluster_name
+            let cluster_specifier = match route.weighted_clusters {
+                Some(weighted) => route_action::ClusterSpecifier::WeightedClusters(WeightedCluster {
+                    clusters: weighted
+                        .clusters
+                        .into_iter()
+                        .map(|cluster| weighted_cluster::ClusterWeight {
+                            name: cluster.name,
+                            weight: Some(UInt32Value { value: cluster.weight }),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    runtime_key_prefix: weighted.runtime_key_prefix.unwrap_or_default(),
+                    ..Default::default()
+                }),
+                None => route_action::ClusterSpecifier::Cluster(route.cluster_name),
+            };
+            envoy_types::pb::envoy::config::route::v3::route::Action::Route(RouteAction {
+                cluster_specifier: Some(cluster_specifier),
+                prefix_rewrite: route.prefix_rewrite.unwrap_or_default(),
+                regex_rewrite,
+                // Envoy exposes append_x_forwarded_host on the route action rather than the HCM
+                append_x_forwarded_host: app_config.envoy_generation.listener.forwarded_headers.append_x_forwarded_host,
+                idle_timeout: route.idle_timeout_seconds.map(|seconds| envoy_types::pb::google::protobuf::Duration {
+                    seconds: seconds as i64,
+                    nanos: 0,
+                }),
+                retry_policy,
+                timeout,
+                priority: match route.routing_priority.as_deref() {
+                    Some("high") => RoutingPriority::High as i32,
+                    _ => RoutingPriority::Default as i32,
+                },
+                ..Default::default()
+            })
+        };
+
         let proto_route = Route {
+            // Surfaces the stored name in Envoy's config dump and route stats
+            name: route.name,
             r#match: Some(RouteMatch {
                 path_specifier: Some(envoy_types::pb::envoy::config::route::v3::route_match::PathSpecifier::Prefix(route.path)),
                 headers,
+                case_sensitive: route.case_sensitive.map(|value| BoolValue { value }),
                 ..Default::default()
             }),
-            action: Some(envoy_types::pb::envoy::config::route::v3::route::Action::Route(RouteAction {
-                cluster_specifier: Some(envoy_types::pb::envoy::config::route::v3::route_action::ClusterSpecifier::Cluster(route.cluster_name)),
-                prefix_rewrite: route.prefix_rewrite.unwrap_or_default(),
-                ..Default::default()
-            })),
+            action: Some(action),
+            typed_per_filter_config,
+            response_headers_to_add,
+            request_headers_to_remove: route.request_headers_to_remove.unwrap_or_default(),
             ..Default::default()
         };
-        
-        proto_routes.push(proto_route);
+
+        match route_groups.iter_mut().find(|(name, _)| *name == route_config_name) {
+            Some((_, group)) => group.push(proto_route),
+            None => route_groups.push((route_config_name, vec![proto_route])),
+        }
     }
+    route_groups.sort_by_key(|(name, _)| *name != app_config.envoy_generation.naming.route_config_name);
+
+    let cors_config = virtual_host_cors_config(http_filters)?;
+    let mut route_configs = Vec::with_capacity(route_groups.len());
+    for (route_config_name, proto_routes) in route_groups {
+        // Create virtual host with all routes of this listener
+        let virtual_host = VirtualHost {
+            name: app_config.envoy_generation.naming.virtual_host_name.clone(),
+            domains: virtual_host_domains(app_config, &route_config_name),
+            routes: proto_routes,
+            typed_per_filter_config: cors_config.clone(),
+            ..Default::default()
+        };
 
-    // Create virtual host with all routes
-    let virtual_host = VirtualHost {
-        name: app_config.envoy_generation.naming.virtual_host_name.clone(),
-        domains: app_config.envoy_generation.naming.default_domains.clone(),
-        routes: proto_routes,
-        ..Default::default()
+        // Create RouteConfiguration
+        let route_config = RouteConfiguration {
+            name: route_config_name,
+            virtual_hosts: vec![virtual_host],
+            // A header set on the route overrides the same header from the virtual host
+            // or route configuration instead of being overwritten by them
+            most_specific_header_mutations_wins: true,
+            ..Default::default()
+        };
+
+        // Encode to protobuf bytes with proper error handling
+        let mut buf = Vec::new();
+        route_config.encode(&mut buf)
+            .map_err(|e| ConversionError::ProtobufEncoding {
+                resource_type: "RouteConfiguration".to_string(),
+                source: e,
+            })?;
+
+        route_configs.push(Any {
+            type_url: "type.googleapis.com/envoy.config.route.v3.RouteConfiguration".to_string(),
+            value: buf,
+        });
+    }
+
+    Ok(route_configs)
+}
+
+/// Virtual host domains of a route configuration: the owning additional listener's
+/// `domains` when set, `naming.default_domains` otherwise
+fn virtual_host_domains(app_config: &AppConfig, route_config_name: &str) -> Vec<String> {
+    let envoy = &app_config.envoy_generation;
+    envoy
+        .listener
+        .additional_listeners
+        .iter()
+        .find(|additional| {
+            route_config_name_for(app_config, Some(&additional.name)).as_deref() == Some(route_config_name)
+        })
+        .and_then(|additional| additional.domains.clone())
+        .unwrap_or_else(|| envoy.naming.default_domains.clone())
+}
+
+/// Name of the RouteConfiguration served to a listener over RDS
+///
+/// Routes without a listener (or naming the main listener) share `naming.route_config_name`;
+/// each additional listener gets `<route_config_name>_<listener>`. Returns `None` for
+/// listeners that are not configured.
+pub fn route_config_name_for(app_config: &AppConfig, listener: Option<&str>) -> Option<String> {
+    let envoy = &app_config.envoy_generation;
+    match listener {
+        None => Some(envoy.naming.route_config_name.clone()),
+        Some(name) if name == envoy.bootstrap.main_listener_name => Some(envoy.naming.route_config_name.clone()),
+        Some(name) => envoy
+            .listener
+            .additional_listeners
+            .iter()
+            .any(|additional| additional.name == name)
+            .then(|| format!("{}_{}", envoy.naming.route_config_name, name)),
+    }
+}
+
+/// Which route a sample request would hit and the conditions that selected it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RouteMatchExplanation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_route: Option<String>,
+    /// Conditions the matched route checked, or a note that nothing matched
+    pub reasons: Vec<String>,
+}
+
+/// Evaluate `routes` in order the way Envoy does for the generated virtual host: the
+/// first route whose prefix, methods, scheme and media type all match wins
+pub fn explain_route_match(routes: &[InternalRoute], request: &SimulatedRequest) -> RouteMatchExplanation {
+    routes
+        .iter()
+        .find_map(|route| {
+            route_match_reasons(route, request).map(|reasons| RouteMatchExplanation {
+                matched_route: Some(route.name.clone()),
+                reasons,
+            })
+        })
+        .unwrap_or_else(|| RouteMatchExplanation {
+            matched_route: None,
+            reasons: vec![format!("No route matches {} {}", request.method, request.path)],
+        })
+}
+
+/// Reasons `route` matches the request, or `None` if any of its matchers fails
+fn route_match_reasons(route: &InternalRoute, request: &SimulatedRequest) -> Option<Vec<String>> {
+    let header = |name: &str| {
+        request
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
     };
 
-    // Create RouteConfiguration
-    let route_config = RouteConfiguration {
-        name: app_config.envoy_generation.naming.route_config_name.clone(),
-        virtual_hosts: vec![virtual_host],
-        ..Default::default()
+    let prefix_matches = if route.case_sensitive == Some(false) {
+        request.path.to_ascii_lowercase().starts_with(&route.path.to_ascii_lowercase())
+    } else {
+        request.path.starts_with(&route.path)
+    };
+    if !prefix_matches {
+        return None;
+    }
+    let mut reasons = vec![format!("path '{}' starts with prefix '{}'", request.path, route.path)];
+
+    if let Some(methods) = &route.http_methods {
+        if !methods.contains(&request.method) {
+            return None;
+        }
+        reasons.push(format!("method {} is one of [{}]", request.method, methods.join(", ")));
+    }
+
+    if let Some(scheme) = &route.scheme_match {
+        // Envoy always sets :scheme; plain-text listeners default to http
+        if header(":scheme").unwrap_or("http") != scheme {
+            return None;
+        }
+        reasons.push(format!(":scheme is {}", scheme));
+    }
+
+    if let Some(content_type) = &route.content_type_match {
+        let value = header(&content_type.header)?.to_ascii_lowercase();
+        let media_type = content_type.media_type.to_ascii_lowercase();
+        let matched = if content_type.header == "accept" {
+            value.contains(&media_type)
+        } else {
+            value.starts_with(&media_type)
+        };
+        if !matched {
+            return None;
+        }
+        reasons.push(format!("{} header matches {}", content_type.header, content_type.media_type));
+    }
+
+    Some(reasons)
+}
+
+/// A route that can never be selected because an earlier route matches every request it would
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShadowedRoute {
+    pub route: String,
+    pub path: String,
+    pub shadowed_by: String,
+    pub shadowed_by_path: String,
+}
+
+/// Routes in `routes` (in RDS order) whose match is fully subsumed by an earlier route
+pub fn find_shadowed_routes(routes: &[InternalRoute]) -> Vec<ShadowedRoute> {
+    routes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, route)| {
+            routes[..i]
+                .iter()
+                .find(|earlier| subsumes(earlier, route))
+                .map(|earlier| ShadowedRoute {
+                    route: route.name.clone(),
+                    path: route.path.clone(),
+                    shadowed_by: earlier.name.clone(),
+                    shadowed_by_path: earlier.path.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Whether every request `later` matches is also matched by `earlier`
+fn subsumes(earlier: &InternalRoute, later: &InternalRoute) -> bool {
+    let earlier_ignores_case = earlier.case_sensitive == Some(false);
+    let later_ignores_case = later.case_sensitive == Some(false);
+    let prefix_covered = if earlier_ignores_case {
+        later.path.to_ascii_lowercase().starts_with(&earlier.path.to_ascii_lowercase())
+    } else {
+        // A case-insensitive later route also matches casings a case-sensitive prefix with letters misses
+        later.path.starts_with(&earlier.path)
+            && (!later_ignores_case || !earlier.path.chars().any(|c| c.is_ascii_alphabetic()))
+    };
+
+    let methods_covered = match (&earlier.http_methods, &later.http_methods) {
+        (None, _) => true,
+        (Some(earlier_methods), Some(later_methods)) => {
+            later_methods.iter().all(|method| earlier_methods.contains(method))
+        }
+        (Some(_), None) => false,
+    };
+
+    let scheme_covered = earlier.scheme_match.is_none() || earlier.scheme_match == later.scheme_match;
+    let content_type_covered =
+        earlier.content_type_match.is_none() || earlier.content_type_match == later.content_type_match;
+
+    prefix_covered && methods_covered && scheme_covered && content_type_covered
+}
+
+fn millis_to_duration(millis: u64) -> Duration {
+    Duration {
+        seconds: (millis / 1000) as i64,
+        nanos: ((millis % 1000) * 1_000_000) as i32,
+    }
+}
+
+/// Attach the policy of the first enabled, listener-wide cors filter to the virtual host,
+/// which is where Envoy's CORS filter reads it from
+fn virtual_host_cors_config(
+    http_filters: &[InternalHttpFilter],
+) -> Result<HashMap<String, envoy_types::pb::google::protobuf::Any>, ConversionError> {
+    let mut config = HashMap::new();
+
+    let Some(filter) = http_filters
+        .iter()
+        .find(|f| f.filter_type == "cors" && f.enabled && !f.is_route_scoped())
+    else {
+        return Ok(config);
     };
 
-    // Encode to protobuf bytes with proper error handling
     let mut buf = Vec::new();
-    route_config.encode(&mut buf)
-        .map_err(|e| ConversionError::ProtobufEncoding {
-            resource_type: "RouteConfiguration".to_string(),
-            source: e,
-        })?;
+    cors_policy(filter).encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "CorsPolicy".to_string(),
+        source: e,
+    })?;
+
+    config.insert(
+        get_envoy_filter_name(&filter.filter_type)?,
+        envoy_types::pb::google::protobuf::Any {
+            type_url: "type.googleapis.com/envoy.extensions.filters.http.cors.v3.CorsPolicy".to_string(),
+            value: buf,
+        },
+    );
+    Ok(config)
+}
+
+/// Build per-filter overrides that enable listener-level filters which are disabled by default,
+/// either because the route references them or because the route path falls under their path_prefix,
+/// and that switch off the filters named in the route's `disabled_filters`
+fn per_route_filter_overrides(
+    route_name: &str,
+    route_path: &str,
+    disabled_filters: &[String],
+    route_filters: &[RouteFilters],
+    http_filters: &[InternalHttpFilter],
+) -> Result<HashMap<String, envoy_types::pb::google::protobuf::Any>, ConversionError> {
+    let mut overrides = HashMap::new();
 
-    Ok(vec![Any {
-        type_url: "type.googleapis.com/envoy.config.route.v3.RouteConfiguration".to_string(),
+    let associated: Vec<&InternalHttpFilter> = route_filters
+        .iter()
+        .find(|rf| rf.route_name == route_name)
+        .map(|association| {
+            association
+                .filter_names
+                .iter()
+                .filter_map(|name| http_filters.iter().find(|f| &f.name == name))
+                .filter(|f| f.disabled_by_default)
+                .collect()
+        })
+        .unwrap_or_default();
+    let prefix_matched = http_filters.iter().filter(|f| f.applies_to_path(route_path));
+
+    for filter in associated.into_iter().chain(prefix_matched) {
+        if !filter.enabled {
+            continue;
+        }
+
+        // An explicit, non-disabled FilterConfig turns the filter back on for this route
+        info!("    - Enabling filter '{}' on route '{}'", filter.name, route_name);
+        overrides.insert(get_envoy_filter_name(&filter.filter_type)?, filter_config_override(false)?);
+    }
+
+    // Applied last so an explicit disable wins over any enable above
+    for name in disabled_filters {
+        let Some(filter) = http_filters.iter().find(|f| &f.name == name) else {
+            warn!("    - Route '{}' disables unknown filter '{}', ignoring", route_name, name);
+            continue;
+        };
+        info!("    - Disabling filter '{}' on route '{}'", filter.name, route_name);
+        overrides.insert(get_envoy_filter_name(&filter.filter_type)?, filter_config_override(true)?);
+    }
+
+    Ok(overrides)
+}
+
+/// Encode a route-level `FilterConfig` that turns a listener filter on or off
+fn filter_config_override(disabled: bool) -> Result<envoy_types::pb::google::protobuf::Any, ConversionError> {
+    let filter_config = FilterConfig {
+        config: None,
+        is_optional: false,
+        disabled,
+    };
+
+    let mut buf = Vec::new();
+    filter_config.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+        resource_type: "FilterConfig".to_string(),
+        source: e,
+    })?;
+
+    Ok(envoy_types::pb::google::protobuf::Any {
+        type_url: "type.googleapis.com/envoy.config.route.v3.FilterConfig".to_string(),
         value: buf,
-    }])
-}
\ No newline at end of file
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::RegexRewrite;
+
+    fn decode_route_action(proto_routes: &[Any]) -> RouteAction {
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        match decoded.virtual_hosts[0].routes[0].action.clone() {
+            Some(envoy_types::pb::envoy::config::route::v3::route::Action::Route(action)) => action,
+            _ => panic!("Expected route action"),
+        }
+    }
+
+    #[test]
+    fn test_route_with_regex_rewrite() {
+        let route = InternalRoute {
+            name: "regex-rewrite-route".to_string(),
+            path: "/service".to_string(),
+            cluster_name: "service-cluster".to_string(),
+            regex_rewrite: Some(RegexRewrite {
+                pattern: r"^/service/([^/]+)(/.*)$".to_string(),
+                substitution: r"\2/instance/\1".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let route_action = decode_route_action(&routes_to_proto(vec![route]).unwrap());
+        assert!(route_action.prefix_rewrite.is_empty());
+
+        let regex_rewrite = route_action.regex_rewrite.unwrap();
+        assert_eq!(regex_rewrite.pattern.unwrap().regex, r"^/service/([^/]+)(/.*)$");
+        assert_eq!(regex_rewrite.substitution, r"\2/instance/\1");
+    }
+
+    #[test]
+    fn test_route_with_prefix_and_regex_rewrite_rejected() {
+        let route = InternalRoute {
+            name: "conflicting-rewrite-route".to_string(),
+            path: "/service".to_string(),
+            cluster_name: "service-cluster".to_string(),
+            prefix_rewrite: Some("/backend".to_string()),
+            regex_rewrite: Some(RegexRewrite {
+                pattern: "^/service".to_string(),
+                substitution: "/backend".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        assert!(routes_to_proto(vec![route]).is_err());
+    }
+
+    #[test]
+    fn test_route_with_idle_timeout() {
+        let route = InternalRoute {
+            name: "idle-timeout-route".to_string(),
+            path: "/api/stream".to_string(),
+            cluster_name: "stream-service".to_string(),
+            idle_timeout_seconds: Some(300),
+            ..Default::default()
+        };
+
+        let route_action = decode_route_action(&routes_to_proto(vec![route]).unwrap());
+
+        // Idle timeout is set on its own, leaving the request timeout untouched
+        assert_eq!(route_action.idle_timeout.unwrap().seconds, 300);
+        assert!(route_action.timeout.is_none());
+    }
+
+    #[test]
+    fn test_disabled_by_default_filter_enabled_per_route() {
+        let app_config = crate::config::AppConfig::create_test_config();
+        let auth_filter = InternalHttpFilter::new(
+            "route-auth".to_string(),
+            "authentication".to_string(),
+            serde_json::json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com"
+            }),
+        )
+        .with_disabled_by_default(true);
+
+        // The filter is installed on the listener but disabled globally
+        let listener_filters = super::super::convert_http_filters(
+            vec![auth_filter.clone()],
+            &app_config.control_plane.http_filters.default_order,
+            &app_config,
+        )
+        .unwrap();
+        let jwt_filter = listener_filters
+            .iter()
+            .find(|f| f.name == "envoy.filters.http.jwt_authn")
+            .unwrap();
+        assert!(jwt_filter.disabled);
+
+        let protected = InternalRoute {
+            name: "protected".to_string(),
+            path: "/api/private".to_string(),
+            cluster_name: "private-service".to_string(),
+            ..Default::default()
+        };
+        let public = InternalRoute {
+            name: "public".to_string(),
+            path: "/api/public".to_string(),
+            cluster_name: "public-service".to_string(),
+            ..Default::default()
+        };
+        let route_filters = vec![RouteFilters::new(
+            "protected".to_string(),
+            vec!["route-auth".to_string()],
+        )];
+
+        let proto_routes =
+            routes_to_proto_with_filters(vec![protected, public], &route_filters, &[auth_filter]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let routes = &decoded.virtual_hosts[0].routes;
+
+        // Only the route that references the filter re-enables it
+        let override_any = routes[0]
+            .typed_per_filter_config
+            .get("envoy.filters.http.jwt_authn")
+            .unwrap();
+        assert_eq!(override_any.type_url, "type.googleapis.com/envoy.config.route.v3.FilterConfig");
+        let filter_config = FilterConfig::decode(&override_any.value[..]).unwrap();
+        assert!(!filter_config.disabled);
+        assert!(routes[1].typed_per_filter_config.is_empty());
+    }
+
+    #[test]
+    fn test_maintenance_route_emits_503_direct_response() {
+        let route = InternalRoute {
+            name: "maintenance-route".to_string(),
+            path: "/api/orders".to_string(),
+            cluster_name: "orders-service".to_string(),
+            maintenance_mode: true,
+            maintenance_body: Some("Orders are down for maintenance".to_string()),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto(vec![route]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+
+        match decoded.virtual_hosts[0].routes[0].action.clone() {
+            Some(envoy_types::pb::envoy::config::route::v3::route::Action::DirectResponse(direct)) => {
+                assert_eq!(direct.status, 503);
+                assert_eq!(
+                    direct.body.unwrap().specifier,
+                    Some(data_source::Specifier::InlineString("Orders are down for maintenance".to_string()))
+                );
+            }
+            _ => panic!("Expected direct response action for maintenance route"),
+        }
+    }
+
+    #[test]
+    fn test_route_retry_policy_with_back_off() {
+        let route = InternalRoute {
+            name: "retry-route".to_string(),
+            path: "/api/orders".to_string(),
+            cluster_name: "orders-service".to_string(),
+            retry_policy: Some(crate::storage::models::RetryPolicy {
+                retry_on: "retriable-status-codes".to_string(),
+                num_retries: Some(3),
+                retriable_status_codes: vec![503, 504],
+                retry_back_off: Some(crate::storage::models::RetryBackOff {
+                    base_interval_ms: 250,
+                    max_interval_ms: 2000,
+                }),
+                grpc_retry_on: vec![],
+            }),
+            ..Default::default()
+        };
+
+        let route_action = decode_route_action(&routes_to_proto(vec![route]).unwrap());
+        let retry_policy = route_action.retry_policy.unwrap();
+        assert_eq!(retry_policy.retry_on, "retriable-status-codes");
+        assert_eq!(retry_policy.num_retries, Some(UInt32Value { value: 3 }));
+        assert_eq!(retry_policy.retriable_status_codes, vec![503, 504]);
+
+        let back_off = retry_policy.retry_back_off.unwrap();
+        assert_eq!(back_off.base_interval, Some(Duration { seconds: 0, nanos: 250_000_000 }));
+        assert_eq!(back_off.max_interval, Some(Duration { seconds: 2, nanos: 0 }));
+    }
+
+    #[test]
+    fn test_route_retry_back_off_base_above_max_rejected() {
+        let route = InternalRoute {
+            name: "bad-retry-route".to_string(),
+            path: "/api/orders".to_string(),
+            cluster_name: "orders-service".to_string(),
+            retry_policy: Some(crate::storage::models::RetryPolicy {
+                retry_on: "5xx".to_string(),
+                num_retries: None,
+                retriable_status_codes: vec![],
+                retry_back_off: Some(crate::storage::models::RetryBackOff {
+                    base_interval_ms: 5000,
+                    max_interval_ms: 1000,
+                }),
+                grpc_retry_on: vec![],
+            }),
+            ..Default::default()
+        };
+
+        assert!(routes_to_proto(vec![route]).is_err());
+    }
+
+    #[test]
+    fn test_global_default_request_timeout_applies_to_route_without_timeout() {
+        let mut app_config = AppConfig::create_test_config();
+        app_config.envoy_generation.listener.default_request_timeout_seconds = Some(15);
+
+        let route = InternalRoute {
+            name: "default-timeout-route".to_string(),
+            path: "/api/reports".to_string(),
+            cluster_name: "reports-service".to_string(),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto_with_config(vec![route], &[], &[], &app_config).unwrap();
+        let route_action = decode_route_action(&proto_routes);
+        assert_eq!(route_action.timeout, Some(Duration { seconds: 15, nanos: 0 }));
+    }
+
+    #[test]
+    fn test_route_timeout_overrides_global_default() {
+        let mut app_config = AppConfig::create_test_config();
+        app_config.envoy_generation.listener.default_request_timeout_seconds = Some(15);
+
+        let route = InternalRoute {
+            name: "custom-timeout-route".to_string(),
+            path: "/api/exports".to_string(),
+            cluster_name: "exports-service".to_string(),
+            timeout_seconds: Some(120),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto_with_config(vec![route], &[], &[], &app_config).unwrap();
+        let route_action = decode_route_action(&proto_routes);
+        assert_eq!(route_action.timeout, Some(Duration { seconds: 120, nanos: 0 }));
+    }
+
+    #[test]
+    fn test_path_prefix_filter_applies_only_to_matching_routes() {
+        let admin_auth = InternalHttpFilter::new(
+            "admin-auth".to_string(),
+            "authentication".to_string(),
+            serde_json::json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com"
+            }),
+        )
+        .with_path_prefix(Some("/admin".to_string()));
+
+        let admin_users = InternalRoute {
+            name: "admin-users".to_string(),
+            path: "/admin/users".to_string(),
+            cluster_name: "admin-service".to_string(),
+            ..Default::default()
+        };
+        let public = InternalRoute {
+            name: "public".to_string(),
+            path: "/public".to_string(),
+            cluster_name: "public-service".to_string(),
+            ..Default::default()
+        };
+        let administrator = InternalRoute {
+            name: "administrator".to_string(),
+            path: "/administrator".to_string(),
+            cluster_name: "public-service".to_string(),
+            ..Default::default()
+        };
+
+        let proto_routes =
+            routes_to_proto_with_filters(vec![admin_users, public, administrator], &[], &[admin_auth]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let routes = &decoded.virtual_hosts[0].routes;
+
+        let override_any = routes[0]
+            .typed_per_filter_config
+            .get("envoy.filters.http.jwt_authn")
+            .expect("filter should be enabled on /admin/users");
+        let filter_config = FilterConfig::decode(&override_any.value[..]).unwrap();
+        assert!(!filter_config.disabled);
+        assert!(routes[1].typed_per_filter_config.is_empty());
+        assert!(
+            routes[2].typed_per_filter_config.is_empty(),
+            "/admin must not match /administrator"
+        );
+    }
+
+    #[test]
+    fn test_maintenance_route_carries_retry_after_header() {
+        let route = InternalRoute {
+            name: "maintenance-route".to_string(),
+            path: "/api/orders".to_string(),
+            cluster_name: "orders-service".to_string(),
+            maintenance_mode: true,
+            response_headers_to_add: Some(HashMap::from([("Retry-After".to_string(), "120".to_string())])),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto(vec![route]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let route = &decoded.virtual_hosts[0].routes[0];
+
+        assert!(matches!(
+            route.action,
+            Some(envoy_types::pb::envoy::config::route::v3::route::Action::DirectResponse(_))
+        ));
+        let header = route.response_headers_to_add[0].header.as_ref().unwrap();
+        assert_eq!(header.key, "Retry-After");
+        assert_eq!(header.value, "120");
+    }
+
+    #[test]
+    fn test_invalid_response_header_name_rejected() {
+        let route = InternalRoute {
+            name: "bad-header-route".to_string(),
+            path: "/api/orders".to_string(),
+            cluster_name: "orders-service".to_string(),
+            response_headers_to_add: Some(HashMap::from([("Retry After".to_string(), "120".to_string())])),
+            ..Default::default()
+        };
+
+        assert!(routes_to_proto(vec![route]).is_err());
+    }
+
+    #[test]
+    fn test_grpc_retry_conditions_emitted_in_retry_on() {
+        let route = InternalRoute {
+            name: "grpc-retry-route".to_string(),
+            path: "/orders.OrderService/".to_string(),
+            cluster_name: "orders-grpc".to_string(),
+            retry_policy: Some(crate::storage::models::RetryPolicy {
+                retry_on: "retriable-status-codes".to_string(),
+                num_retries: Some(2),
+                retriable_status_codes: vec![503],
+                retry_back_off: None,
+                grpc_retry_on: vec!["cancelled".to_string(), "unavailable".to_string()],
+            }),
+            ..Default::default()
+        };
+
+        let route_action = decode_route_action(&routes_to_proto(vec![route]).unwrap());
+        let retry_policy = route_action.retry_policy.unwrap();
+        assert_eq!(retry_policy.retry_on, "retriable-status-codes,cancelled,unavailable");
+        assert_eq!(retry_policy.retriable_status_codes, vec![503]);
+    }
+
+    #[test]
+    fn test_unknown_grpc_retry_condition_rejected() {
+        let route = InternalRoute {
+            name: "grpc-retry-route".to_string(),
+            path: "/orders.OrderService/".to_string(),
+            cluster_name: "orders-grpc".to_string(),
+            retry_policy: Some(crate::storage::models::RetryPolicy {
+                retry_on: String::new(),
+                num_retries: None,
+                retriable_status_codes: vec![],
+                retry_back_off: None,
+                grpc_retry_on: vec!["not-found".to_string()],
+            }),
+            ..Default::default()
+        };
+
+        assert!(routes_to_proto(vec![route]).is_err());
+    }
+
+    #[test]
+    fn test_cors_policy_attached_to_virtual_host() {
+        let cors = InternalHttpFilter::new(
+            "api-cors".to_string(),
+            "cors".to_string(),
+            serde_json::json!({
+                "allowed_origins": ["https://example.com"],
+                "expose_headers": ["X-Request-Id"],
+                "max_age": 600
+            }),
+        );
+        let route = InternalRoute {
+            name: "api".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "api-service".to_string(),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto_with_config(vec![route], &[], &[cors], &AppConfig::create_test_config()).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+
+        let policy_any = decoded.virtual_hosts[0]
+            .typed_per_filter_config
+            .get("envoy.filters.http.cors")
+            .expect("virtual host should carry the CORS policy");
+        let policy =
+            envoy_types::pb::envoy::extensions::filters::http::cors::v3::CorsPolicy::decode(&policy_any.value[..])
+                .unwrap();
+        assert_eq!(policy.expose_headers, "X-Request-Id");
+        assert_eq!(policy.max_age, "600");
+    }
+
+    #[test]
+    fn test_https_only_route_matches_scheme_header() {
+        let route = InternalRoute {
+            name: "secure-route".to_string(),
+            path: "/account".to_string(),
+            cluster_name: "account-service".to_string(),
+            scheme_match: Some("https".to_string()),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto(vec![route]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let route_match = decoded.virtual_hosts[0].routes[0].r#match.clone().unwrap();
+
+        let scheme_matcher = route_match.headers.iter().find(|h| h.name == ":scheme").unwrap();
+        assert_eq!(
+            scheme_matcher.header_match_specifier,
+            Some(envoy_types::pb::envoy::config::route::v3::header_matcher::HeaderMatchSpecifier::StringMatch(
+                StringMatcher {
+                    match_pattern: Some(
+                        envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::Exact("https".to_string())
+                    ),
+                    ..Default::default()
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_weighted_clusters_carry_runtime_key_prefix() {
+        let route = InternalRoute {
+            name: "checkout-rollout".to_string(),
+            path: "/checkout".to_string(),
+            cluster_name: "checkout-v1".to_string(),
+            weighted_clusters: Some(crate::storage::models::WeightedClusters {
+                clusters: vec![
+                    crate::storage::models::ClusterWeight {
+                        name: "checkout-v1".to_string(),
+                        weight: 90,
+                    },
+                    crate::storage::models::ClusterWeight {
+                        name: "checkout-v2".to_string(),
+                        weight: 10,
+                    },
+                ],
+                runtime_key_prefix: Some("routing.checkout".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        let action = decode_route_action(&routes_to_proto(vec![route]).unwrap());
+        let Some(route_action::ClusterSpecifier::WeightedClusters(weighted)) = action.cluster_specifier else {
+            panic!("Expected weighted clusters");
+        };
+        assert_eq!(weighted.runtime_key_prefix, "routing.checkout");
+        assert_eq!(weighted.clusters.len(), 2);
+        assert_eq!(weighted.clusters[1].name, "checkout-v2");
+        assert_eq!(weighted.clusters[1].weight, Some(UInt32Value { value: 10 }));
+    }
+
+    #[test]
+    fn test_unsafe_runtime_key_prefix_rejected() {
+        let route = InternalRoute {
+            name: "checkout-rollout".to_string(),
+            path: "/checkout".to_string(),
+            cluster_name: "checkout-v1".to_string(),
+            weighted_clusters: Some(crate::storage::models::WeightedClusters {
+                clusters: vec![crate::storage::models::ClusterWeight {
+                    name: "checkout-v1".to_string(),
+                    weight: 100,
+                }],
+                runtime_key_prefix: Some("routing..checkout\n".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        assert!(routes_to_proto(vec![route]).is_err());
+    }
+
+    #[test]
+    fn test_high_routing_priority_set_on_route_action() {
+        let route = InternalRoute {
+            name: "payments-critical".to_string(),
+            path: "/payments".to_string(),
+            cluster_name: "payments-service".to_string(),
+            routing_priority: Some("high".to_string()),
+            ..Default::default()
+        };
+
+        let action = decode_route_action(&routes_to_proto(vec![route]).unwrap());
+        assert_eq!(action.priority, RoutingPriority::High as i32);
+    }
+
+    #[test]
+    fn test_envoy_route_name_matches_stored_name() {
+        let route = InternalRoute {
+            name: "orders-api".to_string(),
+            path: "/orders".to_string(),
+            cluster_name: "orders-service".to_string(),
+            description: Some("Public orders API".to_string()),
+            ..Default::default()
+        };
+
+        let route_configs = routes_to_proto(vec![route]).unwrap();
+        let route_config = RouteConfiguration::decode(&route_configs[0].value[..]).unwrap();
+        let envoy_route = &route_config.virtual_hosts[0].routes[0];
+        assert_eq!(envoy_route.name, "orders-api");
+    }
+
+    #[test]
+    fn test_content_type_match_emits_header_matcher() {
+        let route = InternalRoute {
+            name: "protobuf-api".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "protobuf-service".to_string(),
+            content_type_match: Some(crate::storage::ContentTypeMatch {
+                header: "accept".to_string(),
+                media_type: "application/x-protobuf".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto(vec![route]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let route_match = decoded.virtual_hosts[0].routes[0].r#match.clone().unwrap();
+        let matcher = route_match.headers.iter().find(|h| h.name == "accept").expect("accept header matcher");
+        match &matcher.header_match_specifier {
+            Some(envoy_types::pb::envoy::config::route::v3::header_matcher::HeaderMatchSpecifier::StringMatch(m)) => {
+                assert!(m.ignore_case);
+                assert_eq!(
+                    m.match_pattern,
+                    Some(envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::Contains(
+                        "application/x-protobuf".to_string()
+                    ))
+                );
+            }
+            other => panic!("Expected string matcher, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_routes_for_two_listeners_yield_two_route_configs() {
+        let mut app_config = AppConfig::create_test_config();
+        app_config.envoy_generation.listener.additional_listeners = vec![crate::config::AdditionalListenerConfig {
+            name: "admin_listener".to_string(),
+            port: 10001,
+            domains: None,
+        }];
+
+        let public_route = InternalRoute {
+            name: "public-route".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "api-cluster".to_string(),
+            ..Default::default()
+        };
+        let admin_route = InternalRoute {
+            name: "admin-route".to_string(),
+            path: "/admin".to_string(),
+            cluster_name: "admin-cluster".to_string(),
+            listener: Some("admin_listener".to_string()),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto_with_config(vec![admin_route, public_route], &[], &[], &app_config).unwrap();
+        assert_eq!(proto_routes.len(), 2);
+
+        let route_configs: Vec<RouteConfiguration> = proto_routes
+            .iter()
+            .map(|any| RouteConfiguration::decode(&any.value[..]).unwrap())
+            .collect();
+        let naming = &app_config.envoy_generation.naming;
+        assert_eq!(route_configs[0].name, naming.route_config_name);
+        assert_eq!(route_configs[0].virtual_hosts[0].routes[0].name, "public-route");
+        assert_eq!(route_configs[1].name, format!("{}_admin_listener", naming.route_config_name));
+        assert_eq!(route_configs[1].virtual_hosts[0].routes[0].name, "admin-route");
+
+        let unknown = InternalRoute {
+            name: "orphan-route".to_string(),
+            path: "/orphan".to_string(),
+            cluster_name: "api-cluster".to_string(),
+            listener: Some("missing_listener".to_string()),
+            ..Default::default()
+        };
+        assert!(routes_to_proto_with_config(vec![unknown], &[], &[], &app_config).is_err());
+    }
+
+    #[test]
+    fn test_explain_reports_first_matching_route_in_order() {
+        let prefix_route = |name: &str, path: &str| InternalRoute {
+            name: name.to_string(),
+            path: path.to_string(),
+            cluster_name: "api-cluster".to_string(),
+            ..Default::default()
+        };
+        let request = SimulatedRequest {
+            method: "GET".to_string(),
+            path: "/api/v1/users".to_string(),
+            ..Default::default()
+        };
+
+        let specific_first = vec![prefix_route("api-v1", "/api/v1"), prefix_route("api", "/api")];
+        let explanation = explain_route_match(&specific_first, &request);
+        assert_eq!(explanation.matched_route.as_deref(), Some("api-v1"));
+        assert_eq!(explanation.reasons, vec!["path '/api/v1/users' starts with prefix '/api/v1'"]);
+
+        // The broader prefix shadows the specific one when it comes first
+        let broad_first = vec![prefix_route("api", "/api"), prefix_route("api-v1", "/api/v1")];
+        assert_eq!(explain_route_match(&broad_first, &request).matched_route.as_deref(), Some("api"));
+    }
+
+    #[test]
+    fn test_explain_skips_routes_whose_method_does_not_match() {
+        let routes = vec![
+            InternalRoute {
+                name: "api-writes".to_string(),
+                path: "/api".to_string(),
+                cluster_name: "api-cluster".to_string(),
+                http_methods: Some(vec!["POST".to_string(), "PUT".to_string()]),
+                ..Default::default()
+            },
+            InternalRoute {
+                name: "api-reads".to_string(),
+                path: "/api".to_string(),
+                cluster_name: "api-cluster".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let get = SimulatedRequest {
+            method: "GET".to_string(),
+            path: "/api/items".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(explain_route_match(&routes, &get).matched_route.as_deref(), Some("api-reads"));
+
+        let post = SimulatedRequest {
+            method: "POST".to_string(),
+            ..get.clone()
+        };
+        let explanation = explain_route_match(&routes, &post);
+        assert_eq!(explanation.matched_route.as_deref(), Some("api-writes"));
+        assert!(explanation.reasons.contains(&"method POST is one of [POST, PUT]".to_string()));
+
+        let unmatched = SimulatedRequest {
+            path: "/other".to_string(),
+            ..get
+        };
+        assert_eq!(explain_route_match(&routes, &unmatched).matched_route, None);
+    }
+
+    #[test]
+    fn test_case_insensitive_route_match() {
+        let route = InternalRoute {
+            name: "legacy-route".to_string(),
+            path: "/Legacy/API".to_string(),
+            cluster_name: "legacy-cluster".to_string(),
+            case_sensitive: Some(false),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto(vec![route.clone()]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let route_match = decoded.virtual_hosts[0].routes[0].r#match.clone().unwrap();
+        assert_eq!(route_match.case_sensitive, Some(BoolValue { value: false }));
+
+        let request = SimulatedRequest {
+            method: "GET".to_string(),
+            path: "/legacy/api/items".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(explain_route_match(&[route], &request).matched_route.as_deref(), Some("legacy-route"));
+    }
+
+    #[test]
+    fn test_request_headers_to_remove_set_on_route() {
+        let route = InternalRoute {
+            name: "strip-internal-headers".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "api-cluster".to_string(),
+            request_headers_to_remove: Some(vec!["x-internal-token".to_string(), "x-debug".to_string()]),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto(vec![route]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        assert_eq!(
+            decoded.virtual_hosts[0].routes[0].request_headers_to_remove,
+            vec!["x-internal-token", "x-debug"]
+        );
+
+        let pseudo_header = InternalRoute {
+            name: "strip-path".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "api-cluster".to_string(),
+            request_headers_to_remove: Some(vec![":path".to_string()]),
+            ..Default::default()
+        };
+        assert!(routes_to_proto(vec![pseudo_header]).is_err());
+    }
+
+    #[test]
+    fn test_route_config_prefers_most_specific_header_mutations() {
+        let route = InternalRoute::new(
+            "header-route".to_string(),
+            "/api".to_string(),
+            "test-cluster".to_string(),
+            None,
+        );
+
+        let proto_routes = routes_to_proto(vec![route]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        assert!(decoded.most_specific_header_mutations_wins);
+    }
+
+    #[test]
+    fn test_specific_route_after_catch_all_prefix_is_shadowed() {
+        let route = |name: &str, path: &str| {
+            InternalRoute::new(name.to_string(), path.to_string(), "backend".to_string(), None)
+        };
+        let users_post_only = InternalRoute {
+            http_methods: Some(vec!["POST".to_string()]),
+            ..route("users-post", "/api/users")
+        };
+        let get_only_api = InternalRoute {
+            http_methods: Some(vec!["GET".to_string()]),
+            ..route("api-get", "/internal")
+        };
+        let routes = vec![
+            route("api", "/api"),
+            route("users", "/api/users"),
+            get_only_api,
+            route("internal-admin", "/internal/admin"),
+            users_post_only,
+        ];
+
+        let shadowed = find_shadowed_routes(&routes);
+
+        assert_eq!(
+            shadowed,
+            vec![
+                ShadowedRoute {
+                    route: "users".to_string(),
+                    path: "/api/users".to_string(),
+                    shadowed_by: "api".to_string(),
+                    shadowed_by_path: "/api".to_string(),
+                },
+                ShadowedRoute {
+                    route: "users-post".to_string(),
+                    path: "/api/users".to_string(),
+                    shadowed_by: "api".to_string(),
+                    shadowed_by_path: "/api".to_string(),
+                },
+            ]
+        );
+        // A method-restricted prefix leaves other methods to later routes
+        assert!(!shadowed.iter().any(|s| s.route == "internal-admin"));
+
+        // Specific routes first are all reachable
+        assert!(find_shadowed_routes(&[route("users", "/api/users"), route("api", "/api")]).is_empty());
+    }
+
+    #[test]
+    fn test_route_configs_get_their_configured_domains() {
+        let mut app_config = AppConfig::create_test_config();
+        app_config.envoy_generation.naming.default_domains = vec!["api.example.com".to_string()];
+        app_config.envoy_generation.listener.additional_listeners = vec![crate::config::AdditionalListenerConfig {
+            name: "tenant_listener".to_string(),
+            port: 10001,
+            domains: Some(vec!["*.tenant.example.com".to_string(), "tenant.example.com:8443".to_string()]),
+        }];
+
+        let public_route = InternalRoute {
+            name: "public-route".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "api-cluster".to_string(),
+            ..Default::default()
+        };
+        let tenant_route = InternalRoute {
+            name: "tenant-route".to_string(),
+            path: "/tenant".to_string(),
+            cluster_name: "tenant-cluster".to_string(),
+            listener: Some("tenant_listener".to_string()),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto_with_config(vec![tenant_route, public_route], &[], &[], &app_config).unwrap();
+        let route_configs: Vec<RouteConfiguration> = proto_routes
+            .iter()
+            .map(|any| RouteConfiguration::decode(&any.value[..]).unwrap())
+            .collect();
+
+        assert_eq!(route_configs[0].virtual_hosts[0].domains, vec!["api.example.com"]);
+        assert_eq!(
+            route_configs[1].virtual_hosts[0].domains,
+            vec!["*.tenant.example.com", "tenant.example.com:8443"]
+        );
+    }
+
+    #[test]
+    fn test_route_disabled_filters_turn_off_auth_filter() {
+        let auth_filter = InternalHttpFilter::new(
+            "api-auth".to_string(),
+            "authentication".to_string(),
+            serde_json::json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com"
+            }),
+        );
+
+        let health = InternalRoute {
+            name: "health".to_string(),
+            path: "/health".to_string(),
+            cluster_name: "api-service".to_string(),
+            disabled_filters: Some(vec!["api-auth".to_string()]),
+            ..Default::default()
+        };
+        let protected = InternalRoute {
+            name: "protected".to_string(),
+            path: "/api".to_string(),
+            cluster_name: "api-service".to_string(),
+            ..Default::default()
+        };
+
+        let proto_routes = routes_to_proto_with_filters(vec![health, protected], &[], &[auth_filter]).unwrap();
+        let decoded = RouteConfiguration::decode(&proto_routes[0].value[..]).unwrap();
+        let routes = &decoded.virtual_hosts[0].routes;
+
+        let override_any = routes[0]
+            .typed_per_filter_config
+            .get("envoy.filters.http.jwt_authn")
+            .unwrap();
+        assert_eq!(override_any.type_url, "type.googleapis.com/envoy.config.route.v3.FilterConfig");
+        assert!(FilterConfig::decode(&override_any.value[..]).unwrap().disabled);
+        assert!(routes[1].typed_per_filter_config.is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+//! Startup self-test for stored resources
+//!
+//! Runs every stored cluster, route and HTTP filter through the same conversion
+//! path used by the xDS server, so resources that no longer convert (for example
+//! a filter whose type was removed) are reported before Envoy ever requests them.
+//! The same check backs the on-demand `GET /admin/config-status` report.
+
+use super::errors::ConversionError;
+use super::{clusters_to_proto, convert_http_filters, routes_to_proto};
+use crate::config::AppConfig;
+use crate::storage::ConfigStore;
+use serde::Serialize;
+use tracing::{error, info};
+
+/// A single resource that failed the conversion self-test
+#[derive(Debug)]
+pub struct SelfTestFailure {
+    pub resource_type: &'static str,
+    pub resource_name: String,
+    pub error: ConversionError,
+}
+
+/// Per-resource outcome of the conversion check, as reported by `GET /admin/config-status`
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceStatus {
+    pub resource_type: &'static str,
+    pub name: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Convert each stored resource individually and collect any failures
+///
+/// Resources are converted one at a time so a single bad resource does not hide
+/// problems with the others.
+pub fn run_conversion_self_test(store: &ConfigStore, app_config: &AppConfig) -> Vec<SelfTestFailure> {
+    let failures: Vec<SelfTestFailure> = check_stored_resources(store, app_config)
+        .into_iter()
+        .filter_map(|(resource_type, resource_name, result)| {
+            result.err().map(|error| SelfTestFailure {
+                resource_type,
+                resource_name,
+                error,
+            })
+        })
+        .collect();
+
+    if failures.is_empty() {
+        info!("✅ Conversion self-test passed for all stored resources");
+    } else {
+        for failure in &failures {
+            error!(
+                "❌ Conversion self-test failed for {} '{}': {}",
+                failure.resource_type, failure.resource_name, failure.error
+            );
+        }
+    }
+
+    failures
+}
+
+/// On-demand variant of the self-test reporting every resource, passing or not
+pub fn conversion_status(store: &ConfigStore, app_config: &AppConfig) -> Vec<ResourceStatus> {
+    check_stored_resources(store, app_config)
+        .into_iter()
+        .map(|(resource_type, name, result)| ResourceStatus {
+            resource_type,
+            name,
+            ok: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+        })
+        .collect()
+}
+
+fn check_stored_resources(
+    store: &ConfigStore,
+    app_config: &AppConfig,
+) -> Vec<(&'static str, String, Result<(), ConversionError>)> {
+    let mut results = Vec::new();
+
+    for cluster in store.list_clusters() {
+        let result = clusters_to_proto(vec![(*cluster).clone()]).map(|_| ());
+        results.push(("Cluster", cluster.name.clone(), result));
+    }
+
+    for route in store.list_routes() {
+        let result = routes_to_proto(vec![(*route).clone()]).map(|_| ());
+        results.push(("Route", route.name.clone(), result));
+    }
+
+    for filter in store.list_http_filters() {
+        // Convert against the filter's own type so types missing from default_order are still checked
+        let order = vec![filter.filter_type.clone()];
+        let result = convert_http_filters(vec![(*filter).clone()], &order, app_config).map(|_| ());
+        results.push(("HttpFilter", filter.name.clone(), result));
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::HttpFilter;
+    use serde_json::json;
+
+    #[test]
+    fn test_self_test_passes_for_empty_store() {
+        let store = ConfigStore::new();
+        let app_config = AppConfig::create_test_config();
+
+        assert!(run_conversion_self_test(&store, &app_config).is_empty());
+    }
+
+    #[test]
+    fn test_self_test_reports_unsupported_filter_type() {
+        let store = ConfigStore::new();
+        let app_config = AppConfig::create_test_config();
+
+        // The store accepts the filter because the type is listed as supported,
+        // but no conversion strategy exists for it anymore
+        let filter = HttpFilter::new(
+            "legacy-filter".to_string(),
+            "legacy_compression".to_string(),
+            json!({"level": 5}),
+        );
+        store
+            .add_http_filter(filter, &["legacy_compression".to_string()])
+            .unwrap();
+
+        let failures = run_conversion_self_test(&store, &app_config);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].resource_type, "HttpFilter");
+        assert_eq!(failures[0].resource_name, "legacy-filter");
+        assert!(matches!(
+            failures[0].error,
+            ConversionError::UnsupportedFilterType { .. }
+        ));
+    }
+}
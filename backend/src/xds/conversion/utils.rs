@@ -1,6 +1,6 @@
 use super::errors::ConversionError;
 use crate::config::AppConfig;
-use crate::storage::models::{Cluster as InternalCluster, Route as InternalRoute};
+use crate::storage::models::{Cluster as InternalCluster, LoadBalancingPolicy, Route as InternalRoute};
 use crate::validation::security::Validator;
 use tracing::{info, warn};
 
@@ -53,6 +53,124 @@ pub fn validate_route(route: &InternalRoute) -> Result<(), ConversionError> {
             reason: format!("Route path '{}' failed security validation: {}", route.path, e)
         })?;
 
+    if route.prefix_rewrite.is_some() && route.regex_rewrite.is_some() {
+        return Err(ConversionError::InvalidResource {
+            resource_type: "Route".to_string(),
+            resource_id: route.path.clone(),
+            reason: "prefix_rewrite and regex_rewrite are mutually exclusive".to_string(),
+        });
+    }
+
+    if let Some(idle_timeout) = route.idle_timeout_seconds {
+        if !(1..=3600).contains(&idle_timeout) {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: format!("idle_timeout_seconds must be between 1 and 3600, got {}", idle_timeout),
+            });
+        }
+    }
+
+    if let Some(back_off) = route.retry_policy.as_ref().and_then(|p| p.retry_back_off.as_ref()) {
+        if back_off.base_interval_ms == 0 || back_off.base_interval_ms > back_off.max_interval_ms {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: format!(
+                    "retry_back_off requires 0 < base_interval_ms <= max_interval_ms, got {}ms/{}ms",
+                    back_off.base_interval_ms, back_off.max_interval_ms
+                ),
+            });
+        }
+    }
+
+    if let Some(policy) = &route.retry_policy {
+        if crate::validation::validate_retry_policy(policy).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: format!(
+                    "retry_policy has unknown retry conditions (retry_on '{}', grpc_retry_on {:?}) or invalid status codes",
+                    policy.retry_on, policy.grpc_retry_on
+                ),
+            });
+        }
+    }
+
+    if let Some(scheme) = &route.scheme_match {
+        if crate::validation::validate_scheme_match(scheme).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: format!("scheme_match must be 'http' or 'https', got '{}'", scheme),
+            });
+        }
+    }
+
+    if let Some(content_type) = &route.content_type_match {
+        if crate::validation::validate_content_type_match(content_type).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: format!(
+                    "content_type_match needs header 'accept' or 'content-type' and a type/subtype media type, got {} '{}'",
+                    content_type.header, content_type.media_type
+                ),
+            });
+        }
+    }
+
+    if let Some(priority) = &route.routing_priority {
+        if crate::validation::validate_routing_priority(priority).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: format!("routing_priority must be 'default' or 'high', got '{}'", priority),
+            });
+        }
+    }
+
+    if let Some(weighted) = &route.weighted_clusters {
+        if crate::validation::validate_weighted_clusters(weighted).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: "weighted_clusters needs positive weights, valid cluster names and a safe runtime_key_prefix"
+                    .to_string(),
+            });
+        }
+    }
+
+    if let Some(headers) = &route.response_headers_to_add {
+        if crate::validation::validate_response_headers(headers).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: "response_headers_to_add contains an invalid header name or value".to_string(),
+            });
+        }
+    }
+
+    if let Some(headers) = &route.request_headers_to_remove {
+        if crate::validation::validate_request_headers_to_remove(headers).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: "request_headers_to_remove contains an invalid or protected header name".to_string(),
+            });
+        }
+    }
+
+    if let Some(names) = &route.disabled_filters {
+        if crate::validation::validate_disabled_filters(names).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Route".to_string(),
+                resource_id: route.path.clone(),
+                reason: "disabled_filters must list distinct HTTP filter names".to_string(),
+            });
+        }
+    }
+
     // Validate HTTP methods if present
     if let Some(methods) = &route.http_methods {
         for method in methods {
@@ -70,7 +188,9 @@ pub fn validate_route(route: &InternalRoute) -> Result<(), ConversionError> {
 }
 
 /// Validate cluster configuration for XDS conversion
-pub fn validate_cluster(cluster: &InternalCluster) -> Result<(), ConversionError> {
+///
+/// `lb_policy` is the cluster's policy resolved against the configured default.
+pub fn validate_cluster(cluster: &InternalCluster, lb_policy: &LoadBalancingPolicy) -> Result<(), ConversionError> {
     if cluster.name.is_empty() {
         return Err(ConversionError::InvalidResource {
             resource_type: "Cluster".to_string(),
@@ -119,6 +239,185 @@ pub fn validate_cluster(cluster: &InternalCluster) -> Result<(), ConversionError
                 reason: format!("Cluster '{}' endpoint {} host '{}' failed security validation: {}", 
                                cluster.name, i, endpoint.host, e)
             })?;
+
+        if let Some(ref hostname) = endpoint.hostname {
+            if crate::validation::validate_hostname(hostname).is_err() {
+                return Err(ConversionError::InvalidResource {
+                    resource_type: "Cluster".to_string(),
+                    resource_id: cluster.name.clone(),
+                    reason: format!("Endpoint {} has invalid hostname '{}'", i, hostname),
+                });
+            }
+        }
+
+        if let Some(ref additional_addresses) = endpoint.additional_addresses {
+            if crate::validation::validate_additional_addresses(additional_addresses).is_err() {
+                return Err(ConversionError::InvalidResource {
+                    resource_type: "Cluster".to_string(),
+                    resource_id: cluster.name.clone(),
+                    reason: format!("Endpoint {} additional_addresses must be 1-4 distinct IP addresses", i),
+                });
+            }
+        }
+
+        if let Some(ref health_status) = endpoint.health_status {
+            if !matches!(health_status.as_str(), "HEALTHY" | "DEGRADED" | "UNHEALTHY") {
+                return Err(ConversionError::InvalidResource {
+                    resource_type: "Cluster".to_string(),
+                    resource_id: cluster.name.clone(),
+                    reason: format!("Endpoint {} has invalid health status '{}'", i, health_status),
+                });
+            }
+        }
+    }
+
+    let weighted = cluster.endpoints.iter().filter(|e| e.weight.is_some()).count();
+    if weighted > 0 && weighted < cluster.endpoints.len() {
+        return Err(ConversionError::InvalidResource {
+            resource_type: "Cluster".to_string(),
+            resource_id: cluster.name.clone(),
+            reason: "Endpoint weights must be set on all endpoints or none".to_string(),
+        });
+    }
+    if cluster.endpoints.iter().any(|e| e.weight == Some(0)) {
+        return Err(ConversionError::InvalidResource {
+            resource_type: "Cluster".to_string(),
+            resource_id: cluster.name.clone(),
+            reason: "Endpoint weights must be positive".to_string(),
+        });
+    }
+
+    if cluster.locality_weighted_lb {
+        let weights = cluster.locality_weights.as_ref();
+        for (i, endpoint) in cluster.endpoints.iter().enumerate() {
+            let weight = endpoint
+                .locality
+                .as_ref()
+                .and_then(|locality| weights.and_then(|w| w.get(locality)));
+            match weight {
+                Some(weight) if *weight > 0 => {}
+                _ => {
+                    return Err(ConversionError::InvalidResource {
+                        resource_type: "Cluster".to_string(),
+                        resource_id: cluster.name.clone(),
+                        reason: format!(
+                            "Endpoint {} needs a locality with a positive weight when locality_weighted_lb is enabled",
+                            i
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(choice_count) = cluster.choice_count {
+        if *lb_policy != LoadBalancingPolicy::LeastRequest {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: "choice_count is only valid with the LEAST_REQUEST load balancing policy".to_string(),
+            });
+        }
+        if choice_count < 2 {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!("choice_count must be at least 2, got {}", choice_count),
+            });
+        }
+    }
+
+    if let Some(ref http_protocol) = cluster.http_protocol {
+        if crate::validation::validate_http_protocol(http_protocol).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!("http_protocol must be auto, http1 or http2, got '{}'", http_protocol),
+            });
+        }
+    }
+
+    if let Some(ref action) = cluster.headers_with_underscores_action {
+        if crate::validation::validate_headers_with_underscores_action(action).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!(
+                    "headers_with_underscores_action must be ALLOW, REJECT_REQUEST or DROP_HEADER, got '{}'",
+                    action
+                ),
+            });
+        }
+    }
+
+    if let Some(seconds) = cluster.dns_failure_refresh_rate_seconds {
+        if !(1..=300).contains(&seconds) {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!("dns_failure_refresh_rate_seconds must be between 1 and 300, got {}", seconds),
+            });
+        }
+    }
+
+    if let Some(ref alt_stat_name) = cluster.alt_stat_name {
+        if alt_stat_name.len() > 100 || crate::validation::validate_stat_name(alt_stat_name).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!("alt_stat_name '{}' is not a valid stat name", alt_stat_name),
+            });
+        }
+    }
+
+    if let Some(ref metadata) = cluster.metadata {
+        if crate::validation::validate_cluster_metadata(metadata).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: "metadata must map each namespace to a JSON object".to_string(),
+            });
+        }
+    }
+
+    if let Some(ref alpn_protocols) = cluster.alpn_protocols {
+        if crate::validation::validate_alpn_protocols(alpn_protocols).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: "alpn_protocols must be a non-empty list of ALPN protocol ids".to_string(),
+            });
+        }
+    }
+
+    if let Some(ref dns_resolvers) = cluster.dns_resolvers {
+        if crate::validation::validate_dns_resolvers(dns_resolvers).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: "dns_resolvers must be a list of 1-10 IP:port addresses".to_string(),
+            });
+        }
+    }
+
+    if let Some(ref retry_budget) = cluster.retry_budget {
+        if crate::validation::validate_retry_budget(retry_budget).is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!("retry_budget.budget_percent {} must be in (0, 100]", retry_budget.budget_percent),
+            });
+        }
+    }
+
+    if let Some(ref source_address) = cluster.source_address {
+        if source_address.parse::<std::net::IpAddr>().is_err() {
+            return Err(ConversionError::InvalidResource {
+                resource_type: "Cluster".to_string(),
+                resource_id: cluster.name.clone(),
+                reason: format!("source_address '{}' is not a valid IP address", source_address),
+            });
+        }
     }
 
     Ok(())
@@ -168,6 +467,7 @@ pub fn get_envoy_filter_name(filter_type: &str) -> Result<String, ConversionErro
         "authentication" => "envoy.filters.http.jwt_authn",
         "header_manipulation" => "envoy.filters.http.lua",
         "request_validation" => "envoy.filters.http.rbac",
+        "header_to_metadata" => "envoy.filters.http.header_to_metadata",
         _ => {
             return Err(ConversionError::UnsupportedFilterType {
                 filter_type: filter_type.to_string(),
@@ -177,6 +477,7 @@ pub fn get_envoy_filter_name(filter_type: &str) -> Result<String, ConversionErro
                     "authentication".to_string(),
                     "header_manipulation".to_string(),
                     "request_validation".to_string(),
+                    "header_to_metadata".to_string(),
                 ],
             });
         }
@@ -1,8 +1,10 @@
 use crate::storage::HttpFilter as InternalHttpFilter;
 use crate::xds::conversion::ConversionError;
-use crate::xds::filters::FilterStrategy;
+use crate::xds::filters::{required_config_field, FilterStrategy};
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
-use envoy_types::pb::envoy::extensions::filters::http::jwt_authn::v3::{JwtAuthentication, JwtProvider, JwtRequirement};
+use envoy_types::pb::envoy::extensions::filters::http::jwt_authn::v3::{
+    JwtAuthentication, JwtClaimToHeader, JwtProvider, JwtRequirement,
+};
 use envoy_types::pb::google::protobuf::Any;
 use std::collections::HashMap;
 use tracing::info;
@@ -11,6 +13,73 @@ use base64::prelude::*;
 /// Strategy for converting authentication filters to Envoy JWT Authentication
 pub struct AuthenticationStrategy;
 
+impl AuthenticationStrategy {
+    /// Parse the optional `audiences` list, rejecting non-string or empty entries
+    fn parse_audiences(filter: &InternalHttpFilter) -> Result<Vec<String>, ConversionError> {
+        let audiences = match filter.config.get("audiences") {
+            None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+            Some(value) => value.as_array().ok_or_else(|| ConversionError::ValidationFailed {
+                reason: format!("audiences for filter '{}' must be an array of strings", filter.name)
+            })?,
+        };
+
+        audiences
+            .iter()
+            .map(|audience| match audience.as_str() {
+                Some(audience) if !audience.trim().is_empty() => Ok(audience.to_string()),
+                _ => Err(ConversionError::ValidationFailed {
+                    reason: format!("audiences for filter '{}' must contain non-empty strings", filter.name)
+                }),
+            })
+            .collect()
+    }
+
+    /// Parse the optional `claim_to_headers` object mapping claim names (dot-separated for
+    /// nested claims) to the request headers their values are copied into
+    fn parse_claim_to_headers(filter: &InternalHttpFilter) -> Result<Vec<JwtClaimToHeader>, ConversionError> {
+        let mappings = match filter.config.get("claim_to_headers") {
+            None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+            Some(value) => value.as_object().ok_or_else(|| ConversionError::ValidationFailed {
+                reason: format!("claim_to_headers for filter '{}' must be an object of claim name to header name", filter.name)
+            })?,
+        };
+
+        let mut claim_to_headers: Vec<JwtClaimToHeader> = Vec::with_capacity(mappings.len());
+        for (claim_name, header_name) in mappings {
+            let valid_claim = !claim_name.is_empty()
+                && claim_name.len() <= 100
+                && claim_name.split('.').all(|segment| {
+                    !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                });
+            if !valid_claim {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!("claim_to_headers for filter '{}' has invalid claim name '{}'", filter.name, claim_name)
+                });
+            }
+
+            let header_name = header_name.as_str().ok_or_else(|| ConversionError::ValidationFailed {
+                reason: format!("claim_to_headers header for claim '{}' in filter '{}' must be a string", claim_name, filter.name)
+            })?;
+            crate::validation::security::Validator::validate_http_header_name(header_name)
+                .map_err(ConversionError::from)?;
+            if claim_to_headers.iter().any(|existing| existing.header_name.eq_ignore_ascii_case(header_name)) {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!(
+                        "claim_to_headers for filter '{}' maps more than one claim to header '{}'",
+                        filter.name, header_name
+                    )
+                });
+            }
+
+            claim_to_headers.push(JwtClaimToHeader {
+                header_name: header_name.to_string(),
+                claim_name: claim_name.clone(),
+            });
+        }
+        Ok(claim_to_headers)
+    }
+}
+
 impl FilterStrategy for AuthenticationStrategy {
     fn filter_type(&self) -> &'static str {
         "authentication"
@@ -18,25 +87,32 @@ impl FilterStrategy for AuthenticationStrategy {
 
     fn validate(&self, filter: &InternalHttpFilter) -> Result<(), ConversionError> {
         // Validate JWT secret
-        let jwt_secret = filter.config.get("jwt_secret")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ConversionError::ValidationFailed {
-                reason: format!("JWT secret for filter '{}' is missing", filter.name)
-            })?;
+        let jwt_secret = required_config_field(filter, "jwt_secret", "a string", |v| v.as_str())?;
 
         crate::validation::security::Validator::validate_jwt_secret(jwt_secret)
             .map_err(ConversionError::from)?;
 
         // Validate JWT issuer
-        let jwt_issuer = filter.config.get("jwt_issuer")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ConversionError::ValidationFailed {
-                reason: format!("JWT issuer for filter '{}' is missing", filter.name)
-            })?;
+        let jwt_issuer = required_config_field(filter, "jwt_issuer", "a string", |v| v.as_str())?;
 
         crate::validation::security::Validator::validate_length(jwt_issuer, "jwt_issuer", Some(1), Some(100))
             .map_err(ConversionError::from)?;
 
+        // Validate optional audiences
+        Self::parse_audiences(filter)?;
+
+        // Validate optional payload forwarding header
+        if let Some(header) = filter.config.get("forward_payload_header") {
+            let header = header.as_str().ok_or_else(|| ConversionError::ValidationFailed {
+                reason: format!("forward_payload_header for filter '{}' must be a string", filter.name)
+            })?;
+            crate::validation::security::Validator::validate_http_header_name(header)
+                .map_err(ConversionError::from)?;
+        }
+
+        // Validate optional claim-to-header mapping
+        Self::parse_claim_to_headers(filter)?;
+
         Ok(())
     }
 
@@ -52,11 +128,22 @@ impl FilterStrategy for AuthenticationStrategy {
             .and_then(|v| v.as_str())
             .unwrap_or("https://default-issuer.com");
 
+        let audiences = Self::parse_audiences(filter)?;
+
+        let forward_payload_header = filter.config.get("forward_payload_header")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+
+        let claim_to_headers = Self::parse_claim_to_headers(filter)?;
+
         let provider_name = format!("{}_provider", filter.name);
 
         // Create JWT provider (following existing pattern)
         let jwt_provider = JwtProvider {
             issuer: jwt_issuer.to_string(),
+            audiences,
+            forward_payload_header: forward_payload_header.to_string(),
+            claim_to_headers,
             jwt_cache_config: Some(envoy_types::pb::envoy::extensions::filters::http::jwt_authn::v3::JwtCacheConfig {
                 jwt_cache_size: 1000,
                 ..Default::default()
@@ -130,6 +217,11 @@ impl FilterStrategy for AuthenticationStrategy {
     fn description(&self) -> &'static str {
         "JWT authentication filter for validating JSON Web Tokens"
     }
+
+    fn requires_before(&self) -> &'static [&'static str] {
+        // Rate limiting by user needs the authenticated identity
+        &["rate_limit"]
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +242,7 @@ mod tests {
                 "jwt_secret": "this-is-a-very-secure-jwt-key-with-sufficient-length-for-validation",
                 "jwt_issuer": "https://auth.example.com"
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&valid_filter).is_ok());
@@ -162,6 +255,7 @@ mod tests {
             config: json!({
                 "jwt_issuer": "https://auth.example.com"
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter).is_err());
@@ -179,6 +273,7 @@ mod tests {
                 "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
                 "jwt_issuer": "https://auth.example.com"
             }),
+            ..Default::default()
         };
         
         let result = strategy.convert(&filter);
@@ -191,6 +286,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_authentication_audiences() {
+        let strategy = AuthenticationStrategy;
+
+        let filter = InternalHttpFilter {
+            name: "test-auth".to_string(),
+            filter_type: "authentication".to_string(),
+            enabled: true,
+            config: json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com",
+                "audiences": ["api.example.com", "admin.example.com"]
+            }),
+            ..Default::default()
+        };
+
+        assert!(strategy.validate(&filter).is_ok());
+
+        let Ok(ConfigType::TypedConfig(any)) = strategy.convert(&filter) else {
+            panic!("Expected TypedConfig result");
+        };
+        let jwt_auth = <JwtAuthentication as prost::Message>::decode(any.value.as_slice()).unwrap();
+        let provider = jwt_auth.providers.get("test-auth_provider").unwrap();
+        assert_eq!(provider.audiences, vec!["api.example.com", "admin.example.com"]);
+
+        // Empty audience entries are rejected
+        let invalid_filter = InternalHttpFilter {
+            name: "test-invalid".to_string(),
+            filter_type: "authentication".to_string(),
+            enabled: true,
+            config: json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com",
+                "audiences": ["api.example.com", ""]
+            }),
+            ..Default::default()
+        };
+
+        assert!(strategy.validate(&invalid_filter).is_err());
+    }
+
+    #[test]
+    fn test_authentication_forward_payload_header() {
+        let strategy = AuthenticationStrategy;
+
+        let filter = InternalHttpFilter {
+            name: "test-auth".to_string(),
+            filter_type: "authentication".to_string(),
+            enabled: true,
+            config: json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com",
+                "forward_payload_header": "x-jwt-payload"
+            }),
+            ..Default::default()
+        };
+
+        assert!(strategy.validate(&filter).is_ok());
+
+        let Ok(ConfigType::TypedConfig(any)) = strategy.convert(&filter) else {
+            panic!("Expected TypedConfig result");
+        };
+        let jwt_auth = <JwtAuthentication as prost::Message>::decode(any.value.as_slice()).unwrap();
+        let provider = jwt_auth.providers.get("test-auth_provider").unwrap();
+        assert_eq!(provider.forward_payload_header, "x-jwt-payload");
+
+        // Invalid header names are rejected
+        let invalid_filter = InternalHttpFilter {
+            name: "test-invalid".to_string(),
+            filter_type: "authentication".to_string(),
+            enabled: true,
+            config: json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com",
+                "forward_payload_header": "bad header:"
+            }),
+            ..Default::default()
+        };
+
+        assert!(strategy.validate(&invalid_filter).is_err());
+    }
+
     #[test]
     fn test_authentication_weak_secret_validation() {
         let strategy = AuthenticationStrategy;
@@ -204,9 +381,48 @@ mod tests {
                 "jwt_secret": "my-secret-key", // Contains "secret" - should be rejected
                 "jwt_issuer": "https://auth.example.com"
             }),
+            ..Default::default()
         };
         
         let result = strategy.validate(&invalid_filter);
         assert!(result.is_err(), "Should reject weak JWT secret");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_authentication_claim_to_headers() {
+        let strategy = AuthenticationStrategy;
+        let filter_with = |claim_to_headers: serde_json::Value| InternalHttpFilter {
+            name: "test-auth".to_string(),
+            filter_type: "authentication".to_string(),
+            enabled: true,
+            config: json!({
+                "jwt_secret": "my-super-secure-jwt-key-with-sufficient-length-for-validation",
+                "jwt_issuer": "https://auth.example.com",
+                "claim_to_headers": claim_to_headers
+            }),
+            ..Default::default()
+        };
+
+        let filter = filter_with(json!({"role": "x-user-role", "org.id": "x-org-id"}));
+        assert!(strategy.validate(&filter).is_ok());
+
+        let Ok(ConfigType::TypedConfig(any)) = strategy.convert(&filter) else {
+            panic!("Expected TypedConfig result");
+        };
+        let jwt_auth = <JwtAuthentication as prost::Message>::decode(any.value.as_slice()).unwrap();
+        let provider = jwt_auth.providers.get("test-auth_provider").unwrap();
+        let mut mappings: Vec<(&str, &str)> = provider
+            .claim_to_headers
+            .iter()
+            .map(|mapping| (mapping.claim_name.as_str(), mapping.header_name.as_str()))
+            .collect();
+        mappings.sort();
+        assert_eq!(mappings, vec![("org.id", "x-org-id"), ("role", "x-user-role")]);
+
+        // Invalid header names, claim names and duplicate headers are rejected
+        assert!(strategy.validate(&filter_with(json!({"role": "bad header:"}))).is_err());
+        assert!(strategy.validate(&filter_with(json!({"role..name": "x-role"}))).is_err());
+        assert!(strategy.validate(&filter_with(json!({"role": "x-user", "sub": "X-User"}))).is_err());
+        assert!(strategy.validate(&filter_with(json!(["role"]))).is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use crate::storage::HttpFilter as InternalHttpFilter;
 use crate::xds::conversion::ConversionError;
-use crate::xds::filters::FilterStrategy;
+use crate::xds::filters::{optional_config_field, FilterStrategy};
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
-use envoy_types::pb::envoy::extensions::filters::http::cors::v3::Cors;
-use envoy_types::pb::google::protobuf::Any;
+use envoy_types::pb::envoy::extensions::filters::http::cors::v3::{Cors, CorsPolicy};
+use envoy_types::pb::envoy::r#type::matcher::v3::{string_matcher, RegexMatcher, StringMatcher};
+use envoy_types::pb::google::protobuf::{Any, BoolValue};
 use tracing::info;
 
 /// Strategy for converting CORS filters to Envoy CORS
@@ -58,46 +59,43 @@ impl FilterStrategy for CorsStrategy {
             }
         }
 
-        Ok(())
-    }
+        // Validate allowed_headers if present
+        if let Some(headers) = optional_config_field(filter, "allowed_headers", "an array", |v| v.as_array())? {
+            for (i, header) in headers.iter().enumerate() {
+                if !header.is_string() {
+                    return Err(ConversionError::ValidationFailed {
+                        reason: format!("CORS allowed_headers[{}] must be a string for filter '{}'", i, filter.name)
+                    });
+                }
+            }
+        }
 
-    fn convert(&self, filter: &InternalHttpFilter) -> Result<ConfigType, ConversionError> {
-        info!("Converting CORS filter '{}' to Envoy CORS", filter.name);
+        // Validate expose_headers if present
+        if let Some(headers) = optional_config_field(filter, "expose_headers", "an array", |v| v.as_array())? {
+            for (i, header) in headers.iter().enumerate() {
+                let header_str = header.as_str()
+                    .ok_or_else(|| ConversionError::ValidationFailed {
+                        reason: format!("CORS expose_headers[{}] must be a string for filter '{}'", i, filter.name)
+                    })?;
+                crate::validation::security::Validator::validate_http_header_name(header_str)
+                    .map_err(ConversionError::from)?;
+            }
+        }
 
-        // Extract CORS config from our JSON
-        let allowed_origins = filter.config.get("allowed_origins")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_else(|| vec!["*".to_string()]);
+        // Validate allow_credentials if present
+        optional_config_field(filter, "allow_credentials", "a boolean", |v| v.as_bool())?;
 
-        let allowed_methods = filter.config.get("allowed_methods")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_else(|| vec!["GET".to_string(), "POST".to_string()]);
+        // Validate max_age (seconds) if present
+        optional_config_field(filter, "max_age", "a non-negative integer", |v| v.as_u64())?;
 
-        let allowed_headers = filter.config.get("allowed_headers")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_else(|| vec!["Content-Type".to_string(), "Authorization".to_string()]);
+        Ok(())
+    }
 
-        let allow_credentials = filter.config.get("allow_credentials")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+    fn convert(&self, filter: &InternalHttpFilter) -> Result<ConfigType, ConversionError> {
+        info!("Converting CORS filter '{}' to Envoy CORS", filter.name);
 
-        // Create basic CORS configuration (following existing simplified pattern)
-        // TODO: In future versions, we can expand this to use the extracted config values
+        // Envoy's filter-level CORS config is empty; the policy built by `cors_policy`
+        // is attached to the virtual host as per-filter config during route conversion
         let cors_config = Cors::default();
 
         // Serialize to Any proto
@@ -122,6 +120,57 @@ impl FilterStrategy for CorsStrategy {
     }
 }
 
+/// Build the CORS policy Envoy enforces for a cors filter's config
+pub fn cors_policy(filter: &InternalHttpFilter) -> CorsPolicy {
+    let string_list = |field: &str| {
+        filter.config.get(field)
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect::<Vec<String>>()
+            })
+    };
+
+    let allowed_origins = string_list("allowed_origins").unwrap_or_else(|| vec!["*".to_string()]);
+    let allowed_methods = string_list("allowed_methods")
+        .unwrap_or_else(|| vec!["GET".to_string(), "POST".to_string()]);
+    let allowed_headers = string_list("allowed_headers")
+        .unwrap_or_else(|| vec!["Content-Type".to_string(), "Authorization".to_string()]);
+    let expose_headers = string_list("expose_headers").unwrap_or_default();
+    let allow_credentials = filter.config.get("allow_credentials")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let max_age = filter.config.get("max_age").and_then(|v| v.as_u64());
+
+    CorsPolicy {
+        allow_origin_string_match: allowed_origins.into_iter().map(origin_matcher).collect(),
+        allow_methods: allowed_methods.join(","),
+        allow_headers: allowed_headers.join(","),
+        expose_headers: expose_headers.join(","),
+        max_age: max_age.map(|seconds| seconds.to_string()).unwrap_or_default(),
+        allow_credentials: Some(BoolValue { value: allow_credentials }),
+        ..Default::default()
+    }
+}
+
+/// `*` allows any origin; anything else must match exactly
+fn origin_matcher(origin: String) -> StringMatcher {
+    let match_pattern = if origin == "*" {
+        string_matcher::MatchPattern::SafeRegex(RegexMatcher {
+            regex: ".*".to_string(),
+            ..Default::default()
+        })
+    } else {
+        string_matcher::MatchPattern::Exact(origin)
+    };
+
+    StringMatcher {
+        match_pattern: Some(match_pattern),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,6 +189,7 @@ mod tests {
                 "allowed_origins": ["https://example.com"],
                 "allowed_methods": ["GET", "POST"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&valid_filter).is_ok());
@@ -152,6 +202,7 @@ mod tests {
             config: json!({
                 "allowed_methods": ["INVALID"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter).is_err());
@@ -170,6 +221,7 @@ mod tests {
                 "allowed_methods": ["GET", "POST"],
                 "allow_credentials": true
             }),
+            ..Default::default()
         };
         
         let result = strategy.convert(&filter);
@@ -192,9 +244,50 @@ mod tests {
             filter_type: "cors".to_string(),
             enabled: true,
             config: json!({}),
+            ..Default::default()
         };
         
         let result = strategy.convert(&filter);
         assert!(result.is_ok(), "Should handle empty config with defaults");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cors_policy_includes_expose_headers_and_max_age() {
+        let filter = InternalHttpFilter {
+            name: "api-cors".to_string(),
+            filter_type: "cors".to_string(),
+            enabled: true,
+            config: json!({
+                "allowed_origins": ["https://example.com"],
+                "allowed_methods": ["GET", "POST"],
+                "expose_headers": ["X-Request-Id", "X-RateLimit-Remaining"],
+                "max_age": 600
+            }),
+            ..Default::default()
+        };
+
+        assert!(CorsStrategy.validate(&filter).is_ok());
+
+        let policy = cors_policy(&filter);
+        assert_eq!(policy.expose_headers, "X-Request-Id,X-RateLimit-Remaining");
+        assert_eq!(policy.max_age, "600");
+        assert_eq!(policy.allow_methods, "GET,POST");
+        assert_eq!(
+            policy.allow_origin_string_match[0].match_pattern,
+            Some(string_matcher::MatchPattern::Exact("https://example.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_cors_negative_max_age_rejected() {
+        let filter = InternalHttpFilter {
+            name: "api-cors".to_string(),
+            filter_type: "cors".to_string(),
+            enabled: true,
+            config: json!({"max_age": -1}),
+            ..Default::default()
+        };
+
+        assert!(CorsStrategy.validate(&filter).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::storage::HttpFilter as InternalHttpFilter;
 use crate::xds::conversion::ConversionError;
-use crate::xds::filters::FilterStrategy;
+use crate::xds::filters::{optional_config_field, FilterStrategy};
 use crate::validation::security::Validator;
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
 use envoy_types::pb::envoy::extensions::filters::http::lua::v3::Lua;
@@ -14,80 +14,71 @@ use tracing::info;
 /// Lua code that safely manipulates request/response headers.
 pub struct HeaderManipulationStrategy;
 
-impl FilterStrategy for HeaderManipulationStrategy {
-    fn filter_type(&self) -> &'static str {
-        "header_manipulation"
-    }
+impl HeaderManipulationStrategy {
+    /// Validate a `*_headers_to_add` list: every entry must be `{"header": {"key": str, "value": str}}`
+    fn validate_headers_to_add(filter: &InternalHttpFilter, field: &str, direction: &str) -> Result<(), ConversionError> {
+        let Some(headers) = optional_config_field(filter, field, "an array", |v| v.as_array())? else {
+            return Ok(());
+        };
 
-    fn validate(&self, filter: &InternalHttpFilter) -> Result<(), ConversionError> {
-        // Validate request headers to add
-        if let Some(headers_to_add) = filter.config.get("request_headers_to_add").and_then(|v| v.as_array()) {
-            for header in headers_to_add {
-                if let Some(header_obj) = header.get("header") {
-                    if let (Some(key), Some(value)) = (
-                        header_obj.get("key").and_then(|k| k.as_str()),
-                        header_obj.get("value").and_then(|v| v.as_str())
-                    ) {
-                        Validator::validate_http_header_name(key)
-                            .map_err(|e| ConversionError::ValidationFailed {
-                                reason: format!("Invalid request header name in filter '{}': {}", filter.name, e)
-                            })?;
-                        Validator::validate_http_header_value(value)
-                            .map_err(|e| ConversionError::ValidationFailed {
-                                reason: format!("Invalid request header value in filter '{}': {}", filter.name, e)
-                            })?;
-                    }
-                }
-            }
-        }
+        for (i, entry) in headers.iter().enumerate() {
+            let header_obj = entry.get("header").filter(|h| h.is_object());
+            let key = header_obj.and_then(|h| h.get("key")).and_then(|k| k.as_str());
+            let value = header_obj.and_then(|h| h.get("value")).and_then(|v| v.as_str());
+            let (Some(key), Some(value)) = (key, value) else {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!(
+                        "Field '{}[{}]' in filter '{}' must be an object like {{\"header\": {{\"key\": string, \"value\": string}}}}",
+                        field, i, filter.name
+                    )
+                });
+            };
 
-        // Validate request headers to remove
-        if let Some(headers_to_remove) = filter.config.get("request_headers_to_remove").and_then(|v| v.as_array()) {
-            for header in headers_to_remove {
-                if let Some(header_name) = header.as_str() {
-                    Validator::validate_http_header_name(header_name)
-                        .map_err(|e| ConversionError::ValidationFailed {
-                            reason: format!("Invalid request header name to remove in filter '{}': {}", filter.name, e)
-                        })?;
-                }
-            }
+            Validator::validate_http_header_name(key)
+                .map_err(|e| ConversionError::ValidationFailed {
+                    reason: format!("Invalid {} header name in filter '{}': {}", direction, filter.name, e)
+                })?;
+            Validator::validate_http_header_value(value)
+                .map_err(|e| ConversionError::ValidationFailed {
+                    reason: format!("Invalid {} header value in filter '{}': {}", direction, filter.name, e)
+                })?;
         }
 
-        // Validate response headers to add
-        if let Some(headers_to_add) = filter.config.get("response_headers_to_add").and_then(|v| v.as_array()) {
-            for header in headers_to_add {
-                if let Some(header_obj) = header.get("header") {
-                    if let (Some(key), Some(value)) = (
-                        header_obj.get("key").and_then(|k| k.as_str()),
-                        header_obj.get("value").and_then(|v| v.as_str())
-                    ) {
-                        Validator::validate_http_header_name(key)
-                            .map_err(|e| ConversionError::ValidationFailed {
-                                reason: format!("Invalid response header name in filter '{}': {}", filter.name, e)
-                            })?;
-                        Validator::validate_http_header_value(value)
-                            .map_err(|e| ConversionError::ValidationFailed {
-                                reason: format!("Invalid response header value in filter '{}': {}", filter.name, e)
-                            })?;
-                    }
-                }
-            }
-        }
+        Ok(())
+    }
 
-        // Validate response headers to remove
-        if let Some(headers_to_remove) = filter.config.get("response_headers_to_remove").and_then(|v| v.as_array()) {
-            for header in headers_to_remove {
-                if let Some(header_name) = header.as_str() {
-                    Validator::validate_http_header_name(header_name)
-                        .map_err(|e| ConversionError::ValidationFailed {
-                            reason: format!("Invalid response header name to remove in filter '{}': {}", filter.name, e)
-                        })?;
-                }
-            }
+    /// Validate a `*_headers_to_remove` list: every entry must be a header name string
+    fn validate_headers_to_remove(filter: &InternalHttpFilter, field: &str, direction: &str) -> Result<(), ConversionError> {
+        let Some(headers) = optional_config_field(filter, field, "an array", |v| v.as_array())? else {
+            return Ok(());
+        };
+
+        for (i, header) in headers.iter().enumerate() {
+            let header_name = header.as_str().ok_or_else(|| ConversionError::ValidationFailed {
+                reason: format!("Field '{}[{}]' in filter '{}' must be a string", field, i, filter.name)
+            })?;
+            Validator::validate_http_header_name(header_name)
+                .map_err(|e| ConversionError::ValidationFailed {
+                    reason: format!("Invalid {} header name to remove in filter '{}': {}", direction, filter.name, e)
+                })?;
         }
 
         Ok(())
     }
+}
+
+impl FilterStrategy for HeaderManipulationStrategy {
+    fn filter_type(&self) -> &'static str {
+        "header_manipulation"
+    }
+
+    fn validate(&self, filter: &InternalHttpFilter) -> Result<(), ConversionError> {
+        Self::validate_headers_to_add(filter, "request_headers_to_add", "request")?;
+        Self::validate_headers_to_remove(filter, "request_headers_to_remove", "request")?;
+        Self::validate_headers_to_add(filter, "response_headers_to_add", "response")?;
+        Self::validate_headers_to_remove(filter, "response_headers_to_remove", "response")?;
+        Ok(())
+    }
 
     fn convert(&self, filter: &InternalHttpFilter) -> Result<ConfigType, ConversionError> {
         info!("Converting header_manipulation filter '{}' to Envoy Lua script", filter.name);
@@ -266,6 +257,7 @@ mod tests {
                 ],
                 "request_headers_to_remove": ["X-Remove-This"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&valid_filter).is_ok());
@@ -285,11 +277,37 @@ mod tests {
                     }
                 ]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter).is_err());
     }
 
+    #[test]
+    fn test_header_manipulation_rejects_type_mismatched_fields() {
+        let strategy = HeaderManipulationStrategy;
+
+        let not_an_array = InternalHttpFilter {
+            name: "test-not-array".to_string(),
+            filter_type: "header_manipulation".to_string(),
+            config: json!({"request_headers_to_remove": "X-Remove-This"}),
+            ..Default::default()
+        };
+        let message = strategy.validate(&not_an_array).unwrap_err().to_string();
+        assert!(message.contains("'request_headers_to_remove'"));
+
+        let numeric_value = InternalHttpFilter {
+            name: "test-numeric-value".to_string(),
+            filter_type: "header_manipulation".to_string(),
+            config: json!({
+                "response_headers_to_add": [{"header": {"key": "X-Retry-After", "value": 30}}]
+            }),
+            ..Default::default()
+        };
+        let message = strategy.validate(&numeric_value).unwrap_err().to_string();
+        assert!(message.contains("'response_headers_to_add[0]'"));
+    }
+
     #[test]
     fn test_header_manipulation_conversion() {
         let strategy = HeaderManipulationStrategy;
@@ -316,6 +334,7 @@ mod tests {
                     }
                 ]
             }),
+            ..Default::default()
         };
         
         let result = strategy.convert(&filter);
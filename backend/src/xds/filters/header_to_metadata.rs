@@ -0,0 +1,181 @@
+use crate::storage::HttpFilter as InternalHttpFilter;
+use crate::validation::security::Validator;
+use crate::xds::conversion::ConversionError;
+use crate::xds::filters::{required_config_field, FilterStrategy};
+use envoy_types::pb::envoy::extensions::filters::http::header_to_metadata::v3::{
+    config::{KeyValuePair, Rule},
+    Config,
+};
+use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
+use envoy_types::pb::google::protobuf::Any;
+use prost::Message;
+use tracing::info;
+
+/// Strategy for converting header_to_metadata filters to Envoy's header-to-metadata filter
+///
+/// Each rule copies a request header into dynamic metadata so that routing and
+/// load balancing (e.g. subset selection) can act on it:
+/// `{"request_rules": [{"header": "x-tenant", "metadata_key": "tenant", "metadata_namespace": "envoy.lb", "remove": false}]}`
+pub struct HeaderToMetadataStrategy;
+
+impl HeaderToMetadataStrategy {
+    /// Metadata keys and namespaces are dotted identifiers like `envoy.lb`
+    fn validate_metadata_name(filter: &InternalHttpFilter, field: &str, value: &str) -> Result<(), ConversionError> {
+        Validator::validate_length(value, field, Some(1), Some(100))
+            .and_then(|_| Validator::validate_charset(value, field, &['-', '_', '.']))
+            .map_err(|e| ConversionError::ValidationFailed {
+                reason: format!("Invalid {} in filter '{}': {}", field, filter.name, e),
+            })
+    }
+}
+
+impl FilterStrategy for HeaderToMetadataStrategy {
+    fn filter_type(&self) -> &'static str {
+        "header_to_metadata"
+    }
+
+    fn validate(&self, filter: &InternalHttpFilter) -> Result<(), ConversionError> {
+        let rules = required_config_field(filter, "request_rules", "a non-empty array", |v| {
+            v.as_array().filter(|rules| !rules.is_empty())
+        })?;
+
+        for (i, rule) in rules.iter().enumerate() {
+            let header = rule.get("header").and_then(|h| h.as_str());
+            let metadata_key = rule.get("metadata_key").and_then(|k| k.as_str());
+            let (Some(header), Some(metadata_key)) = (header, metadata_key) else {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!(
+                        "Field 'request_rules[{}]' in filter '{}' must be an object like {{\"header\": string, \"metadata_key\": string}}",
+                        i, filter.name
+                    ),
+                });
+            };
+
+            Validator::validate_http_header_name(header).map_err(|e| ConversionError::ValidationFailed {
+                reason: format!("Invalid header name in filter '{}': {}", filter.name, e),
+            })?;
+            Self::validate_metadata_name(filter, "metadata_key", metadata_key)?;
+
+            if let Some(namespace) = rule.get("metadata_namespace") {
+                let namespace = namespace.as_str().ok_or_else(|| ConversionError::ValidationFailed {
+                    reason: format!("Field 'request_rules[{}].metadata_namespace' in filter '{}' must be a string", i, filter.name),
+                })?;
+                Self::validate_metadata_name(filter, "metadata_namespace", namespace)?;
+            }
+
+            if rule.get("remove").is_some_and(|remove| !remove.is_boolean()) {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!("Field 'request_rules[{}].remove' in filter '{}' must be a boolean", i, filter.name),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn convert(&self, filter: &InternalHttpFilter) -> Result<ConfigType, ConversionError> {
+        info!("Converting header_to_metadata filter '{}' to Envoy header-to-metadata", filter.name);
+
+        let request_rules = filter
+            .config
+            .get("request_rules")
+            .and_then(|v| v.as_array())
+            .map(|rules| {
+                rules
+                    .iter()
+                    .filter_map(|rule| {
+                        let header = rule.get("header")?.as_str()?;
+                        let metadata_key = rule.get("metadata_key")?.as_str()?;
+                        Some(Rule {
+                            header: header.to_string(),
+                            // An empty value copies the header value into the metadata key
+                            on_header_present: Some(KeyValuePair {
+                                metadata_namespace: rule
+                                    .get("metadata_namespace")
+                                    .and_then(|n| n.as_str())
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                key: metadata_key.to_string(),
+                                ..Default::default()
+                            }),
+                            remove: rule.get("remove").and_then(|r| r.as_bool()).unwrap_or(false),
+                            ..Default::default()
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let config = Config {
+            request_rules,
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        config.encode(&mut buf).map_err(|e| ConversionError::ProtobufEncoding {
+            resource_type: "HeaderToMetadata".to_string(),
+            source: e,
+        })?;
+
+        Ok(ConfigType::TypedConfig(Any {
+            type_url: "type.googleapis.com/envoy.extensions.filters.http.header_to_metadata.v3.Config".to_string(),
+            value: buf,
+        }))
+    }
+
+    fn description(&self) -> &'static str {
+        "Copies request headers into dynamic metadata for metadata-based routing and load balancing"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_header_mapped_to_metadata_key() {
+        let filter = InternalHttpFilter::new(
+            "tenant-metadata".to_string(),
+            "header_to_metadata".to_string(),
+            json!({
+                "request_rules": [
+                    {"header": "x-tenant-id", "metadata_key": "tenant", "metadata_namespace": "envoy.lb", "remove": true}
+                ]
+            }),
+        );
+
+        let strategy = HeaderToMetadataStrategy;
+        strategy.validate(&filter).unwrap();
+
+        let typed_config = match strategy.convert(&filter).unwrap() {
+            ConfigType::TypedConfig(any) => any,
+            _ => panic!("Expected typed config"),
+        };
+        assert_eq!(
+            typed_config.type_url,
+            "type.googleapis.com/envoy.extensions.filters.http.header_to_metadata.v3.Config"
+        );
+
+        let config = Config::decode(&typed_config.value[..]).unwrap();
+        let rule = &config.request_rules[0];
+        assert_eq!(rule.header, "x-tenant-id");
+        assert!(rule.remove);
+        let on_present = rule.on_header_present.as_ref().unwrap();
+        assert_eq!(on_present.key, "tenant");
+        assert_eq!(on_present.metadata_namespace, "envoy.lb");
+    }
+
+    #[test]
+    fn test_invalid_metadata_key_rejected() {
+        let filter = InternalHttpFilter::new(
+            "bad-metadata".to_string(),
+            "header_to_metadata".to_string(),
+            json!({
+                "request_rules": [{"header": "x-tenant-id", "metadata_key": "tenant key"}]
+            }),
+        );
+
+        assert!(HeaderToMetadataStrategy.validate(&filter).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::config::AppConfig;
 use crate::storage::HttpFilter as InternalHttpFilter;
 use crate::xds::conversion::ConversionError;
+use std::collections::HashMap;
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
 
 pub mod rate_limit;
@@ -8,12 +9,16 @@ pub mod cors;
 pub mod authentication;
 pub mod header_manipulation;
 pub mod request_validation;
+pub mod header_to_metadata;
+pub mod render;
 
 pub use rate_limit::RateLimitStrategy;
 pub use cors::CorsStrategy;
 pub use authentication::AuthenticationStrategy;
 pub use header_manipulation::HeaderManipulationStrategy;
 pub use request_validation::RequestValidationStrategy;
+pub use header_to_metadata::HeaderToMetadataStrategy;
+pub use render::render_typed_config;
 
 /// Strategy pattern trait for converting different HTTP filter types to Envoy protobuf
 /// 
@@ -36,6 +41,56 @@ pub trait FilterStrategy {
     fn supports(&self, filter_type: &str) -> bool {
         self.filter_type() == filter_type
     }
+
+    /// Filter types that must run after this filter in the HTTP filter chain
+    fn requires_before(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Look up an optional filter config field, rejecting values of the wrong JSON type
+/// so that conversion never silently falls back to a default
+pub fn optional_config_field<'a, T>(
+    filter: &'a InternalHttpFilter,
+    field: &str,
+    expected: &str,
+    extract: impl FnOnce(&'a serde_json::Value) -> Option<T>,
+) -> Result<Option<T>, ConversionError> {
+    match filter.config.get(field) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => extract(value).map(Some).ok_or_else(|| ConversionError::ValidationFailed {
+            reason: format!(
+                "Field '{}' in filter '{}' must be {}, got {}",
+                field,
+                filter.name,
+                expected,
+                json_type_name(value)
+            ),
+        }),
+    }
+}
+
+/// Look up a required filter config field, rejecting missing or type-mismatched values
+pub fn required_config_field<'a, T>(
+    filter: &'a InternalHttpFilter,
+    field: &str,
+    expected: &str,
+    extract: impl FnOnce(&'a serde_json::Value) -> Option<T>,
+) -> Result<T, ConversionError> {
+    optional_config_field(filter, field, expected, extract)?.ok_or_else(|| ConversionError::ValidationFailed {
+        reason: format!("Field '{}' in filter '{}' is missing. Must be {}", field, filter.name, expected),
+    })
+}
+
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
 }
 
 /// Registry of all available filter strategies
@@ -62,6 +117,7 @@ impl FilterStrategyRegistry {
         self.register(Box::new(AuthenticationStrategy));
         self.register(Box::new(HeaderManipulationStrategy));
         self.register(Box::new(RequestValidationStrategy::new(app_config.clone())));
+        self.register(Box::new(HeaderToMetadataStrategy));
     }
     
     /// Register a new filter strategy
@@ -129,6 +185,131 @@ impl FilterStrategyRegistry {
     }
 }
 
+impl FilterStrategyRegistry {
+    /// First `(earlier, later)` pair of types whose `requires_before` hint `default_order` lists the other way round
+    pub fn first_order_violation(&self, default_order: &[String]) -> Option<(&'static str, &'static str)> {
+        let position = |filter_type: &str| default_order.iter().position(|t| t == filter_type);
+        default_order.iter().enumerate().find_map(|(index, filter_type)| {
+            let strategy = self.get_strategy(filter_type)?;
+            strategy
+                .requires_before()
+                .iter()
+                .find(|later| position(later).is_some_and(|later_index| later_index < index))
+                .map(|&later| (strategy.filter_type(), later))
+        })
+    }
+
+    /// Order filter types so every `requires_before` hint is respected
+    /// Types without a constraint between them keep their relative `default_order` position
+    pub fn ordered_filter_types(&self, default_order: &[String]) -> Result<Vec<String>, ConversionError> {
+        let mut in_degree: HashMap<&str, usize> = default_order.iter().map(|t| (t.as_str(), 0)).collect();
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for filter_type in default_order {
+            let Some(strategy) = self.get_strategy(filter_type) else {
+                continue;
+            };
+
+            // Hints referencing filters outside the configured order are ignored
+            for &later in strategy.requires_before() {
+                if let Some(degree) = in_degree.get_mut(later) {
+                    *degree += 1;
+                    edges.entry(filter_type.as_str()).or_default().push(later);
+                }
+            }
+        }
+
+        let mut remaining: Vec<&str> = default_order.iter().map(String::as_str).collect();
+        let mut ordered = Vec::with_capacity(remaining.len());
+
+        while !remaining.is_empty() {
+            let Some(pos) = remaining.iter().position(|t| in_degree[t] == 0) else {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!("HTTP filter ordering hints form a cycle between: {}", remaining.join(", ")),
+                });
+            };
+
+            let filter_type = remaining.remove(pos);
+            for later in edges.get(filter_type).into_iter().flatten() {
+                if let Some(degree) = in_degree.get_mut(later) {
+                    *degree -= 1;
+                }
+            }
+            ordered.push(filter_type.to_string());
+        }
+
+        Ok(ordered)
+    }
+}
+
 // Note: Default implementation is not provided because FilterStrategyRegistry
 // requires an AppConfig to properly configure strategies like RequestValidationStrategy
-// Users should call FilterStrategyRegistry::new(app_config) explicitly
\ No newline at end of file
+// Users should call FilterStrategyRegistry::new(app_config) explicitly
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct HintedStrategy {
+        filter_type: &'static str,
+        before: &'static [&'static str],
+    }
+
+    impl FilterStrategy for HintedStrategy {
+        fn filter_type(&self) -> &'static str {
+            self.filter_type
+        }
+
+        fn validate(&self, _filter: &InternalHttpFilter) -> Result<(), ConversionError> {
+            Ok(())
+        }
+
+        fn convert(&self, _filter: &InternalHttpFilter) -> Result<ConfigType, ConversionError> {
+            Err(ConversionError::ValidationFailed {
+                reason: "not used in ordering tests".to_string(),
+            })
+        }
+
+        fn description(&self) -> &'static str {
+            "Test strategy with ordering hints"
+        }
+
+        fn requires_before(&self) -> &'static [&'static str] {
+            self.before
+        }
+    }
+
+    #[test]
+    fn test_requires_before_overrides_default_order() {
+        let mut registry = FilterStrategyRegistry { strategies: Vec::new() };
+        registry.register(Box::new(HintedStrategy { filter_type: "first", before: &["second"] }));
+        registry.register(Box::new(HintedStrategy { filter_type: "second", before: &[] }));
+        registry.register(Box::new(HintedStrategy { filter_type: "other", before: &[] }));
+
+        // The hint wins over a configured order that lists the types the other way round
+        let default_order = vec!["second".to_string(), "other".to_string(), "first".to_string()];
+        let ordered = registry.ordered_filter_types(&default_order).unwrap();
+        assert_eq!(ordered, vec!["other", "first", "second"]);
+    }
+
+    #[test]
+    fn test_first_order_violation_reports_hinted_pair() {
+        let mut registry = FilterStrategyRegistry { strategies: Vec::new() };
+        registry.register(Box::new(HintedStrategy { filter_type: "first", before: &["second"] }));
+        registry.register(Box::new(HintedStrategy { filter_type: "second", before: &[] }));
+
+        let violating = vec!["second".to_string(), "first".to_string()];
+        assert_eq!(registry.first_order_violation(&violating), Some(("first", "second")));
+        assert_eq!(registry.first_order_violation(&["first".to_string(), "second".to_string()]), None);
+    }
+
+    #[test]
+    fn test_ordering_hint_cycle_rejected() {
+        let mut registry = FilterStrategyRegistry { strategies: Vec::new() };
+        registry.register(Box::new(HintedStrategy { filter_type: "first", before: &["second"] }));
+        registry.register(Box::new(HintedStrategy { filter_type: "second", before: &["first"] }));
+
+        let result = registry.ordered_filter_types(&["first".to_string(), "second".to_string()]);
+        assert!(matches!(result, Err(ConversionError::ValidationFailed { .. })));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::storage::HttpFilter as InternalHttpFilter;
 use crate::xds::conversion::ConversionError;
-use crate::xds::filters::FilterStrategy;
+use crate::xds::filters::{optional_config_field, required_config_field, FilterStrategy};
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
 use envoy_types::pb::envoy::extensions::filters::http::local_ratelimit::v3::LocalRateLimit;
 use envoy_types::pb::google::protobuf::Any;
@@ -16,11 +16,8 @@ impl FilterStrategy for RateLimitStrategy {
 
     fn validate(&self, filter: &InternalHttpFilter) -> Result<(), ConversionError> {
         // Validate requests_per_unit (required field)
-        let requests_per_unit = filter.config.get("requests_per_unit")
-            .and_then(|v| v.as_u64())
-            .ok_or_else(|| ConversionError::ValidationFailed {
-                reason: format!("Rate limit 'requests_per_unit' for filter '{}' is missing or invalid. Must be a positive integer.", filter.name)
-            })?;
+        let requests_per_unit =
+            required_config_field(filter, "requests_per_unit", "a positive integer", |v| v.as_u64())?;
 
         if requests_per_unit == 0 || requests_per_unit > 1000000 {
             return Err(ConversionError::ValidationFailed {
@@ -29,11 +26,12 @@ impl FilterStrategy for RateLimitStrategy {
         }
 
         // Validate time_unit (required field)
-        let time_unit = filter.config.get("time_unit")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ConversionError::ValidationFailed {
-                reason: format!("Rate limit 'time_unit' for filter '{}' is missing. Must be one of: second, minute, hour, day", filter.name)
-            })?;
+        let time_unit = required_config_field(
+            filter,
+            "time_unit",
+            "one of: second, minute, hour, day",
+            |v| v.as_str(),
+        )?;
 
         match time_unit {
             "second" | "minute" | "hour" | "day" => {},
@@ -44,12 +42,7 @@ impl FilterStrategy for RateLimitStrategy {
         }
 
         // Validate burst_size (optional field) and its relationship with requests_per_unit
-        if let Some(burst_size_value) = filter.config.get("burst_size") {
-            let burst_size = burst_size_value.as_u64()
-                .ok_or_else(|| ConversionError::ValidationFailed {
-                    reason: format!("Rate limit 'burst_size' for filter '{}' is invalid. Must be a positive integer.", filter.name)
-                })?;
-
+        if let Some(burst_size) = optional_config_field(filter, "burst_size", "a positive integer", |v| v.as_u64())? {
             if burst_size == 0 {
                 return Err(ConversionError::ValidationFailed {
                     reason: format!("Rate limit 'burst_size' must be greater than 0 for filter '{}', got: {}", filter.name, burst_size)
@@ -216,6 +209,7 @@ mod tests {
                 "requests_per_unit": 100,
                 "time_unit": "minute"
             }),
+            ..Default::default()
         };
         assert!(strategy.validate(&valid_filter).is_ok());
         
@@ -229,6 +223,7 @@ mod tests {
                 "time_unit": "minute",
                 "burst_size": 100
             }),
+            ..Default::default()
         };
         assert!(strategy.validate(&valid_burst_filter).is_ok());
         
@@ -238,6 +233,7 @@ mod tests {
             filter_type: "rate_limit".to_string(),
             enabled: true,
             config: json!({"time_unit": "minute"}),
+            ..Default::default()
         };
         assert!(strategy.validate(&missing_rate).is_err());
         
@@ -247,6 +243,7 @@ mod tests {
             filter_type: "rate_limit".to_string(),
             enabled: true,
             config: json!({"requests_per_unit": 100}),
+            ..Default::default()
         };
         assert!(strategy.validate(&missing_time_unit).is_err());
         
@@ -260,6 +257,7 @@ mod tests {
                 "time_unit": "minute",
                 "burst_size": 50
             }),
+            ..Default::default()
         };
         let result = strategy.validate(&invalid_burst);
         assert!(result.is_err());
@@ -274,6 +272,7 @@ mod tests {
                 "requests_per_unit": 100,
                 "unit": "minute"  // Should be "time_unit"
             }),
+            ..Default::default()
         };
         let result = strategy.validate(&wrong_field);
         assert!(result.is_err());
@@ -290,12 +289,60 @@ mod tests {
                 "time_unit": "minute",
                 "invalid_extra_field": "should_not_be_here"
             }),
+            ..Default::default()
         };
         let result = strategy.validate(&extra_field);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid field 'invalid_extra_field'"));
     }
 
+    #[test]
+    fn test_rate_limit_rejects_type_mismatched_fields() {
+        let strategy = RateLimitStrategy;
+
+        // A numeric string must not be coerced or replaced by the default
+        let string_rate = InternalHttpFilter {
+            name: "test-string-rate".to_string(),
+            filter_type: "rate_limit".to_string(),
+            config: json!({
+                "requests_per_unit": "100",
+                "time_unit": "minute"
+            }),
+            ..Default::default()
+        };
+        let err = strategy.validate(&string_rate).unwrap_err();
+        assert!(matches!(err, ConversionError::ValidationFailed { .. }));
+        let message = err.to_string();
+        assert!(message.contains("'requests_per_unit'"));
+        assert!(message.contains("got a string"));
+
+        let numeric_time_unit = InternalHttpFilter {
+            name: "test-numeric-unit".to_string(),
+            filter_type: "rate_limit".to_string(),
+            config: json!({
+                "requests_per_unit": 100,
+                "time_unit": 60
+            }),
+            ..Default::default()
+        };
+        let message = strategy.validate(&numeric_time_unit).unwrap_err().to_string();
+        assert!(message.contains("'time_unit'"));
+        assert!(message.contains("got a number"));
+
+        let string_burst = InternalHttpFilter {
+            name: "test-string-burst".to_string(),
+            filter_type: "rate_limit".to_string(),
+            config: json!({
+                "requests_per_unit": 100,
+                "time_unit": "minute",
+                "burst_size": "lots"
+            }),
+            ..Default::default()
+        };
+        let message = strategy.validate(&string_burst).unwrap_err().to_string();
+        assert!(message.contains("'burst_size'"));
+    }
+
     #[test]
     fn test_rate_limit_conversion() {
         let strategy = RateLimitStrategy;
@@ -309,6 +356,7 @@ mod tests {
                 "time_unit": "minute",
                 "burst_size": 150
             }),
+            ..Default::default()
         };
         
         let result = strategy.convert(&filter);
@@ -0,0 +1,252 @@
+use crate::xds::conversion::ConversionError;
+use envoy_types::pb::envoy::config::core::v3::RuntimeFractionalPercent;
+use envoy_types::pb::envoy::config::rbac::v3::{permission, principal, rbac::Action, Permission, Principal};
+use envoy_types::pb::envoy::config::route::v3::{
+    header_matcher::HeaderMatchSpecifier, route_match::PathSpecifier, HeaderMatcher,
+};
+use envoy_types::pb::envoy::extensions::filters::http::jwt_authn::v3::{
+    jwt_provider::JwksSourceSpecifier, jwt_requirement::RequiresType, requirement_rule::RequirementType,
+    JwtAuthentication,
+};
+use envoy_types::pb::envoy::extensions::filters::http::{
+    cors::v3::Cors, header_to_metadata::v3::Config as HeaderToMetadata, local_ratelimit::v3::LocalRateLimit,
+    lua::v3::Lua, rbac::v3::Rbac,
+};
+use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
+use envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern;
+use envoy_types::pb::envoy::r#type::v3::fractional_percent::DenominatorType;
+use prost::Message;
+use serde_json::{json, Map, Value};
+
+/// Render a converted filter config as JSON (`type_url` plus decoded fields) for debugging
+///
+/// Only the fields the filter strategies set are rendered, under their protobuf field
+/// names; enums show their protobuf names. Rules the strategies never generate render
+/// as null, and inline JWKS key material is left out.
+pub fn render_typed_config(config: &ConfigType) -> Result<Value, ConversionError> {
+    let ConfigType::TypedConfig(any) = config else {
+        return Ok(json!({ "config_discovery": true }));
+    };
+
+    let rendered = match any.type_url.rsplit('/').next().unwrap_or_default() {
+        "envoy.extensions.filters.http.local_ratelimit.v3.LocalRateLimit" => {
+            render_local_rate_limit(&decode(&any.value)?)
+        }
+        "envoy.extensions.filters.http.cors.v3.Cors" => {
+            decode::<Cors>(&any.value)?;
+            json!({})
+        }
+        "envoy.extensions.filters.http.jwt_authn.v3.JwtAuthentication" => {
+            render_jwt_authentication(&decode(&any.value)?)
+        }
+        "envoy.extensions.filters.http.lua.v3.Lua" => {
+            let lua: Lua = decode(&any.value)?;
+            json!({ "inline_code": lua.inline_code })
+        }
+        "envoy.extensions.filters.http.rbac.v3.RBAC" => render_rbac(&decode(&any.value)?),
+        "envoy.extensions.filters.http.header_to_metadata.v3.Config" => {
+            render_header_to_metadata(&decode(&any.value)?)
+        }
+        _ => Value::Null,
+    };
+
+    Ok(json!({ "type_url": any.type_url, "config": rendered }))
+}
+
+fn decode<T: Message + Default>(bytes: &[u8]) -> Result<T, ConversionError> {
+    T::decode(bytes).map_err(|e| ConversionError::InvalidResource {
+        resource_type: "HttpFilter".to_string(),
+        resource_id: std::any::type_name::<T>().to_string(),
+        reason: format!("Failed to decode typed config: {e}"),
+    })
+}
+
+fn render_local_rate_limit(config: &LocalRateLimit) -> Value {
+    json!({
+        "stat_prefix": config.stat_prefix,
+        "token_bucket": config.token_bucket.as_ref().map(|bucket| json!({
+            "max_tokens": bucket.max_tokens,
+            "tokens_per_fill": bucket.tokens_per_fill.as_ref().map(|fill| json!({ "value": fill.value })),
+            "fill_interval": bucket.fill_interval.as_ref().map(|interval| json!({
+                "seconds": interval.seconds,
+                "nanos": interval.nanos,
+            })),
+        })),
+        "filter_enabled": config.filter_enabled.as_ref().map(render_runtime_percent),
+        "filter_enforced": config.filter_enforced.as_ref().map(render_runtime_percent),
+    })
+}
+
+fn render_runtime_percent(percent: &RuntimeFractionalPercent) -> Value {
+    json!({
+        "default_value": percent.default_value.as_ref().map(|value| json!({
+            "numerator": value.numerator,
+            "denominator": DenominatorType::try_from(value.denominator)
+                .map_or_else(|_| json!(value.denominator), |denominator| json!(denominator.as_str_name())),
+        })),
+        "runtime_key": percent.runtime_key,
+    })
+}
+
+fn render_jwt_authentication(config: &JwtAuthentication) -> Value {
+    let providers: Map<String, Value> = config
+        .providers
+        .iter()
+        .map(|(name, provider)| {
+            let jwks_source = match &provider.jwks_source_specifier {
+                Some(JwksSourceSpecifier::LocalJwks(_)) => json!("local_jwks"),
+                Some(JwksSourceSpecifier::RemoteJwks(_)) => json!("remote_jwks"),
+                None => Value::Null,
+            };
+            let rendered = json!({
+                "issuer": provider.issuer,
+                "audiences": provider.audiences,
+                "forward_payload_header": provider.forward_payload_header,
+                "claim_to_headers": provider
+                    .claim_to_headers
+                    .iter()
+                    .map(|mapping| json!({ "claim_name": mapping.claim_name, "header_name": mapping.header_name }))
+                    .collect::<Vec<_>>(),
+                "jwt_cache_size": provider.jwt_cache_config.as_ref().map(|cache| cache.jwt_cache_size),
+                "jwks_source": jwks_source,
+            });
+            (name.clone(), rendered)
+        })
+        .collect();
+
+    let rules: Vec<Value> = config
+        .rules
+        .iter()
+        .map(|rule| {
+            let prefix = match rule.r#match.as_ref().and_then(|m| m.path_specifier.as_ref()) {
+                Some(PathSpecifier::Prefix(prefix)) => json!(prefix),
+                _ => Value::Null,
+            };
+            let provider_name = match &rule.requirement_type {
+                Some(RequirementType::Requires(requirement)) => match &requirement.requires_type {
+                    Some(RequiresType::ProviderName(name)) => json!(name),
+                    _ => Value::Null,
+                },
+                _ => Value::Null,
+            };
+            json!({ "match_prefix": prefix, "requires_provider": provider_name })
+        })
+        .collect();
+
+    json!({ "providers": providers, "rules": rules })
+}
+
+fn render_rbac(config: &Rbac) -> Value {
+    let Some(rules) = &config.rules else {
+        return json!({ "rules": null });
+    };
+
+    let policies: Map<String, Value> = rules
+        .policies
+        .iter()
+        .map(|(name, policy)| {
+            let rendered = json!({
+                "permissions": policy.permissions.iter().map(render_permission).collect::<Vec<_>>(),
+                "principals": policy.principals.iter().map(render_principal).collect::<Vec<_>>(),
+            });
+            (name.clone(), rendered)
+        })
+        .collect();
+
+    json!({
+        "rules": {
+            "action": Action::try_from(rules.action)
+                .map_or_else(|_| json!(rules.action), |action| json!(action.as_str_name())),
+            "policies": policies,
+        }
+    })
+}
+
+fn render_permission(permission: &Permission) -> Value {
+    match &permission.rule {
+        Some(permission::Rule::AndRules(set)) => {
+            json!({ "and_rules": set.rules.iter().map(render_permission).collect::<Vec<_>>() })
+        }
+        Some(permission::Rule::Header(matcher)) => json!({ "header": render_header_matcher(matcher) }),
+        Some(permission::Rule::Any(any)) => json!({ "any": any }),
+        _ => Value::Null,
+    }
+}
+
+fn render_header_matcher(matcher: &HeaderMatcher) -> Value {
+    let (key, value) = match &matcher.header_match_specifier {
+        Some(HeaderMatchSpecifier::PresentMatch(present)) => ("present_match", json!(present)),
+        Some(HeaderMatchSpecifier::StringMatch(string_match)) => match &string_match.match_pattern {
+            Some(MatchPattern::Exact(exact)) => ("exact", json!(exact)),
+            Some(MatchPattern::Prefix(prefix)) => ("prefix", json!(prefix)),
+            Some(MatchPattern::Suffix(suffix)) => ("suffix", json!(suffix)),
+            Some(MatchPattern::Contains(contains)) => ("contains", json!(contains)),
+            Some(MatchPattern::SafeRegex(regex)) => ("safe_regex", json!(regex.regex)),
+            _ => ("string_match", Value::Null),
+        },
+        _ => ("match", Value::Null),
+    };
+    json!({ "name": matcher.name, key: value })
+}
+
+fn render_principal(principal: &Principal) -> Value {
+    match &principal.identifier {
+        Some(principal::Identifier::Any(any)) => json!({ "any": any }),
+        Some(principal::Identifier::RemoteIp(cidr)) => json!({
+            "remote_ip": format!(
+                "{}/{}",
+                cidr.address_prefix,
+                cidr.prefix_len.as_ref().map_or(0, |prefix_len| prefix_len.value)
+            )
+        }),
+        _ => Value::Null,
+    }
+}
+
+fn render_header_to_metadata(config: &HeaderToMetadata) -> Value {
+    let request_rules: Vec<Value> = config
+        .request_rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "header": rule.header,
+                "on_header_present": rule.on_header_present.as_ref().map(|pair| json!({
+                    "metadata_namespace": pair.metadata_namespace,
+                    "key": pair.key,
+                })),
+                "remove": rule.remove,
+            })
+        })
+        .collect();
+
+    json!({ "request_rules": request_rules })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::HttpFilter as InternalHttpFilter;
+    use crate::xds::filters::{FilterStrategy, RateLimitStrategy};
+
+    #[test]
+    fn test_rate_limit_render_contains_token_bucket() {
+        let filter = InternalHttpFilter::new(
+            "api-rate-limit".to_string(),
+            "rate_limit".to_string(),
+            json!({"requests_per_unit": 250, "time_unit": "minute"}),
+        );
+
+        let rendered = render_typed_config(&RateLimitStrategy.convert(&filter).unwrap()).unwrap();
+        assert_eq!(
+            rendered["type_url"],
+            "type.googleapis.com/envoy.extensions.filters.http.local_ratelimit.v3.LocalRateLimit"
+        );
+
+        let token_bucket = &rendered["config"]["token_bucket"];
+        assert_eq!(token_bucket["max_tokens"], 250);
+        assert_eq!(token_bucket["tokens_per_fill"]["value"], 250);
+        assert_eq!(token_bucket["fill_interval"]["seconds"], 60);
+        assert_eq!(rendered["config"]["stat_prefix"], "rate_limit_api-rate-limit");
+        assert_eq!(rendered["config"]["filter_enabled"]["default_value"]["denominator"], "HUNDRED");
+    }
+}
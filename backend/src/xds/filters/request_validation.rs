@@ -4,12 +4,20 @@ use crate::xds::conversion::ConversionError;
 use crate::xds::filters::FilterStrategy;
 use crate::validation::security::Validator;
 use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::http_filter::ConfigType;
+use envoy_types::pb::envoy::config::accesslog::v3::{access_log_filter::FilterSpecifier, AccessLogFilter, ResponseFlagFilter};
+use envoy_types::pb::envoy::config::core::v3::{
+    data_source, substitution_format_string, CidrRange, DataSource, SubstitutionFormatString,
+};
 use envoy_types::pb::envoy::extensions::filters::http::rbac::v3::Rbac;
+use envoy_types::pb::envoy::extensions::filters::network::http_connection_manager::v3::ResponseMapper;
 use envoy_types::pb::envoy::r#type::matcher::v3::RegexMatcher;
-use envoy_types::pb::google::protobuf::Any;
+use envoy_types::pb::google::protobuf::{Any, UInt32Value};
 use prost::Message;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use tracing::info;
 use std::collections::HashMap;
+use std::net::IpAddr;
 
 /// Strategy for converting request validation filters to Envoy RBAC
 /// 
@@ -31,6 +39,25 @@ impl FilterStrategy for RequestValidationStrategy {
     }
 
     fn validate(&self, filter: &InternalHttpFilter) -> Result<(), ConversionError> {
+        // Validate mode if present
+        let mode = match filter.config.get("mode") {
+            None => ValidationMode::Allow,
+            Some(mode) => mode.as_str().and_then(ValidationMode::parse).ok_or_else(|| ConversionError::ValidationFailed {
+                reason: format!("mode must be \"allow\" or \"deny\" for filter '{}'", filter.name)
+            })?,
+        };
+
+        // A deny policy with no conditions would block every request
+        if mode == ValidationMode::Deny
+            && !["allowed_methods", "required_headers", "required_query_params", "allowed_paths"]
+                .iter()
+                .any(|field| filter.config.get(*field).and_then(|v| v.as_array()).is_some_and(|v| !v.is_empty()))
+        {
+            return Err(ConversionError::ValidationFailed {
+                reason: format!("Deny mode requires at least one rule for filter '{}'", filter.name)
+            });
+        }
+
         // Validate allowed_methods if present
         if let Some(methods) = filter.config.get("allowed_methods") {
             let methods_array = methods.as_array()
@@ -82,6 +109,28 @@ impl FilterStrategy for RequestValidationStrategy {
             }
         }
 
+        // Validate required_query_params if present
+        if let Some(params) = filter.config.get("required_query_params") {
+            let params_array = params.as_array()
+                .ok_or_else(|| ConversionError::ValidationFailed {
+                    reason: format!("required_query_params must be an array for filter '{}'", filter.name)
+                })?;
+
+            for param in params_array {
+                let param_str = param.as_str()
+                    .ok_or_else(|| ConversionError::ValidationFailed {
+                        reason: format!("Each required query parameter must be a string for filter '{}'", filter.name)
+                    })?;
+
+                // Names are embedded in a :path regex, so keep them to a plain charset
+                Validator::validate_length(param_str, "required_query_params", Some(1), Some(100))
+                    .and_then(|_| Validator::validate_charset(param_str, "required_query_params", &['-', '_', '.']))
+                    .map_err(|e| ConversionError::ValidationFailed {
+                        reason: format!("Invalid required query parameter name in filter '{}': {}", filter.name, e)
+                    })?;
+            }
+        }
+
         // Validate allowed_paths if present
         if let Some(paths) = filter.config.get("allowed_paths") {
             let paths_array = paths.as_array()
@@ -123,38 +172,50 @@ impl FilterStrategy for RequestValidationStrategy {
             }
         }
 
+        // Validate allowed_source_cidrs if present
+        if let Some(cidrs) = filter.config.get("allowed_source_cidrs") {
+            let cidrs_array = cidrs.as_array()
+                .ok_or_else(|| ConversionError::ValidationFailed {
+                    reason: format!("allowed_source_cidrs must be an array for filter '{}'", filter.name)
+                })?;
+
+            // Deny mode blocks matching requests, so an allow-list of sources has no meaning there
+            if mode == ValidationMode::Deny && !cidrs_array.is_empty() {
+                return Err(ConversionError::ValidationFailed {
+                    reason: format!("allowed_source_cidrs is only supported in allow mode for filter '{}'", filter.name)
+                });
+            }
+
+            for cidr in cidrs_array {
+                let cidr_str = cidr.as_str()
+                    .ok_or_else(|| ConversionError::ValidationFailed {
+                        reason: format!("Each allowed source CIDR must be a string for filter '{}'", filter.name)
+                    })?;
+
+                if parse_cidr(cidr_str).is_none() {
+                    return Err(ConversionError::ValidationFailed {
+                        reason: format!("Invalid CIDR '{}' in allowed_source_cidrs for filter '{}'", cidr_str, filter.name)
+                    });
+                }
+            }
+        }
+
+        DeniedResponse::from_filter(filter)?;
+
         Ok(())
     }
 
     fn convert(&self, filter: &InternalHttpFilter) -> Result<ConfigType, ConversionError> {
         info!("Converting request_validation filter '{}' to Envoy RBAC", filter.name);
 
-        let allowed_methods = filter.config.get("allowed_methods")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_else(|| vec!["GET".to_string(), "POST".to_string()]);
-
-        let required_headers = filter.config.get("required_headers")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default();
-
-        let allowed_paths = filter.config.get("allowed_paths")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default();
+        let RequestValidationRules {
+            mode,
+            allowed_methods,
+            required_headers,
+            required_query_params,
+            allowed_paths,
+            allowed_source_cidrs,
+        } = RequestValidationRules::from_filter(filter);
 
         // Create RBAC policy for request validation
         let mut policies = HashMap::new();
@@ -202,6 +263,33 @@ impl FilterStrategy for RequestValidationStrategy {
             });
         }
 
+        // Add required query parameter validation (RBAC has no query matcher, so match on :path)
+        for param in &required_query_params {
+            and_rules.push(envoy_types::pb::envoy::config::rbac::v3::Permission {
+                rule: Some(envoy_types::pb::envoy::config::rbac::v3::permission::Rule::Header(
+                    envoy_types::pb::envoy::config::route::v3::HeaderMatcher {
+                        name: ":path".to_string(),
+                        header_match_specifier: Some(
+                            envoy_types::pb::envoy::config::route::v3::header_matcher::HeaderMatchSpecifier::StringMatch(
+                                envoy_types::pb::envoy::r#type::matcher::v3::StringMatcher {
+                                    match_pattern: Some(
+                                        envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::SafeRegex(
+                                            RegexMatcher {
+                                                regex: query_param_regex(param),
+                                                ..Default::default()
+                                            }
+                                        )
+                                    ),
+                                    ..Default::default()
+                                }
+                            )
+                        ),
+                        ..Default::default()
+                    }
+                )),
+            });
+        }
+
         // Add path validation if specified
         if !allowed_paths.is_empty() {
             and_rules.push(envoy_types::pb::envoy::config::rbac::v3::Permission {
@@ -214,7 +302,7 @@ impl FilterStrategy for RequestValidationStrategy {
                                     match_pattern: Some(
                                         envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::SafeRegex(
                                             RegexMatcher {
-                                                regex: format!("^({})$", allowed_paths.join("|")),
+                                                regex: allowed_paths_regex(&allowed_paths),
                                                 ..Default::default()
                                             }
                                         )
@@ -229,7 +317,13 @@ impl FilterStrategy for RequestValidationStrategy {
             });
         }
 
-        policies.insert("allow_valid_requests".to_string(), 
+        // Allow mode admits requests matching every rule; deny mode blocks them instead
+        let (policy_name, action) = match mode {
+            ValidationMode::Allow => ("allow_valid_requests", envoy_types::pb::envoy::config::rbac::v3::rbac::Action::Allow),
+            ValidationMode::Deny => ("deny_matching_requests", envoy_types::pb::envoy::config::rbac::v3::rbac::Action::Deny),
+        };
+
+        policies.insert(policy_name.to_string(), 
             envoy_types::pb::envoy::config::rbac::v3::Policy {
                 permissions: vec![envoy_types::pb::envoy::config::rbac::v3::Permission {
                     rule: Some(envoy_types::pb::envoy::config::rbac::v3::permission::Rule::AndRules(
@@ -238,16 +332,14 @@ impl FilterStrategy for RequestValidationStrategy {
                         }
                     )),
                 }],
-                principals: vec![envoy_types::pb::envoy::config::rbac::v3::Principal {
-                    identifier: Some(envoy_types::pb::envoy::config::rbac::v3::principal::Identifier::Any(true)),
-                }],
+                principals: source_principals(&allowed_source_cidrs),
                 ..Default::default()
             }
         );
 
         let rbac_config = Rbac {
             rules: Some(envoy_types::pb::envoy::config::rbac::v3::Rbac {
-                action: envoy_types::pb::envoy::config::rbac::v3::rbac::Action::Allow as i32,
+                action: action as i32,
                 policies,
                 ..Default::default()
             }),
@@ -272,8 +364,336 @@ impl FilterStrategy for RequestValidationStrategy {
     }
 
     fn description(&self) -> &'static str {
-        "Request validation filter using Envoy's RBAC for secure validation of methods, headers, and paths"
+        "Request validation filter using Envoy's RBAC for secure validation of methods, headers, query parameters, paths, and source addresses"
+    }
+}
+
+/// Whether requests matching the rules are admitted or blocked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValidationMode {
+    Allow,
+    Deny,
+}
+
+impl ValidationMode {
+    fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "allow" => Some(Self::Allow),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Rules enforced by a request_validation filter, with the same defaults used for RBAC
+struct RequestValidationRules {
+    mode: ValidationMode,
+    allowed_methods: Vec<String>,
+    required_headers: Vec<String>,
+    required_query_params: Vec<String>,
+    allowed_paths: Vec<String>,
+    allowed_source_cidrs: Vec<String>,
+}
+
+impl RequestValidationRules {
+    fn from_filter(filter: &InternalHttpFilter) -> Self {
+        let string_list = |field: &str| {
+            filter.config.get(field)
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                        .collect::<Vec<String>>()
+                })
+        };
+
+        let mode = filter.config.get("mode")
+            .and_then(|v| v.as_str())
+            .and_then(ValidationMode::parse)
+            .unwrap_or(ValidationMode::Allow);
+
+        // Only allow mode falls back to a method allow-list; deny mode blocks exactly what is listed
+        let default_methods = match mode {
+            ValidationMode::Allow => vec!["GET".to_string(), "POST".to_string()],
+            ValidationMode::Deny => vec![],
+        };
+
+        Self {
+            mode,
+            allowed_methods: string_list("allowed_methods").unwrap_or(default_methods),
+            required_headers: string_list("required_headers").unwrap_or_default(),
+            required_query_params: string_list("required_query_params").unwrap_or_default(),
+            allowed_paths: string_list("allowed_paths").unwrap_or_default(),
+            allowed_source_cidrs: string_list("allowed_source_cidrs").unwrap_or_default(),
+        }
+    }
+}
+
+/// RBAC principals for the policy: any downstream, or only the listed client address ranges
+///
+/// Matches `remote_ip`, the client address after the listener's X-Forwarded-For handling.
+fn source_principals(allowed_source_cidrs: &[String]) -> Vec<envoy_types::pb::envoy::config::rbac::v3::Principal> {
+    use envoy_types::pb::envoy::config::rbac::v3::{principal::Identifier, Principal};
+
+    if allowed_source_cidrs.is_empty() {
+        return vec![Principal {
+            identifier: Some(Identifier::Any(true)),
+        }];
+    }
+
+    allowed_source_cidrs
+        .iter()
+        .filter_map(|cidr| parse_cidr(cidr))
+        .map(|(address, prefix_len)| Principal {
+            identifier: Some(Identifier::RemoteIp(CidrRange {
+                address_prefix: address.to_string(),
+                prefix_len: Some(UInt32Value { value: prefix_len }),
+            })),
+        })
+        .collect()
+}
+
+/// Parse `address/prefix_len`; a bare address is a single host
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (address, prefix_len) = match cidr.split_once('/') {
+        Some((address, prefix_len)) => (address.parse::<IpAddr>().ok()?, prefix_len.parse::<u32>().ok()?),
+        None => {
+            let address = cidr.parse::<IpAddr>().ok()?;
+            (address, if address.is_ipv4() { 32 } else { 128 })
+        }
+    };
+    let max_len = if address.is_ipv4() { 32 } else { 128 };
+    (prefix_len <= max_len).then_some((address, prefix_len))
+}
+
+/// Status and JSON body sent instead of Envoy's bare 403 when the filter denies a request
+struct DeniedResponse {
+    status_code: u32,
+    body: Option<serde_json::Value>,
+}
+
+impl DeniedResponse {
+    /// Parse the optional `denied_response: {status_code, body}` block of a filter config
+    fn from_filter(filter: &InternalHttpFilter) -> Result<Option<Self>, ConversionError> {
+        let Some(denied_response) = filter.config.get("denied_response") else {
+            return Ok(None);
+        };
+        let invalid = |reason: &str| ConversionError::ValidationFailed {
+            reason: format!("denied_response {} for filter '{}'", reason, filter.name),
+        };
+
+        let status_code = denied_response
+            .get("status_code")
+            .and_then(|v| v.as_u64())
+            .filter(|code| (400..=599).contains(code))
+            .ok_or_else(|| invalid("status_code must be an integer between 400 and 599"))?;
+
+        let body = denied_response.get("body").cloned();
+        if let Some(ref body) = body {
+            if !body.is_object() {
+                return Err(invalid("body must be a JSON object"));
+            }
+            if body.to_string().len() > 4096 {
+                return Err(invalid("body must serialize to at most 4096 bytes"));
+            }
+        }
+
+        Ok(Some(Self {
+            status_code: status_code as u32,
+            body,
+        }))
+    }
+}
+
+/// HCM local reply mapper that rewrites RBAC denials into the filter's `denied_response`
+///
+/// Envoy flags every RBAC denial the same way, so the mapper applies to denials from any
+/// RBAC-based filter on the listener.
+pub fn denied_response_mapper(filter: &InternalHttpFilter) -> Result<Option<ResponseMapper>, ConversionError> {
+    let Some(denied_response) = DeniedResponse::from_filter(filter)? else {
+        return Ok(None);
+    };
+
+    let inline = |value: String| DataSource {
+        specifier: Some(data_source::Specifier::InlineString(value)),
+        ..Default::default()
+    };
+
+    Ok(Some(ResponseMapper {
+        filter: Some(AccessLogFilter {
+            filter_specifier: Some(FilterSpecifier::ResponseFlagFilter(ResponseFlagFilter {
+                flags: vec!["RBAC".to_string()],
+            })),
+        }),
+        status_code: Some(UInt32Value { value: denied_response.status_code }),
+        body_format_override: denied_response.body.is_some().then(|| SubstitutionFormatString {
+            format: Some(substitution_format_string::Format::TextFormatSource(inline(
+                "%LOCAL_REPLY_BODY%".to_string(),
+            ))),
+            content_type: "application/json".to_string(),
+            ..Default::default()
+        }),
+        body: denied_response.body.map(|body| inline(body.to_string())),
+        ..Default::default()
+    }))
+}
+
+/// Sample request to evaluate against a request_validation filter
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulatedRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Client address checked against allowed_source_cidrs
+    #[serde(default)]
+    pub source_ip: Option<IpAddr>,
+}
+
+/// Outcome of evaluating a sample request against a filter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimulationResult {
+    pub allowed: bool,
+    /// Config key of the first rule that denied the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_rule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl SimulationResult {
+    fn allow() -> Self {
+        Self { allowed: true, failed_rule: None, reason: None }
+    }
+
+    fn deny(rule: &str, reason: String) -> Self {
+        Self { allowed: false, failed_rule: Some(rule.to_string()), reason: Some(reason) }
+    }
+}
+
+/// Evaluate a sample request against a request_validation filter without deploying it
+///
+/// Mirrors the RBAC policy produced by `RequestValidationStrategy::convert`: the method
+/// and path (ignoring its query string) must fully match the configured patterns, every required header must be
+/// present (header names are case-insensitive, as in Envoy) and every required query
+/// parameter must appear in the path's query string, and the source IP must fall in one
+/// of the allowed source CIDRs. In deny mode a request meeting every rule is blocked and
+/// anything else is allowed.
+pub fn simulate_request(
+    filter: &InternalHttpFilter,
+    request: &SimulatedRequest,
+) -> Result<SimulationResult, ConversionError> {
+    let rules = RequestValidationRules::from_filter(filter);
+    let unmet = first_unmet_rule(filter, &rules, request)?;
+
+    Ok(match (rules.mode, unmet) {
+        (ValidationMode::Allow, Some((rule, reason))) => SimulationResult::deny(rule, reason),
+        (ValidationMode::Allow, None) => SimulationResult::allow(),
+        (ValidationMode::Deny, Some(_)) => SimulationResult::allow(),
+        (ValidationMode::Deny, None) => {
+            SimulationResult::deny("mode", "Request matches every rule of a deny-mode filter".to_string())
+        }
+    })
+}
+
+/// First configured rule the request does not satisfy, as (config key, reason)
+fn first_unmet_rule(
+    filter: &InternalHttpFilter,
+    rules: &RequestValidationRules,
+    request: &SimulatedRequest,
+) -> Result<Option<(&'static str, String)>, ConversionError> {
+    if !rules.allowed_methods.is_empty()
+        && !full_match(filter, &format!("^({})$", rules.allowed_methods.join("|")), &request.method)?
+    {
+        return Ok(Some((
+            "allowed_methods",
+            format!("Method '{}' is not in allowed_methods", request.method),
+        )));
+    }
+
+    for header in &rules.required_headers {
+        if !request.headers.keys().any(|name| name.eq_ignore_ascii_case(header)) {
+            return Ok(Some((
+                "required_headers",
+                format!("Required header '{header}' is missing"),
+            )));
+        }
     }
+
+    for param in &rules.required_query_params {
+        // The regex is built from validated names, so it always compiles
+        if !Regex::new(&query_param_regex(param)).is_ok_and(|regex| regex.is_match(&request.path)) {
+            return Ok(Some((
+                "required_query_params",
+                format!("Required query parameter '{param}' is missing"),
+            )));
+        }
+    }
+
+    if !rules.allowed_paths.is_empty() && !full_match(filter, &allowed_paths_regex(&rules.allowed_paths), &request.path)? {
+        return Ok(Some((
+            "allowed_paths",
+            format!("Path '{}' does not match allowed_paths", request.path),
+        )));
+    }
+
+    if !rules.allowed_source_cidrs.is_empty() {
+        match request.source_ip {
+            Some(source_ip) if rules.allowed_source_cidrs.iter().any(|cidr| cidr_contains(cidr, source_ip)) => {}
+            Some(source_ip) => {
+                return Ok(Some((
+                    "allowed_source_cidrs",
+                    format!("Source IP '{source_ip}' is not in allowed_source_cidrs"),
+                )));
+            }
+            None => {
+                return Ok(Some((
+                    "allowed_source_cidrs",
+                    "No source_ip given to check against allowed_source_cidrs".to_string(),
+                )));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Whether `address` falls in `cidr`; an unparseable CIDR or a different address family never matches
+fn cidr_contains(cidr: &str, address: IpAddr) -> bool {
+    let Some((network, prefix_len)) = parse_cidr(cidr) else {
+        return false;
+    };
+    let (network, address, width) = match (network, address) {
+        (IpAddr::V4(network), IpAddr::V4(address)) => (u32::from(network) as u128, u32::from(address) as u128, 32),
+        (IpAddr::V6(network), IpAddr::V6(address)) => (u128::from(network), u128::from(address), 128),
+        _ => return false,
+    };
+    if prefix_len == 0 {
+        return true;
+    }
+    let mask = u128::MAX << (width - prefix_len);
+    network & mask == address & mask
+}
+
+/// Matches a `:path` whose query string contains `name`, with or without a value
+fn query_param_regex(name: &str) -> String {
+    format!(r"^[^?]*\?(.*&)?{}(=[^&]*)?(&.*)?$", regex::escape(name))
+}
+
+/// Matches a `:path` against the allowed path patterns; any query string after the path is ignored
+fn allowed_paths_regex(allowed_paths: &[String]) -> String {
+    format!(r"^({})(\?.*)?$", allowed_paths.join("|"))
+}
+
+/// Test `value` against an anchored regex built the same way as the RBAC header matcher's
+fn full_match(filter: &InternalHttpFilter, pattern: &str, value: &str) -> Result<bool, ConversionError> {
+    let regex = Regex::new(pattern)
+        .map_err(|e| ConversionError::InvalidResource {
+            resource_type: "HttpFilter".to_string(),
+            resource_id: filter.name.clone(),
+            reason: format!("Invalid pattern: {e}"),
+        })?;
+    Ok(regex.is_match(value))
 }
 
 #[cfg(test)]
@@ -290,11 +710,18 @@ mod tests {
                     rest_port: 8080,
                     xds_port: 18000,
                     host: "0.0.0.0".to_string(),
+                    xds_keepalive: XdsKeepaliveConfig::default(),
+                    strict_resource_types: false,
+                    node_match_mode: "exact".to_string(),
+                    reject_unknown_fields: false,
+                    last_known_good_grace_seconds: None,
+                    read_only: false,
                 },
                 tls: TlsConfig {
                     cert_path: "./certs/server.crt".to_string(),
                     key_path: "./certs/server.key".to_string(),
                     enabled: true,
+                    min_tls_version: "1.2".to_string(),
                 },
                 logging: LoggingConfig {
                     level: "info".to_string(),
@@ -314,7 +741,8 @@ mod tests {
                     jwt_secret: "test-secret-1234567890abcdefghijklmnopqrstuvwxyz".to_string(),
                     jwt_expiry_hours: 24,
                     jwt_issuer: "envoy-control-plane-test".to_string(),
-                    password_hash_cost: 8,
+                    password_hash_cost: crate::config::validation::MIN_TEST_BCRYPT_COST,
+                    password_hash_algorithm: "bcrypt".to_string(),
                 },
                 storage: StorageConfig::default(),
                 http_filters: HttpFiltersFeatureConfig::default(),
@@ -328,12 +756,30 @@ mod tests {
                 listener: ListenerConfig {
                     binding_address: "0.0.0.0".to_string(),
                     default_port: 10000,
+                    forwarded_headers: ForwardedHeadersConfig::default(),
+                    http3: false,
+                    downstream_tls: None,
+                    default_request_timeout_seconds: None,
+                    per_connection_buffer_limit_bytes: None,
+                    exact_connection_balance: false,
+                    drain_type: "DEFAULT".to_string(),
+                    drain_timeout_seconds: None,
+                    server_header_transformation: "OVERWRITE".to_string(),
+                    server_name: None,
+                    suppress_envoy_headers: false,
+                    access_log: None,
+                    additional_listeners: vec![],
+                    allow_early_data: None,
+                    local_reply: None,
                 },
                 cluster: ClusterConfig {
                     connect_timeout_seconds: 5,
                     discovery_type: "STRICT_DNS".to_string(),
                     dns_lookup_family: "V4_ONLY".to_string(),
                     default_protocol: "TCP".to_string(),
+                    dns_resolution_check: DnsResolutionCheckConfig::default(),
+                    max_buffer_limit_bytes: 16 * 1024 * 1024,
+                    reject_duplicate_endpoints: false,
                 },
                 naming: NamingConfig {
                     listener_name: "listener_0".to_string(),
@@ -372,6 +818,7 @@ mod tests {
                 "required_headers": ["Authorization", "Content-Type"],
                 "allowed_paths": ["/api/v1/users", "/api/v1/orders"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&valid_filter).is_ok());
@@ -384,6 +831,7 @@ mod tests {
             config: json!({
                 "allowed_methods": ["get", "POST"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter).is_err());
@@ -402,6 +850,7 @@ mod tests {
             config: json!({
                 "allowed_methods": ["INVALID_METHOD"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter).is_err());
@@ -420,6 +869,7 @@ mod tests {
             config: json!({
                 "allowed_paths": ["/api/../sensitive"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter).is_err());
@@ -432,6 +882,7 @@ mod tests {
             config: json!({
                 "allowed_paths": ["api/users"]
             }),
+            ..Default::default()
         };
         
         assert!(strategy.validate(&invalid_filter2).is_err());
@@ -451,6 +902,7 @@ mod tests {
                 "required_headers": ["Authorization"],
                 "allowed_paths": ["/api/v1/.*"]
             }),
+            ..Default::default()
         };
         
         let result = strategy.convert(&filter);
@@ -462,4 +914,259 @@ mod tests {
             panic!("Expected TypedConfig result");
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_simulate_reports_missing_required_header() {
+        let filter = InternalHttpFilter::new(
+            "validation".to_string(),
+            "request_validation".to_string(),
+            json!({
+                "allowed_methods": ["GET"],
+                "required_headers": ["x-api-key"]
+            }),
+        );
+
+        let mut request = SimulatedRequest {
+            method: "GET".to_string(),
+            path: "/api".to_string(),
+            headers: HashMap::new(),
+            source_ip: None,
+        };
+        let result = simulate_request(&filter, &request).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.failed_rule.as_deref(), Some("required_headers"));
+
+        request.headers.insert("X-API-Key".to_string(), "secret".to_string());
+        assert_eq!(simulate_request(&filter, &request).unwrap(), SimulationResult::allow());
+
+        request.method = "DELETE".to_string();
+        let result = simulate_request(&filter, &request).unwrap();
+        assert_eq!(result.failed_rule.as_deref(), Some("allowed_methods"));
+    }
+
+    #[test]
+    fn test_simulate_denies_source_outside_allowed_cidrs() {
+        let filter = InternalHttpFilter::new(
+            "office-only".to_string(),
+            "request_validation".to_string(),
+            json!({
+                "allowed_methods": ["GET"],
+                "allowed_source_cidrs": ["10.20.0.0/16", "2001:db8::/32"]
+            }),
+        );
+
+        let mut request = SimulatedRequest {
+            method: "GET".to_string(),
+            path: "/api".to_string(),
+            source_ip: Some("192.168.1.5".parse().unwrap()),
+            ..Default::default()
+        };
+        let result = simulate_request(&filter, &request).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.failed_rule.as_deref(), Some("allowed_source_cidrs"));
+
+        request.source_ip = Some("10.20.3.4".parse().unwrap());
+        assert_eq!(simulate_request(&filter, &request).unwrap(), SimulationResult::allow());
+
+        request.source_ip = Some("2001:db8:1::7".parse().unwrap());
+        assert_eq!(simulate_request(&filter, &request).unwrap(), SimulationResult::allow());
+
+        request.source_ip = None;
+        assert!(!simulate_request(&filter, &request).unwrap().allowed);
+    }
+
+    #[test]
+    fn test_allowed_path_with_required_query_param_allowed() {
+        let filter = InternalHttpFilter::new(
+            "versioned-orders".to_string(),
+            "request_validation".to_string(),
+            json!({
+                "allowed_methods": ["GET"],
+                "allowed_paths": ["/api/orders"],
+                "required_query_params": ["api_version"]
+            }),
+        );
+
+        let mut request = SimulatedRequest {
+            method: "GET".to_string(),
+            path: "/api/orders?api_version=2".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(simulate_request(&filter, &request).unwrap(), SimulationResult::allow());
+
+        request.path = "/api/orders/7?api_version=2".to_string();
+        let result = simulate_request(&filter, &request).unwrap();
+        assert_eq!(result.failed_rule.as_deref(), Some("allowed_paths"));
+
+        let regex = Regex::new(&allowed_paths_regex(&["/api/orders".to_string()])).unwrap();
+        assert!(regex.is_match("/api/orders"));
+        assert!(regex.is_match("/api/orders?api_version=2"));
+        assert!(!regex.is_match("/api/orders-admin?api_version=2"));
+    }
+
+    #[test]
+    fn test_required_query_param_produces_path_rule() {
+        let strategy = RequestValidationStrategy::new(create_test_config());
+        let filter = InternalHttpFilter::new(
+            "validation".to_string(),
+            "request_validation".to_string(),
+            json!({
+                "allowed_methods": ["GET"],
+                "required_query_params": ["api_version"]
+            }),
+        );
+        strategy.validate(&filter).unwrap();
+
+        let any = match strategy.convert(&filter).unwrap() {
+            ConfigType::TypedConfig(any) => any,
+            _ => panic!("Expected TypedConfig result"),
+        };
+        let rbac = Rbac::decode(&any.value[..]).unwrap();
+        let rules = rbac.rules.unwrap();
+        let policy = &rules.policies["allow_valid_requests"];
+        let and_rules = match &policy.permissions[0].rule {
+            Some(envoy_types::pb::envoy::config::rbac::v3::permission::Rule::AndRules(set)) => &set.rules,
+            _ => panic!("Expected and_rules"),
+        };
+
+        let query_regex = and_rules
+            .iter()
+            .find_map(|permission| match &permission.rule {
+                Some(envoy_types::pb::envoy::config::rbac::v3::permission::Rule::Header(matcher))
+                    if matcher.name == ":path" =>
+                {
+                    match &matcher.header_match_specifier {
+                        Some(envoy_types::pb::envoy::config::route::v3::header_matcher::HeaderMatchSpecifier::StringMatch(m)) => {
+                            match &m.match_pattern {
+                                Some(envoy_types::pb::envoy::r#type::matcher::v3::string_matcher::MatchPattern::SafeRegex(r)) => {
+                                    Some(r.regex.clone())
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .expect("required query param should produce a :path rule");
+
+        let regex = Regex::new(&query_regex).unwrap();
+        assert!(regex.is_match("/api/orders?api_version=2"));
+        assert!(regex.is_match("/api/orders?limit=5&api_version"));
+        assert!(!regex.is_match("/api/orders?limit=5"));
+        assert!(!regex.is_match("/api/orders?old_api_version=2"));
+
+        let invalid = InternalHttpFilter::new(
+            "validation".to_string(),
+            "request_validation".to_string(),
+            json!({"required_query_params": ["api version"]}),
+        );
+        assert!(strategy.validate(&invalid).is_err());
+    }
+
+    fn rbac_action(filter: &InternalHttpFilter) -> i32 {
+        let strategy = RequestValidationStrategy::new(create_test_config());
+        strategy.validate(filter).unwrap();
+        match strategy.convert(filter).unwrap() {
+            ConfigType::TypedConfig(any) => Rbac::decode(&any.value[..]).unwrap().rules.unwrap().action,
+            _ => panic!("Expected TypedConfig result"),
+        }
+    }
+
+    #[test]
+    fn test_allow_mode_produces_allow_action() {
+        let filter = InternalHttpFilter::new(
+            "allow-list".to_string(),
+            "request_validation".to_string(),
+            json!({"mode": "allow", "allowed_methods": ["GET"]}),
+        );
+        assert_eq!(rbac_action(&filter), envoy_types::pb::envoy::config::rbac::v3::rbac::Action::Allow as i32);
+    }
+
+    #[test]
+    fn test_deny_mode_produces_deny_action() {
+        let filter = InternalHttpFilter::new(
+            "deny-list".to_string(),
+            "request_validation".to_string(),
+            json!({"mode": "deny", "allowed_methods": ["DELETE"]}),
+        );
+        assert_eq!(rbac_action(&filter), envoy_types::pb::envoy::config::rbac::v3::rbac::Action::Deny as i32);
+
+        let request = SimulatedRequest {
+            method: "DELETE".to_string(),
+            path: "/api".to_string(),
+            headers: HashMap::new(),
+            source_ip: None,
+        };
+        assert!(!simulate_request(&filter, &request).unwrap().allowed);
+
+        let strategy = RequestValidationStrategy::new(create_test_config());
+        for config in [json!({"mode": "block"}), json!({"mode": "deny"})] {
+            let invalid = InternalHttpFilter::new("deny-list".to_string(), "request_validation".to_string(), config);
+            assert!(strategy.validate(&invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_denied_response_status_and_body_validated() {
+        let strategy = RequestValidationStrategy::new(create_test_config());
+        let with_denied_response = |denied_response: serde_json::Value| {
+            InternalHttpFilter::new(
+                "api-validation".to_string(),
+                "request_validation".to_string(),
+                json!({"allowed_methods": ["GET"], "denied_response": denied_response}),
+            )
+        };
+
+        let valid = with_denied_response(json!({"status_code": 422, "body": {"error": "invalid request"}}));
+        assert!(strategy.validate(&valid).is_ok());
+
+        for invalid in [
+            json!({"status_code": 200}),
+            json!({"status_code": "403"}),
+            json!({"status_code": 403, "body": "denied"}),
+        ] {
+            assert!(strategy.validate(&with_denied_response(invalid)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_allowed_source_cidrs_produce_remote_ip_principals() {
+        use envoy_types::pb::envoy::config::rbac::v3::principal::Identifier;
+
+        let strategy = RequestValidationStrategy::new(create_test_config());
+        let filter = InternalHttpFilter::new(
+            "office-only".to_string(),
+            "request_validation".to_string(),
+            json!({"allowed_source_cidrs": ["10.20.0.0/16", "2001:db8::1"]}),
+        );
+        strategy.validate(&filter).unwrap();
+
+        let rbac = match strategy.convert(&filter).unwrap() {
+            ConfigType::TypedConfig(any) => Rbac::decode(&any.value[..]).unwrap(),
+            _ => panic!("Expected TypedConfig result"),
+        };
+        let policy = &rbac.rules.unwrap().policies["allow_valid_requests"];
+        let ranges: Vec<(String, u32)> = policy
+            .principals
+            .iter()
+            .map(|principal| match &principal.identifier {
+                Some(Identifier::RemoteIp(range)) => {
+                    (range.address_prefix.clone(), range.prefix_len.as_ref().unwrap().value)
+                }
+                other => panic!("Expected remote_ip principal, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(ranges, vec![("10.20.0.0".to_string(), 16), ("2001:db8::1".to_string(), 128)]);
+
+        for config in [
+            json!({"allowed_source_cidrs": ["10.20.0.0/33"]}),
+            json!({"allowed_source_cidrs": ["office-network"]}),
+            json!({"mode": "deny", "allowed_methods": ["DELETE"], "allowed_source_cidrs": ["10.0.0.0/8"]}),
+        ] {
+            let invalid = InternalHttpFilter::new("office-only".to_string(), "request_validation".to_string(), config);
+            assert!(strategy.validate(&invalid).is_err());
+        }
+    }
+}
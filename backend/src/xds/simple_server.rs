@@ -1,24 +1,84 @@
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 use tokio_stream::{wrappers::ReceiverStream, Stream};
 use tonic::{Request, Response, Status, Streaming};
 
+use crate::config::XdsKeepaliveConfig;
 use crate::storage::ConfigStore;
-use crate::xds::conversion::get_resources_by_type;
+use crate::xds::conversion::{
+    filter_by_node, filter_by_resource_names, get_resources_by_type, is_supported_type_url, SUPPORTED_TYPE_URLS,
+};
 
 // Include the generated protobuf code
 include!(concat!(env!("OUT_DIR"), "/envoy.service.discovery.v3.rs"));
 
 pub use aggregated_discovery_service_server::AggregatedDiscoveryServiceServer;
 
+/// An Envoy currently connected over an ADS stream
+#[derive(Debug, Clone, Serialize)]
+pub struct XdsClientInfo {
+    pub node_id: String,
+    pub node_cluster: String,
+    pub connected_at: DateTime<Utc>,
+    pub last_request_type: Option<String>,
+    /// Last version the client ACKed, keyed by type URL
+    pub acked_versions: BTreeMap<String, String>,
+}
+
+/// Resources captured by `pause()`; streams serve these instead of the live store until `resume()`
+#[derive(Debug)]
+struct PausedSnapshot {
+    version: u64,
+    resources: HashMap<String, Vec<prost_types::Any>>,
+}
+
+type PausedState = Arc<RwLock<Option<Arc<PausedSnapshot>>>>;
+
+/// Resources from the last successful conversion of each type URL
+///
+/// When a later conversion fails, these are served (with a warning) instead of an empty
+/// set that would make Envoy drop its config. `grace` bounds how long after the last
+/// success they may be served; `None` keeps them until a conversion succeeds again.
+#[derive(Debug, Clone, Default)]
+struct LastKnownGood {
+    resources: Arc<DashMap<String, (Instant, Vec<prost_types::Any>)>>,
+    grace: Option<Duration>,
+}
+
+impl LastKnownGood {
+    fn record(&self, type_url: &str, resources: &[prost_types::Any]) {
+        self.resources.insert(type_url.to_string(), (Instant::now(), resources.to_vec()));
+    }
+
+    fn get(&self, type_url: &str) -> Option<Vec<prost_types::Any>> {
+        let entry = self.resources.get(type_url)?;
+        let (converted_at, resources) = entry.value();
+        match self.grace {
+            Some(grace) if converted_at.elapsed() > grace => None,
+            _ => Some(resources.clone()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SimpleXdsServer {
     store: ConfigStore,
     nonce_counter: Arc<AtomicU64>,
     version_counter: Arc<AtomicU64>,
     update_sender: broadcast::Sender<()>,
+    strict_resource_types: bool,
+    node_match_mode: String,
+    stream_counter: Arc<AtomicU64>,
+    clients: Arc<DashMap<u64, XdsClientInfo>>,
+    paused: PausedState,
+    last_known_good: LastKnownGood,
 }
 
 impl SimpleXdsServer {
@@ -29,9 +89,36 @@ impl SimpleXdsServer {
             nonce_counter: Arc::new(AtomicU64::new(0)),
             version_counter: Arc::new(AtomicU64::new(1)),
             update_sender,
+            strict_resource_types: false,
+            node_match_mode: "exact".to_string(),
+            stream_counter: Arc::new(AtomicU64::new(0)),
+            clients: Arc::new(DashMap::new()),
+            paused: Arc::new(RwLock::new(None)),
+            last_known_good: LastKnownGood::default(),
         }
     }
 
+    /// Reject unknown resource type URLs with UNIMPLEMENTED instead of answering with no resources
+    pub fn with_strict_resource_types(mut self, strict: bool) -> Self {
+        self.strict_resource_types = strict;
+        self
+    }
+
+    /// How a cluster's `node_id` label is compared to the requesting node: exact, prefix or regex
+    pub fn with_node_match_mode(mut self, node_match_mode: String) -> Self {
+        self.node_match_mode = node_match_mode;
+        self
+    }
+
+    /// Limit how long after the last successful conversion its resources stand in for a failing one
+    ///
+    /// `None` (the default) serves them until a conversion succeeds again; `Some(Duration::ZERO)`
+    /// disables the fallback.
+    pub fn with_last_known_good_grace(mut self, grace: Option<Duration>) -> Self {
+        self.last_known_good.grace = grace;
+        self
+    }
+
     #[allow(dead_code)]
     fn generate_nonce(&self) -> String {
         // Use simple incrementing integers like Go control plane
@@ -50,6 +137,144 @@ impl SimpleXdsServer {
         let _ = self.update_sender.send(());
         println!("📢 Broadcast update notification sent to all connected Envoy instances");
     }
+
+    /// Force every subscribed xDS stream to resend its resources
+    /// Bumps the version so streams that are already up to date still push
+    /// Returns the number of streams notified
+    pub fn force_push(&self) -> usize {
+        let streams = self.update_sender.receiver_count();
+        self.increment_version();
+        println!("🚀 Forced xDS push to {streams} stream(s)");
+        streams
+    }
+
+    /// Current resource version served to Envoy
+    pub fn current_version(&self) -> u64 {
+        self.version_counter.load(Ordering::SeqCst)
+    }
+
+    /// Subscribe to resource update notifications (one receiver per xDS stream)
+    pub fn subscribe_updates(&self) -> broadcast::Receiver<()> {
+        self.update_sender.subscribe()
+    }
+
+    /// Resources ADS streams serve for a type URL, applying the unknown-type policy and last known good fallback
+    pub fn resources_for_type(&self, type_url: &str) -> Result<Vec<prost_types::Any>, Status> {
+        resolve_resources(&self.store, type_url, self.strict_resource_types, &self.last_known_good)
+    }
+
+    /// Freeze xDS serving at the current version for maintenance
+    ///
+    /// Store changes still bump the version, but streams neither push them nor serve
+    /// them to new requests until `resume()`. Returns the frozen version.
+    pub fn pause(&self) -> u64 {
+        let mut paused = self.paused.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(snapshot) = paused.as_ref() {
+            return snapshot.version;
+        }
+
+        let version = self.current_version();
+        let resources = SUPPORTED_TYPE_URLS
+            .iter()
+            .map(|type_url| {
+                let resources =
+                    resolve_resources(&self.store, type_url, false, &self.last_known_good).unwrap_or_default();
+                (type_url.to_string(), resources)
+            })
+            .collect();
+        *paused = Some(Arc::new(PausedSnapshot { version, resources }));
+        println!("⏸️ xDS serving paused at version {version}");
+        version
+    }
+
+    /// Resume xDS serving and push any changes made while paused
+    pub fn resume(&self) -> u64 {
+        let was_paused = self.paused.write().unwrap_or_else(|e| e.into_inner()).take().is_some();
+        if was_paused {
+            println!("▶️ xDS serving resumed");
+            // Streams behind the current version push on the next notification
+            let _ = self.update_sender.send(());
+        }
+        self.current_version()
+    }
+
+    /// Clients with an open ADS stream, oldest connection first
+    pub fn connected_clients(&self) -> Vec<XdsClientInfo> {
+        let mut clients: Vec<XdsClientInfo> = self.clients.iter().map(|entry| entry.value().clone()).collect();
+        clients.sort_by_key(|client| client.connected_at);
+        clients
+    }
+}
+
+fn paused_snapshot(paused: &PausedState) -> Option<Arc<PausedSnapshot>> {
+    paused.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Track the stream's client; Envoy only sends its node on the first request of a stream
+fn record_client_request(
+    clients: &DashMap<u64, XdsClientInfo>,
+    stream_id: u64,
+    connected_at: DateTime<Utc>,
+    request: &DiscoveryRequest,
+) {
+    let mut client = clients.entry(stream_id).or_insert_with(|| XdsClientInfo {
+        node_id: String::new(),
+        node_cluster: String::new(),
+        connected_at,
+        last_request_type: None,
+        acked_versions: BTreeMap::new(),
+    });
+
+    if let Some(node) = request.node.as_ref().filter(|_| client.node_id.is_empty()) {
+        client.node_id = node.id.clone();
+        client.node_cluster = node.cluster.clone();
+    }
+    client.last_request_type = Some(request.type_url.clone());
+    if !request.nonce.is_empty() && request.error_detail.is_none() {
+        client.acked_versions.insert(request.type_url.clone(), request.version_info.clone());
+    }
+}
+
+/// Convert the store for `type_url`; unknown types get an empty set (lenient) or UNIMPLEMENTED (strict)
+///
+/// A failed conversion falls back to the last known good resources for the type, if any.
+fn resolve_resources(
+    store: &ConfigStore,
+    type_url: &str,
+    strict: bool,
+    last_known_good: &LastKnownGood,
+) -> Result<Vec<prost_types::Any>, Status> {
+    if strict && !is_supported_type_url(type_url) {
+        return Err(Status::unimplemented(format!("Resource type {type_url} not supported")));
+    }
+
+    match get_resources_by_type(type_url, store) {
+        Ok(resources) => {
+            println!("✅ ADS: Found {} resources for type: {type_url}", resources.len());
+            last_known_good.record(type_url, &resources);
+            Ok(resources)
+        }
+        Err(e) => {
+            println!("❌ ADS: Error getting resources for type {type_url}: {e}");
+            match last_known_good.get(type_url) {
+                Some(resources) => {
+                    println!("⚠️ ADS: Serving {} last known good resources for type: {type_url}", resources.len());
+                    Ok(resources)
+                }
+                None => Ok(vec![]),
+            }
+        }
+    }
+}
+
+/// Apply the configured HTTP/2 keepalive to the gRPC server that serves ADS
+pub fn with_xds_keepalive(
+    server: tonic::transport::Server,
+    keepalive: &XdsKeepaliveConfig,
+) -> tonic::transport::Server {
+    server
+        .http2_keepalive_interval(Some(keepalive.interval()))
+        .http2_keepalive_timeout(Some(keepalive.timeout()))
 }
 
 #[tonic::async_trait]
@@ -67,12 +292,21 @@ impl aggregated_discovery_service_server::AggregatedDiscoveryService for SimpleX
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let nonce_counter = self.nonce_counter.clone();
         let version_counter = self.version_counter.clone();
-        let store = self.store.clone();
-        let mut update_receiver = self.update_sender.subscribe();
+        // Resources are resolved through `resources_for_type` so every stream applies the same policy
+        let server = self.clone();
+        let mut update_receiver = self.subscribe_updates();
+        let clients = self.clients.clone();
+        let paused = self.paused.clone();
+        let stream_id = self.stream_counter.fetch_add(1, Ordering::SeqCst);
+        let connected_at = Utc::now();
 
         tokio::spawn(async move {
             let mut last_sent_version = 0;
             let mut pending_types: Vec<String> = Vec::new();
+            // Resource names requested per type URL; empty means every resource of that type
+            let mut subscriptions: HashMap<String, Vec<String>> = HashMap::new();
+            // Envoy only sends its node on the first request of a stream
+            let mut node_id = String::new();
 
             loop {
                 tokio::select! {
@@ -83,11 +317,20 @@ impl aggregated_discovery_service_server::AggregatedDiscoveryService for SimpleX
                                 println!("🔄 ADS: Received request for type: {}", request.type_url);
                                 println!("🔄 ADS: Version: '{}', Nonce: '{}'", request.version_info, request.nonce);
                                 println!("🔄 ADS: Resource names: {:?}", request.resource_names);
+                                record_client_request(&clients, stream_id, connected_at, &request);
+                                if let Some(node) = request.node.as_ref().filter(|_| node_id.is_empty()) {
+                                    node_id = node.id.clone();
+                                }
 
                                 // Check if this is an ACK/NACK (has our previous nonce) or initial request
                                 let is_ack_or_nack = !request.nonce.is_empty();
 
-                                if is_ack_or_nack {
+                                // Every request carries the full subscription, so a changed name list needs a fresh response
+                                let subscription_changed = subscriptions
+                                    .insert(request.type_url.clone(), request.resource_names.clone())
+                                    .is_some_and(|previous| previous != request.resource_names);
+
+                                if is_ack_or_nack && !subscription_changed {
                                     if let Some(error_detail) = &request.error_detail {
                                         println!("❌ ADS: This is a NACK for nonce: {} - Error: {}", request.nonce, error_detail.message);
                                         // Handle NACK - could resend previous version or fix config
@@ -100,27 +343,35 @@ impl aggregated_discovery_service_server::AggregatedDiscoveryService for SimpleX
                                     continue;
                                 }
 
-                                // Track what type this client is interested in
-                                if !pending_types.contains(&request.type_url) {
-                                    pending_types.push(request.type_url.clone());
-                                }
-
                                 println!("📨 ADS: This is an initial request, sending response");
 
+                                // While paused, serve the frozen snapshot instead of the live store
+                                let frozen = paused_snapshot(&paused);
+
                                 // Get actual resources from the store using the conversion module
-                                let resources = match get_resources_by_type(&request.type_url, &store) {
-                                    Ok(resources) => {
-                                        println!("✅ ADS: Found {} resources for type: {}", resources.len(), request.type_url);
-                                        resources
-                                    }
-                                    Err(e) => {
-                                        println!("❌ ADS: Error getting resources for type {}: {}", request.type_url, e);
-                                        vec![]
-                                    }
+                                let resources = match frozen.as_ref().and_then(|snapshot| snapshot.resources.get(&request.type_url)) {
+                                    Some(resources) => resources.clone(),
+                                    None => match server.resources_for_type(&request.type_url) {
+                                        Ok(resources) => resources,
+                                        Err(status) => {
+                                            println!("❌ ADS: Rejecting unsupported resource type: {}", request.type_url);
+                                            let _ = tx.send(Err(status)).await;
+                                            break;
+                                        }
+                                    },
                                 };
+                                let resources = filter_by_resource_names(&request.type_url, resources, &request.resource_names);
+                                let resources = filter_by_node(&request.type_url, resources, &server.store, &server.node_match_mode, &node_id);
+
+                                // Track what type this client is interested in
+                                if !pending_types.contains(&request.type_url) {
+                                    pending_types.push(request.type_url.clone());
+                                }
 
                                 let response_nonce = nonce_counter.fetch_add(1, Ordering::SeqCst).to_string();
-                                let current_version = version_counter.load(Ordering::SeqCst);
+                                let current_version = frozen
+                                    .as_ref()
+                                    .map_or_else(|| version_counter.load(Ordering::SeqCst), |snapshot| snapshot.version);
                                 last_sent_version = current_version;
 
                                 let response = DiscoveryResponse {
@@ -154,6 +405,11 @@ impl aggregated_discovery_service_server::AggregatedDiscoveryService for SimpleX
 
                     // Handle update notifications (when resources change)
                     _ = update_receiver.recv() => {
+                        if paused_snapshot(&paused).is_some() {
+                            println!("⏸️ ADS: xDS serving paused, holding update");
+                            continue;
+                        }
+
                         let current_version = version_counter.load(Ordering::SeqCst);
 
                         // Only send updates if version has changed and we have types to update
@@ -162,16 +418,11 @@ impl aggregated_discovery_service_server::AggregatedDiscoveryService for SimpleX
 
                             // Send updates for all types this client is interested in
                             for type_url in &pending_types {
-                                let resources = match get_resources_by_type(type_url, &store) {
-                                    Ok(resources) => {
-                                        println!("✅ ADS: Found {} resources for type: {type_url}", resources.len());
-                                        resources
-                                    }
-                                    Err(e) => {
-                                        println!("❌ ADS: Error getting resources for type {type_url}: {e}");
-                                        vec![]
-                                    }
-                                };
+                                // Only types accepted on the initial request are tracked, so this cannot fail
+                                let resources = server.resources_for_type(type_url).unwrap_or_default();
+                                let resource_names = subscriptions.get(type_url).map(Vec::as_slice).unwrap_or_default();
+                                let resources = filter_by_resource_names(type_url, resources, resource_names);
+                                let resources = filter_by_node(type_url, resources, &server.store, &server.node_match_mode, &node_id);
 
                                 let response_nonce = nonce_counter.fetch_add(1, Ordering::SeqCst).to_string();
                                 let response = DiscoveryResponse {
@@ -196,6 +447,8 @@ impl aggregated_discovery_service_server::AggregatedDiscoveryService for SimpleX
                     }
                 }
             }
+
+            clients.remove(&stream_id);
         });
 
         Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
@@ -7,8 +7,9 @@ use serde_json::json;
 use tower::ServiceExt;
 
 use envoy_control_plane::api::routes::create_router;
-use envoy_control_plane::auth::JwtKeys;
+use envoy_control_plane::auth::{create_jwt_token, Claims, JwtKeys};
 use envoy_control_plane::config::{AuthenticationConfig, AppConfig, *};
+use envoy_control_plane::config::validation::MIN_TEST_BCRYPT_COST;
 use envoy_control_plane::rbac::RbacEnforcer;
 use envoy_control_plane::storage::ConfigStore;
 use envoy_control_plane::xds::simple_server::SimpleXdsServer;
@@ -23,11 +24,18 @@ fn create_test_config() -> AppConfig {
                 rest_port: 8080,
                 xds_port: 18000,
                 host: "0.0.0.0".to_string(),
+                xds_keepalive: XdsKeepaliveConfig::default(),
+                strict_resource_types: false,
+                node_match_mode: "exact".to_string(),
+                reject_unknown_fields: false,
+                last_known_good_grace_seconds: None,
+                read_only: false,
             },
             tls: TlsConfig {
                 cert_path: "./certs/server.crt".to_string(),
                 key_path: "./certs/server.key".to_string(),
                 enabled: true,
+                min_tls_version: "1.2".to_string(),
             },
             logging: LoggingConfig {
                 level: "info".to_string(),
@@ -50,7 +58,8 @@ fn create_test_config() -> AppConfig {
                 jwt_secret: "test-secret-1234567890abcdefghijklmnopqrstuvwxyz".to_string(),
                 jwt_expiry_hours: 24,
                 jwt_issuer: "envoy-control-plane-test".to_string(),
-                password_hash_cost: 8,
+                password_hash_cost: MIN_TEST_BCRYPT_COST,
+                password_hash_algorithm: "bcrypt".to_string(),
             },
             storage: StorageConfig::default(),
             http_filters: HttpFiltersFeatureConfig::default(),
@@ -64,12 +73,30 @@ fn create_test_config() -> AppConfig {
             listener: ListenerConfig {
                 binding_address: "0.0.0.0".to_string(),
                 default_port: 10000,
+                forwarded_headers: ForwardedHeadersConfig::default(),
+                http3: false,
+                downstream_tls: None,
+                default_request_timeout_seconds: None,
+                per_connection_buffer_limit_bytes: None,
+                exact_connection_balance: false,
+                drain_type: "DEFAULT".to_string(),
+                drain_timeout_seconds: None,
+                server_header_transformation: "OVERWRITE".to_string(),
+                server_name: None,
+                suppress_envoy_headers: false,
+                access_log: None,
+                additional_listeners: vec![],
+                allow_early_data: None,
+                local_reply: None,
             },
             cluster: ClusterConfig {
                 connect_timeout_seconds: 5,
                 discovery_type: "STRICT_DNS".to_string(),
                 dns_lookup_family: "V4_ONLY".to_string(),
                 default_protocol: "TCP".to_string(),
+                dns_resolution_check: DnsResolutionCheckConfig::default(),
+                max_buffer_limit_bytes: 16 * 1024 * 1024,
+                reject_duplicate_endpoints: false,
             },
             naming: NamingConfig {
                 listener_name: "listener_0".to_string(),
@@ -104,7 +131,8 @@ async fn create_auth_enabled_app() -> (Router, ConfigStore) {
         jwt_secret: "test-auth-secret-key".to_string(),
         jwt_expiry_hours: 1,
         jwt_issuer: "test-auth-issuer".to_string(),
-        password_hash_cost: 4, // Low cost for fast tests
+        password_hash_cost: MIN_TEST_BCRYPT_COST,
+        password_hash_algorithm: "bcrypt".to_string(),
     };
     let jwt_keys = JwtKeys::new(auth_config);
     
@@ -126,7 +154,8 @@ async fn create_auth_disabled_app() -> (Router, ConfigStore) {
         jwt_secret: "test-secret-key".to_string(),
         jwt_expiry_hours: 1,
         jwt_issuer: "test-issuer".to_string(),
-        password_hash_cost: 4,
+        password_hash_cost: MIN_TEST_BCRYPT_COST,
+        password_hash_algorithm: "bcrypt".to_string(),
     };
     let jwt_keys = JwtKeys::new(auth_config);
     let rbac = RbacEnforcer::new_simple().await.unwrap();
@@ -582,6 +611,7 @@ async fn test_read_routes_work_with_and_without_auth() {
         cluster_name: "test-cluster".to_string(),
         prefix_rewrite: None,
         http_methods: None,
+        ..Default::default()
     };
     store.add_route(route);
 
@@ -704,4 +734,185 @@ async fn test_logout_endpoint() {
         .unwrap();
     let body_str = std::str::from_utf8(&body).unwrap();
     assert!(body_str.contains("Logout successful"));
-}
\ No newline at end of file
+}
+// ===========================================
+// Runtime Authentication Toggle Tests
+// ===========================================
+
+/// Token for `user_id` signed with the config `create_auth_enabled_app` / `create_auth_disabled_app` use
+fn token_for(user_id: &str, secret: &str, issuer: &str) -> String {
+    let config = AuthenticationConfig {
+        enabled: true,
+        jwt_secret: secret.to_string(),
+        jwt_expiry_hours: 1,
+        jwt_issuer: issuer.to_string(),
+        password_hash_cost: MIN_TEST_BCRYPT_COST,
+        password_hash_algorithm: "bcrypt".to_string(),
+    };
+    create_jwt_token(user_id.to_string(), user_id.to_string(), &config).unwrap()
+}
+
+fn toggle_request(enabled: bool, token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .uri("/admin/auth/toggle")
+        .method("POST")
+        .header("content-type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header("Authorization", format!("Bearer {}", token));
+    }
+    builder
+        .body(Body::from(json!({ "enabled": enabled }).to_string()))
+        .unwrap()
+}
+
+fn protected_request() -> Request<Body> {
+    Request::builder()
+        .uri("/admin/xds-clients")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_can_disable_authentication_at_runtime() {
+    let (app, _store) = create_auth_enabled_app().await;
+    let admin_token = token_for("admin", "test-auth-secret-key", "test-auth-issuer");
+
+    let response = app.clone().oneshot(protected_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(toggle_request(false, Some(&admin_token)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Protected routes no longer need a token
+    let response = app.oneshot(protected_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_admin_can_enable_authentication_at_runtime() {
+    let (app, _store) = create_auth_disabled_app().await;
+    let admin_token = token_for("admin", "test-secret-key", "test-issuer");
+
+    let response = app.clone().oneshot(protected_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(toggle_request(true, Some(&admin_token)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app.oneshot(protected_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_enabling_authentication_requires_an_authorized_caller() {
+    let (app, _store) = create_auth_disabled_app().await;
+
+    // No token: enabling would lock the caller out
+    let response = app.clone().oneshot(toggle_request(true, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Token signed with another secret
+    let forged = token_for("admin", "some-other-secret", "test-issuer");
+    let response = app.clone().oneshot(toggle_request(true, Some(&forged))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Valid token without system write permission
+    let user_token = token_for("user", "test-secret-key", "test-issuer");
+    let response = app.clone().oneshot(toggle_request(true, Some(&user_token))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Enforcement is still off
+    let response = app.clone().oneshot(protected_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Disabling needs no token
+    let response = app.oneshot(toggle_request(false, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_authentication_can_be_reenabled_after_disabling_token_expires() {
+    let (app, _store) = create_auth_enabled_app().await;
+    let admin_token = token_for("admin", "test-auth-secret-key", "test-auth-issuer");
+
+    let response = app
+        .clone()
+        .oneshot(toggle_request(false, Some(&admin_token)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The token used to disable enforcement has since expired
+    let now = chrono::Utc::now().timestamp() as usize;
+    let expired_claims = Claims {
+        sub: "admin".to_string(),
+        exp: now - 3600,
+        iat: now - 7200,
+        iss: "test-auth-issuer".to_string(),
+        username: "admin".to_string(),
+    };
+    let expired_token = jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &expired_claims,
+        &jsonwebtoken::EncodingKey::from_secret(b"test-auth-secret-key"),
+    )
+    .unwrap();
+    let response = app
+        .clone()
+        .oneshot(toggle_request(true, Some(&expired_token)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Logging in again still works while enforcement is off
+    let login_data = json!({
+        "username": "admin",
+        "password": "secure-admin-123"
+    });
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/auth/login")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(login_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let auth_cookie = response
+        .headers()
+        .get("set-cookie")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .expect("login should set the auth cookie")
+        .to_string();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/admin/auth/toggle")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("cookie", auth_cookie)
+                .body(Body::from(json!({ "enabled": true }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app.oneshot(protected_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
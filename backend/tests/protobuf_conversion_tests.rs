@@ -10,13 +10,16 @@ async fn test_cluster_to_proto_conversion() {
             Endpoint {
                 host: "127.0.0.1".to_string(),
                 port: 8080,
+                ..Default::default()
             },
             Endpoint {
                 host: "127.0.0.1".to_string(),
                 port: 8081,
+                ..Default::default()
             },
         ],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     let proto_clusters = ProtoConverter::clusters_to_proto(vec![cluster]).unwrap();
@@ -50,6 +53,7 @@ async fn test_route_to_proto_conversion() {
         cluster_name: "user-service".to_string(),
         prefix_rewrite: Some("/users".to_string()),
         http_methods: None,
+        ..Default::default()
     };
 
     let proto_routes = ProtoConverter::routes_to_proto(vec![route]).unwrap();
@@ -101,6 +105,7 @@ async fn test_multiple_routes_conversion() {
             cluster_name: "user-service".to_string(),
             prefix_rewrite: Some("/users".to_string()),
             http_methods: None,
+            ..Default::default()
         },
         Route {
             name: "route2".to_string(),
@@ -108,6 +113,7 @@ async fn test_multiple_routes_conversion() {
             cluster_name: "order-service".to_string(),
             prefix_rewrite: None,
             http_methods: None,
+            ..Default::default()
         },
     ];
 
@@ -129,8 +135,10 @@ async fn test_cluster_with_single_endpoint() {
         endpoints: vec![Endpoint {
             host: "192.168.1.100".to_string(),
             port: 3000,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     let proto_clusters = ProtoConverter::clusters_to_proto(vec![cluster]).unwrap();
@@ -156,6 +164,7 @@ async fn test_route_without_prefix_rewrite() {
         cluster_name: "health-service".to_string(),
         prefix_rewrite: None,
         http_methods: None,
+        ..Default::default()
     };
 
     let proto_routes = ProtoConverter::routes_to_proto(vec![route]).unwrap();
@@ -180,6 +189,7 @@ async fn test_route_with_prefix_rewrite() {
         cluster_name: "health-service".to_string(),
         prefix_rewrite: Some("/health".to_string()),
         http_methods: None,
+        ..Default::default()
     };
 
     let proto_routes = ProtoConverter::routes_to_proto(vec![route]).unwrap();
@@ -204,16 +214,20 @@ async fn test_multiple_clusters_conversion() {
             endpoints: vec![Endpoint {
                 host: "127.0.0.1".to_string(),
                 port: 8080,
+                ..Default::default()
             }],
             lb_policy: None, // Use default
+            ..Default::default()
         },
         Cluster {
             name: "service2".to_string(),
             endpoints: vec![Endpoint {
                 host: "127.0.0.1".to_string(),
                 port: 8081,
+                ..Default::default()
             }],
             lb_policy: None, // Use default
+            ..Default::default()
         },
     ];
 
@@ -9,8 +9,9 @@ use tower::ServiceExt;
 use envoy_control_plane::api::routes::create_router;
 use envoy_control_plane::auth::JwtKeys;
 use envoy_control_plane::config::{AuthenticationConfig, AppConfig, *};
+use envoy_control_plane::config::validation::MIN_TEST_BCRYPT_COST;
 use envoy_control_plane::rbac::RbacEnforcer;
-use envoy_control_plane::storage::{models::*, ConfigStore};
+use envoy_control_plane::storage::{models::*, ConfigStore, StorageError};
 use envoy_control_plane::xds::simple_server::SimpleXdsServer;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -23,18 +24,30 @@ fn create_test_config() -> AppConfig {
                 rest_port: 8080,
                 xds_port: 18000,
                 host: "0.0.0.0".to_string(),
+                xds_keepalive: XdsKeepaliveConfig::default(),
+                strict_resource_types: false,
+                node_match_mode: "exact".to_string(),
+                reject_unknown_fields: false,
+                last_known_good_grace_seconds: None,
+                read_only: false,
             },
             tls: TlsConfig {
                 cert_path: "./certs/server.crt".to_string(),
                 key_path: "./certs/server.key".to_string(),
                 enabled: true,
+                min_tls_version: "1.2".to_string(),
             },
             logging: LoggingConfig {
                 level: "info".to_string(),
             },
             load_balancing: LoadBalancingConfig {
                 envoy_version: "1.24".to_string(),
-                available_policies: vec!["ROUND_ROBIN".to_string()],
+                available_policies: vec![
+                    "ROUND_ROBIN".to_string(),
+                    "LEAST_REQUEST".to_string(),
+                    "RANDOM".to_string(),
+                    "RING_HASH".to_string(),
+                ],
                 default_policy: "ROUND_ROBIN".to_string(),
             },
             http_methods: HttpMethodsConfig {
@@ -50,7 +63,8 @@ fn create_test_config() -> AppConfig {
                 jwt_secret: "test-secret-1234567890abcdefghijklmnopqrstuvwxyz".to_string(),
                 jwt_expiry_hours: 24,
                 jwt_issuer: "envoy-control-plane-test".to_string(),
-                password_hash_cost: 8,  // Lower cost for faster tests
+                password_hash_cost: MIN_TEST_BCRYPT_COST,
+                password_hash_algorithm: "bcrypt".to_string(),
             },
             storage: StorageConfig::default(),
             http_filters: HttpFiltersFeatureConfig::default(),
@@ -64,12 +78,30 @@ fn create_test_config() -> AppConfig {
             listener: ListenerConfig {
                 binding_address: "0.0.0.0".to_string(),
                 default_port: 10000,
+                forwarded_headers: ForwardedHeadersConfig::default(),
+                http3: false,
+                downstream_tls: None,
+                default_request_timeout_seconds: None,
+                per_connection_buffer_limit_bytes: None,
+                exact_connection_balance: false,
+                drain_type: "DEFAULT".to_string(),
+                drain_timeout_seconds: None,
+                server_header_transformation: "OVERWRITE".to_string(),
+                server_name: None,
+                suppress_envoy_headers: false,
+                access_log: None,
+                additional_listeners: vec![],
+                allow_early_data: None,
+                local_reply: None,
             },
             cluster: ClusterConfig {
                 connect_timeout_seconds: 5,
                 discovery_type: "STRICT_DNS".to_string(),
                 dns_lookup_family: "V4_ONLY".to_string(),
                 default_protocol: "TCP".to_string(),
+                dns_resolution_check: DnsResolutionCheckConfig::default(),
+                max_buffer_limit_bytes: 16 * 1024 * 1024,
+                reject_duplicate_endpoints: false,
             },
             naming: NamingConfig {
                 listener_name: "listener_0".to_string(),
@@ -95,7 +127,12 @@ fn create_test_config() -> AppConfig {
 
 /// Helper function to create a test app with fresh storage
 async fn create_test_app() -> (Router, ConfigStore) {
-    let store = ConfigStore::new();
+    create_test_app_with_config(create_test_config()).await
+}
+
+/// Create test app with a customized configuration
+async fn create_test_app_with_config(config: AppConfig) -> (Router, ConfigStore) {
+    let store = ConfigStore::with_config(config.control_plane.storage.clone());
     let xds_server = SimpleXdsServer::new(store.clone());
     
     // Create auth components with authentication DISABLED for tests
@@ -104,14 +141,15 @@ async fn create_test_app() -> (Router, ConfigStore) {
         jwt_secret: "test-secret-key".to_string(),
         jwt_expiry_hours: 1,
         jwt_issuer: "test-issuer".to_string(),
-        password_hash_cost: 4,
+        password_hash_cost: MIN_TEST_BCRYPT_COST,
+        password_hash_algorithm: "bcrypt".to_string(),
     };
     let jwt_keys = JwtKeys::new(auth_config);
     
     // Create simple RBAC enforcer (not used since auth is disabled)  
     let rbac = RbacEnforcer::new_simple().await.unwrap();
     
-    let config = Arc::new(create_test_config());
+    let config = Arc::new(config);
     let app = create_router(store.clone(), xds_server, jwt_keys, rbac, config);
     (app, store)
 }
@@ -247,8 +285,10 @@ async fn test_delete_cluster() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     let cluster_name = cluster.name.clone();
@@ -284,6 +324,7 @@ async fn test_delete_route() {
         cluster_name: "test-cluster".to_string(),
         prefix_rewrite: Some("/test".to_string()),
         http_methods: None,
+        ..Default::default()
     };
 
     let route_name = route.name.clone();
@@ -586,6 +627,92 @@ async fn test_create_cluster_without_lb_policy_uses_default() {
     assert!(body_str.contains("success"));
 }
 
+#[tokio::test]
+async fn test_create_cluster_with_invalid_regex_node_tag_rejected() {
+    let mut config = create_test_config();
+    config.control_plane.server.node_match_mode = "regex".to_string();
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster_data = json!({
+        "name": "tagged-service",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}],
+        "labels": {"node_id": "prod-["}
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(std::str::from_utf8(&body).unwrap().contains("node_id"));
+    assert!(store.get_cluster("tagged-service").is_err());
+}
+
+#[tokio::test]
+async fn test_choice_count_accepted_when_default_policy_is_least_request() {
+    let mut config = create_test_config();
+    config.control_plane.load_balancing.default_policy = "LEAST_REQUEST".to_string();
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster_data = json!({
+        "name": "default-least-request",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}],
+        "choice_count": 4
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(store.get_cluster("default-least-request").unwrap().choice_count, Some(4));
+}
+
+#[tokio::test]
+async fn test_choice_count_rejected_when_default_policy_is_round_robin() {
+    let mut config = create_test_config();
+    config.control_plane.load_balancing.default_policy = "ROUND_ROBIN".to_string();
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster_data = json!({
+        "name": "default-round-robin",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}],
+        "choice_count": 4
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(store.get_cluster("default-round-robin").is_err());
+}
+
 // Tests for HTTP method routing functionality
 #[tokio::test]
 async fn test_create_route_with_single_http_method() {
@@ -825,6 +952,7 @@ async fn test_update_route_with_http_methods() {
         cluster_name: "user-service".to_string(),
         prefix_rewrite: None,
         http_methods: Some(vec!["GET".to_string()]),
+        ..Default::default()
     };
 
     let route_name = route.name.clone();
@@ -874,6 +1002,7 @@ async fn test_update_route_remove_http_methods() {
         cluster_name: "data-service".to_string(),
         prefix_rewrite: Some("/v1/data".to_string()),
         http_methods: Some(vec!["GET".to_string(), "POST".to_string()]),
+        ..Default::default()
     };
 
     let route_name = route.name.clone();
@@ -916,6 +1045,7 @@ async fn test_update_route_with_invalid_http_method() {
         cluster_name: "test-service".to_string(),
         prefix_rewrite: None,
         http_methods: Some(vec!["GET".to_string()]),
+        ..Default::default()
     };
 
     let route_name = route.name.clone();
@@ -990,6 +1120,7 @@ async fn test_update_route_change_cluster_and_path() {
         cluster_name: "old-service".to_string(),
         prefix_rewrite: Some("/old".to_string()),
         http_methods: Some(vec!["GET".to_string()]),
+        ..Default::default()
     };
 
     let route_name = route.name.clone();
@@ -1026,3 +1157,1407 @@ async fn test_update_route_change_cluster_and_path() {
     // Ensure the ID remains the same
     assert_eq!(updated_route.name, route_name);
 }
+
+#[tokio::test]
+async fn test_create_route_filters_association() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "filtered-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+
+    let route = Route::new(
+        "filtered-route".to_string(),
+        "/api/filtered".to_string(),
+        "filtered-service".to_string(),
+        None,
+    );
+    store.add_route(route).unwrap();
+
+    let filter = HttpFilter::new(
+        "api-rate-limit".to_string(),
+        "rate_limit".to_string(),
+        json!({"requests_per_unit": 100, "unit": "minute"}),
+    );
+    store
+        .add_http_filter(filter, &HttpFiltersFeatureConfig::default().supported_filters)
+        .unwrap();
+
+    let association = json!({
+        "filter_names": ["api-rate-limit"]
+    });
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/routes/filtered-route/filters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(association.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let route_filters = store.get_route_filters("filtered-route").unwrap();
+    assert_eq!(route_filters.filter_names, vec!["api-rate-limit".to_string()]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/filtered-route/filters")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("api-rate-limit"));
+}
+
+#[tokio::test]
+async fn test_create_route_filters_with_nonexistent_filter() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "filtered-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+
+    let route = Route::new(
+        "filtered-route".to_string(),
+        "/api/filtered".to_string(),
+        "filtered-service".to_string(),
+        None,
+    );
+    store.add_route(route).unwrap();
+
+    let association = json!({
+        "filter_names": ["missing-filter"]
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/filtered-route/filters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(association.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("missing-filter"));
+    assert!(store.get_route_filters("filtered-route").is_none());
+}
+
+#[tokio::test]
+async fn test_update_route_filters_with_nonexistent_filter_keeps_existing_association() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_route(Route::new(
+            "filtered-route".to_string(),
+            "/api/filtered".to_string(),
+            "filtered-service".to_string(),
+            None,
+        ))
+        .unwrap();
+    store
+        .add_http_filter(
+            HttpFilter::new(
+                "api-rate-limit".to_string(),
+                "rate_limit".to_string(),
+                json!({"requests_per_unit": 100, "unit": "minute"}),
+            ),
+            &HttpFiltersFeatureConfig::default().supported_filters,
+        )
+        .unwrap();
+    store
+        .add_route_filters(RouteFilters::new("filtered-route".to_string(), vec!["api-rate-limit".to_string()]))
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/filtered-route/filters")
+                .method("PUT")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"filter_names": ["missing-filter"]}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let route_filters = store.get_route_filters("filtered-route").unwrap();
+    assert_eq!(route_filters.filter_names, vec!["api-rate-limit".to_string()]);
+}
+
+#[tokio::test]
+async fn test_update_route_filters_without_association_returns_not_found() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_route(Route::new(
+            "filtered-route".to_string(),
+            "/api/filtered".to_string(),
+            "filtered-service".to_string(),
+            None,
+        ))
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/filtered-route/filters")
+                .method("PUT")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"filter_names": []}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(store.get_route_filters("filtered-route").is_none());
+}
+
+#[tokio::test]
+async fn test_changelog_records_cluster_create_and_delete() {
+    let (app, _store) = create_test_app().await;
+
+    let cluster = json!({
+        "name": "changelog-service",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}]
+    });
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/clusters/changelog-service")
+                .method("DELETE")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/changelog")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let entries = body["data"].as_array().unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["resource_type"], "Cluster");
+    assert_eq!(entries[0]["resource_name"], "changelog-service");
+    assert_eq!(entries[0]["action"], "created");
+    assert_eq!(entries[1]["resource_name"], "changelog-service");
+    assert_eq!(entries[1]["action"], "deleted");
+}
+
+#[tokio::test]
+async fn test_config_diff_reports_changed_cluster_endpoint() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "diff-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+    store
+        .add_route(Route::new(
+            "diff-route".to_string(),
+            "/diff".to_string(),
+            "diff-service".to_string(),
+            None,
+        ))
+        .unwrap();
+
+    // Desired state is the current store with a single endpoint port changed
+    let mut desired = store.snapshot();
+    desired.clusters[0].endpoints[0].port = 9090;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/config/diff")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&desired).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let diff = &body["data"];
+
+    assert!(diff["created"].as_array().unwrap().is_empty());
+    assert!(diff["deleted"].as_array().unwrap().is_empty());
+
+    let updated = diff["updated"].as_array().unwrap();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0]["resource_type"], "Cluster");
+    assert_eq!(updated[0]["name"], "diff-service");
+
+    let changes = updated[0]["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["field"], "endpoints");
+    assert_eq!(changes[0]["current"][0]["port"], 8080);
+    assert_eq!(changes[0]["desired"][0]["port"], 9090);
+
+    // Nothing was applied
+    assert_eq!(store.get_cluster("diff-service").unwrap().endpoints[0].port, 8080);
+}
+
+#[tokio::test]
+async fn test_create_cluster_rejects_unresolvable_host_when_dns_check_enabled() {
+    let mut config = create_test_config();
+    config.envoy_generation.cluster.dns_resolution_check = DnsResolutionCheckConfig {
+        enabled: true,
+        reject_unresolvable: true,
+        timeout_ms: 500,
+    };
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster = json!({
+        "name": "typo-service",
+        "endpoints": [
+            {"host": "127.0.0.1", "port": 8080},
+            {"host": "no-such-host.invalid", "port": 8080}
+        ]
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("no-such-host.invalid"));
+    assert!(!body_str.contains("127.0.0.1"));
+    assert!(store.get_cluster("typo-service").is_err());
+}
+
+#[tokio::test]
+async fn test_cluster_capacity_limit_returns_capacity_error() {
+    let mut config = create_test_config();
+    config.control_plane.storage.limits.max_clusters = 2;
+    let store = ConfigStore::with_config(config.control_plane.storage.clone());
+
+    for name in ["capacity-a", "capacity-b"] {
+        store
+            .add_cluster(Cluster::new(
+                name.to_string(),
+                vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+            ))
+            .unwrap();
+    }
+
+    let result = store.add_cluster(Cluster::new(
+        "capacity-c".to_string(),
+        vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+    ));
+    match result {
+        Err(StorageError::CapacityExceeded { current, limit }) => {
+            assert_eq!(current, 2);
+            assert_eq!(limit, 2);
+        }
+        other => panic!("expected CapacityExceeded, got {:?}", other),
+    }
+    assert!(store.get_cluster("capacity-c").is_err());
+}
+
+#[tokio::test]
+async fn test_create_cluster_beyond_limit_reports_capacity() {
+    let mut config = create_test_config();
+    config.control_plane.storage.limits.max_clusters = 1;
+    let (app, _store) = create_test_app_with_config(config).await;
+
+    let mut statuses = Vec::new();
+    let mut last_body = serde_json::Value::Null;
+    for name in ["limited-a", "limited-b"] {
+        let cluster = json!({
+            "name": name,
+            "endpoints": [{"host": "127.0.0.1", "port": 8080}]
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/clusters")
+                    .method("POST")
+                    .header("content-type", "application/json")
+                    .body(Body::from(cluster.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        statuses.push(response.status());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        last_body = serde_json::from_slice(&body).unwrap();
+    }
+
+    assert_eq!(statuses[0], StatusCode::OK);
+    assert_ne!(statuses[1], StatusCode::OK);
+    assert!(last_body.to_string().contains("capacity exceeded: 1/1"));
+}
+
+
+#[tokio::test]
+async fn test_list_clusters_filtered_by_label_selector() {
+    let (app, _store) = create_test_app().await;
+
+    for (name, team) in [("payments-api", "payments"), ("search-api", "search")] {
+        let cluster = json!({
+            "name": name,
+            "endpoints": [{"host": "127.0.0.1", "port": 8080}],
+            "labels": {"team": team, "env": "prod"}
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/clusters")
+                    .method("POST")
+                    .header("content-type", "application/json")
+                    .body(Body::from(cluster.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/clusters?label.team=payments&label.env=prod")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let clusters = body["data"].as_array().unwrap();
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0]["name"], "payments-api");
+    assert_eq!(clusters[0]["labels"]["team"], "payments");
+
+    // Without a selector every cluster is returned
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_create_route_with_invalid_label_rejected() {
+    let (app, _store) = create_test_app().await;
+
+    let route = json!({
+        "name": "labeled-route",
+        "path": "/labeled",
+        "cluster_name": "labeled-service",
+        "labels": {"team name": "payments"}
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(route.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_events_stream_pushes_cluster_creation() {
+    use tokio_stream::StreamExt;
+
+    let (app, _store) = create_test_app().await;
+
+    // Connect the SSE reader before mutating the store
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/events")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/event-stream"
+    );
+    let mut events = response.into_body().into_data_stream();
+
+    let cluster = json!({
+        "name": "sse-service",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}]
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), events.next())
+        .await
+        .expect("timed out waiting for SSE event")
+        .unwrap()
+        .unwrap();
+    let frame = String::from_utf8(chunk.to_vec()).unwrap();
+
+    assert!(frame.starts_with("event: change\n"));
+    let data = frame
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .unwrap();
+    let event: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(event["type"], "Cluster");
+    assert_eq!(event["action"], "created");
+    assert_eq!(event["name"], "sse-service");
+}
+
+#[tokio::test]
+async fn test_simulate_request_validation_denies_missing_header() {
+    let (app, store) = create_test_app().await;
+
+    let filter = HttpFilter::new(
+        "api-key-required".to_string(),
+        "request_validation".to_string(),
+        json!({
+            "allowed_methods": ["GET", "POST"],
+            "required_headers": ["x-api-key"]
+        }),
+    );
+    store
+        .add_http_filter(filter, &HttpFiltersFeatureConfig::default().supported_filters)
+        .unwrap();
+
+    let simulate = |headers: serde_json::Value| {
+        Request::builder()
+            .uri("/http-filters/api-key-required/simulate")
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({"method": "GET", "path": "/api/orders", "headers": headers}).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(simulate(json!({}))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["allowed"], false);
+    assert_eq!(body["data"]["failed_rule"], "required_headers");
+
+    let response = app
+        .oneshot(simulate(json!({"x-api-key": "secret"})))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["allowed"], true);
+    assert!(body["data"].get("failed_rule").is_none());
+}
+
+#[tokio::test]
+async fn test_explain_route_reports_matching_route() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_route(Route {
+            name: "orders-writes".to_string(),
+            path: "/api/orders".to_string(),
+            cluster_name: "orders-cluster".to_string(),
+            http_methods: Some(vec!["POST".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+    let explain = |method: &str| {
+        Request::builder()
+            .uri("/routes/explain")
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({"method": method, "path": "/api/orders/42", "headers": {}}).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(explain("POST")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["matched_route"], "orders-writes");
+    assert_eq!(body["data"]["reasons"][1], "method POST is one of [POST]");
+
+    let response = app.oneshot(explain("GET")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["data"].get("matched_route").is_none());
+}
+
+#[tokio::test]
+async fn test_config_status_reports_only_the_invalid_filter() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "status-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+    // Accepted by the store, but no conversion strategy exists for this type
+    store
+        .add_http_filter(
+            HttpFilter::new(
+                "legacy-filter".to_string(),
+                "legacy_compression".to_string(),
+                json!({"level": 5}),
+            ),
+            &["legacy_compression".to_string()],
+        )
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/config-status")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["failing"], 1);
+
+    let resources = body["data"]["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 2);
+    let failing: Vec<&serde_json::Value> = resources.iter().filter(|r| r["ok"] == false).collect();
+    assert_eq!(failing.len(), 1);
+    assert_eq!(failing[0]["resource_type"], "HttpFilter");
+    assert_eq!(failing[0]["name"], "legacy-filter");
+    assert!(failing[0]["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_json_patch_replaces_route_path() {
+    let (app, store) = create_test_app().await;
+
+    let route = Route {
+        name: "patched-route".to_string(),
+        path: "/api/v1/orders".to_string(),
+        cluster_name: "orders-service".to_string(),
+        ..Default::default()
+    };
+    store.add_route(route).unwrap();
+
+    let patch = json!([
+        {"op": "test", "path": "/path", "value": "/api/v1/orders"},
+        {"op": "replace", "path": "/path", "value": "/api/v2/orders"}
+    ]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/patched-route")
+                .method("PATCH")
+                .header("content-type", "application/json-patch+json")
+                .body(Body::from(patch.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let patched = store.get_route("patched-route").unwrap();
+    assert_eq!(patched.path, "/api/v2/orders");
+    assert_eq!(patched.cluster_name, "orders-service");
+}
+
+#[tokio::test]
+async fn test_json_patch_accepts_content_type_with_charset() {
+    let (app, store) = create_test_app().await;
+
+    let route = Route {
+        name: "patched-route".to_string(),
+        path: "/api/v1/orders".to_string(),
+        cluster_name: "orders-service".to_string(),
+        ..Default::default()
+    };
+    store.add_route(route).unwrap();
+
+    let patch = json!([{"op": "replace", "path": "/path", "value": "/api/v2/orders"}]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/patched-route")
+                .method("PATCH")
+                .header("content-type", "application/json-patch+json; charset=utf-8")
+                .body(Body::from(patch.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(store.get_route("patched-route").unwrap().path, "/api/v2/orders");
+}
+
+#[tokio::test]
+async fn test_json_patch_producing_empty_path_rejected() {
+    let (app, store) = create_test_app().await;
+
+    let route = Route {
+        name: "patched-route".to_string(),
+        path: "/api/v1/orders".to_string(),
+        cluster_name: "orders-service".to_string(),
+        ..Default::default()
+    };
+    store.add_route(route).unwrap();
+
+    let patch = json!([{"op": "replace", "path": "/path", "value": ""}]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes/patched-route")
+                .method("PATCH")
+                .header("content-type", "application/json-patch+json")
+                .body(Body::from(patch.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // The stored route is left untouched
+    let unchanged = store.get_route("patched-route").unwrap();
+    assert_eq!(unchanged.path, "/api/v1/orders");
+}
+
+#[tokio::test]
+async fn test_effective_config_redacts_secret_and_shows_env_override() {
+    let mut config = create_test_config();
+    std::env::set_var("JWT_ISSUER", "issuer-from-env");
+    let overridden = AppConfig::apply_env_overrides(&mut config);
+    std::env::remove_var("JWT_ISSUER");
+    overridden.unwrap();
+
+    let (app, _store) = create_test_app_with_config(config).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/config")
+                .method("GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let authentication = &json["data"]["control_plane"]["authentication"];
+    assert_eq!(authentication["jwt_issuer"], "issuer-from-env");
+    assert_eq!(authentication["jwt_secret"], "[REDACTED]");
+    assert_eq!(json["data"]["control_plane"]["tls"]["key_path"], "[REDACTED]");
+    assert!(!body.windows(11).any(|w| w == b"test-secret"));
+}
+
+#[tokio::test]
+async fn test_create_cluster_with_policy_outside_available_policies_rejected() {
+    let mut config = create_test_config();
+    config.control_plane.load_balancing.available_policies =
+        vec!["ROUND_ROBIN".to_string(), "LEAST_REQUEST".to_string()];
+    let (app, store) = create_test_app_with_config(config).await;
+
+    // RING_HASH is a known policy, but this deployment does not offer it
+    let cluster_data = json!({
+        "name": "ring-hash-cluster",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}],
+        "lb_policy": "RING_HASH"
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("'RING_HASH' is not allowed"));
+    assert!(body_str.contains("ROUND_ROBIN, LEAST_REQUEST"));
+    assert!(store.get_cluster("ring-hash-cluster").is_err());
+}
+
+#[tokio::test]
+async fn test_create_cluster_dedupes_identical_endpoints() {
+    let (app, store) = create_test_app().await;
+
+    let cluster_data = json!({
+        "name": "dedupe-cluster",
+        "endpoints": [
+            {"host": "Backend.Internal", "port": 8080},
+            {"host": "backend.internal", "port": 8080},
+            {"host": "backend.internal", "port": 8081}
+        ]
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let cluster = store.get_cluster("dedupe-cluster").unwrap();
+    let endpoints: Vec<(String, u16)> = cluster.endpoints.iter().map(|e| (e.host.clone(), e.port)).collect();
+    assert_eq!(
+        endpoints,
+        vec![("backend.internal".to_string(), 8080), ("backend.internal".to_string(), 8081)]
+    );
+}
+
+#[tokio::test]
+async fn test_add_cluster_endpoint() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "endpoint-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/clusters/endpoint-service/endpoints")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"host": "127.0.0.2", "port": 8081}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let cluster = store.get_cluster("endpoint-service").unwrap();
+    let endpoints: Vec<(String, u16)> = cluster.endpoints.iter().map(|e| (e.host.clone(), e.port)).collect();
+    assert_eq!(
+        endpoints,
+        vec![("127.0.0.1".to_string(), 8080), ("127.0.0.2".to_string(), 8081)]
+    );
+
+    // Adding the same host:port again conflicts
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters/endpoint-service/endpoints")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"host": "127.0.0.2", "port": 8081}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(store.get_cluster("endpoint-service").unwrap().endpoints.len(), 2);
+}
+
+#[tokio::test]
+async fn test_remove_cluster_endpoint() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "endpoint-service".to_string(),
+            vec![
+                Endpoint::new("127.0.0.1".to_string(), 8080),
+                Endpoint::new("127.0.0.2".to_string(), 8081),
+            ],
+        ))
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters/endpoint-service/endpoints/127.0.0.1/8080")
+                .method("DELETE")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let cluster = store.get_cluster("endpoint-service").unwrap();
+    assert_eq!(cluster.endpoints.len(), 1);
+    assert_eq!(cluster.endpoints[0].host, "127.0.0.2");
+}
+
+#[tokio::test]
+async fn test_remove_last_cluster_endpoint_rejected() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "endpoint-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters/endpoint-service/endpoints/127.0.0.1/8080")
+                .method("DELETE")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(std::str::from_utf8(&body).unwrap().contains("last endpoint"));
+    assert_eq!(store.get_cluster("endpoint-service").unwrap().endpoints.len(), 1);
+}
+
+#[tokio::test]
+async fn test_admin_xds_dump_returns_encoded_clusters() {
+    let (app, store) = create_test_app().await;
+
+    store
+        .add_cluster(Cluster::new(
+            "dump-service".to_string(),
+            vec![Endpoint::new("127.0.0.1".to_string(), 8080)],
+        ))
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/xds/clusters")
+                .method("GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let resources = body["data"].as_array().unwrap();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0]["type_url"], "type.googleapis.com/envoy.config.cluster.v3.Cluster");
+    assert!(!resources[0]["value"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_admin_xds_dump_rejects_endpoints() {
+    let (app, _store) = create_test_app().await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/xds/endpoints")
+                .method("GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_create_http_filter_with_existing_name_rejected() {
+    let (app, store) = create_test_app().await;
+
+    let filter = HttpFilter::new(
+        "api-rate-limit".to_string(),
+        "rate_limit".to_string(),
+        json!({"requests_per_unit": 100, "unit": "minute"}),
+    );
+    store
+        .add_http_filter(filter, &HttpFiltersFeatureConfig::default().supported_filters)
+        .unwrap();
+
+    let duplicate = json!({
+        "name": "api-rate-limit",
+        "filter_type": "rate_limit",
+        "config": {"requests_per_unit": 5, "unit": "second"}
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/http-filters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(duplicate.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(std::str::from_utf8(&body).unwrap().contains("already exists"));
+
+    assert_eq!(store.list_http_filters().len(), 1);
+    assert_eq!(store.get_http_filter("api-rate-limit").unwrap().config["requests_per_unit"], 100);
+}
+
+#[tokio::test]
+async fn test_endpoint_gauge_tracks_cluster_endpoints() {
+    let (app, store) = create_test_app().await;
+
+    let cluster_data = json!({
+        "name": "gauge-service",
+        "endpoints": [
+            {"host": "127.0.0.1", "port": 8080},
+            {"host": "127.0.0.2", "port": 8080}
+        ]
+    });
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let gauge = store.endpoint_gauges()["gauge-service"];
+    assert_eq!(gauge.endpoints, 2);
+    assert_eq!(gauge.expected_healthy, None);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters/gauge-service")
+                .method("DELETE")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!store.endpoint_gauges().contains_key("gauge-service"));
+}
+
+#[tokio::test]
+async fn test_endpoint_gauges_count_healthy_endpoints_of_health_checked_cluster() {
+    let (app, _store) = create_test_app().await;
+
+    let cluster_data = json!({
+        "name": "checked-service",
+        "endpoints": [
+            {"host": "127.0.0.1", "port": 8080},
+            {"host": "127.0.0.2", "port": 8080, "health_status": "HEALTHY"},
+            {"host": "127.0.0.3", "port": 8080, "health_status": "UNHEALTHY"}
+        ],
+        "health_check": {"path": "/healthz", "interval_seconds": 10, "timeout_seconds": 2}
+    });
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster_data.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/endpoint-gauges")
+                .method("GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["checked-service"]["endpoints"], 3);
+    assert_eq!(body["data"]["checked-service"]["expected_healthy"], 2);
+}
+
+#[tokio::test]
+async fn test_create_cluster_with_misspelled_field_rejected() {
+    let mut config = create_test_config();
+    config.control_plane.server.reject_unknown_fields = true;
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster = json!({
+        "name": "typo-service",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}],
+        "lb_policyy": "LEAST_REQUEST"
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("lb_policyy"));
+    assert!(store.get_cluster("typo-service").is_err());
+}
+
+#[tokio::test]
+async fn test_create_cluster_with_misspelled_nested_field_rejected() {
+    let mut config = create_test_config();
+    config.control_plane.server.reject_unknown_fields = true;
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster = json!({
+        "name": "typo-service",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080, "wieght": 3}]
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(std::str::from_utf8(&body).unwrap().contains("endpoints[0].wieght"));
+    assert!(store.get_cluster("typo-service").is_err());
+}
+
+#[tokio::test]
+async fn test_create_cluster_with_partial_endpoint_weights_rejected() {
+    let (app, store) = create_test_app().await;
+
+    let cluster = json!({
+        "name": "weighted-service",
+        "endpoints": [
+            {"host": "10.0.0.1", "port": 8080, "weight": 3},
+            {"host": "10.0.0.2", "port": 8080}
+        ],
+        "lb_policy": "ROUND_ROBIN"
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("Endpoint weights must be set on all endpoints"));
+    assert!(body_str.contains("10.0.0.2:8080"));
+    assert!(store.get_cluster("weighted-service").is_err());
+}
+
+#[tokio::test]
+async fn test_create_locality_weighted_cluster_with_unweighted_endpoint_rejected() {
+    let (app, store) = create_test_app().await;
+
+    let cluster = json!({
+        "name": "zoned-service",
+        "endpoints": [
+            {"host": "10.0.0.1", "port": 8080, "locality": "us-east-1"},
+            {"host": "10.0.0.2", "port": 8080, "locality": "eu-west-1"}
+        ],
+        "locality_weighted_lb": true,
+        "locality_weights": {"us-east-1": 80}
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/clusters")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(cluster.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = std::str::from_utf8(&body).unwrap();
+    assert!(body_str.contains("Endpoint 2 needs a locality with a positive weight"));
+    assert!(store.get_cluster("zoned-service").is_err());
+}
+
+#[tokio::test]
+async fn test_create_route_disabling_unknown_filter_rejected() {
+    let (app, _store) = create_test_app().await;
+
+    let route = json!({
+        "name": "public-health",
+        "path": "/health",
+        "cluster_name": "health-service",
+        "disabled_filters": ["missing-auth"]
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/routes")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(route.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(std::str::from_utf8(&body).unwrap().contains("missing-auth"));
+}
+
+#[tokio::test]
+async fn test_read_only_mode_rejects_writes_but_serves_reads() {
+    let mut config = create_test_config();
+    config.control_plane.server.read_only = true;
+    let (app, store) = create_test_app_with_config(config).await;
+
+    let cluster = json!({
+        "name": "frozen-service",
+        "endpoints": [{"host": "127.0.0.1", "port": 8080}]
+    });
+    let create_cluster = || {
+        Request::builder()
+            .uri("/clusters")
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(cluster.to_string()))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(create_cluster()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(std::str::from_utf8(&body).unwrap().contains("read-only mode"));
+    assert!(store.get_cluster("frozen-service").is_err());
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/clusters").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Turning read-only mode off through the admin toggle lets writes through again
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/admin/read-only")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"read_only": false}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app.oneshot(create_cluster()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(store.get_cluster("frozen-service").is_ok());
+}
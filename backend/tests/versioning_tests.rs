@@ -4,8 +4,35 @@ use serial_test::serial;
 use std::sync::Arc;
 use std::time::Duration;
 
+use envoy_control_plane::config::{AppConfig, XdsKeepaliveConfig};
 use envoy_control_plane::storage::{models::*, ConfigStore};
-use envoy_control_plane::xds::simple_server::SimpleXdsServer;
+use envoy_control_plane::xds::simple_server::{
+    aggregated_discovery_service_client::AggregatedDiscoveryServiceClient, AggregatedDiscoveryServiceServer,
+    with_xds_keepalive, DiscoveryRequest, Node, SimpleXdsServer,
+};
+use envoy_types::pb::envoy::config::listener::v3::Listener;
+use prost::Message;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+
+/// Serve `xds_server` over ADS in-process on a free local port and connect a client to it
+async fn start_ads_server(
+    xds_server: &SimpleXdsServer,
+) -> (AggregatedDiscoveryServiceClient<Channel>, JoinHandle<Result<(), tonic::transport::Error>>) {
+    let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let service = AggregatedDiscoveryServiceServer::new(xds_server.clone());
+    let server = tokio::spawn(tonic::transport::Server::builder().add_service(service).serve(addr));
+
+    for _ in 0..50 {
+        if let Ok(client) = AggregatedDiscoveryServiceClient::connect(format!("http://{addr}")).await {
+            return (client, server);
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("ADS server should accept connections");
+}
 
 #[tokio::test]
 #[serial]
@@ -19,8 +46,10 @@ async fn test_version_increment_on_cluster_change() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     store.add_cluster(cluster.clone());
@@ -45,6 +74,7 @@ async fn test_version_increment_on_route_change() {
         cluster_name: "test-cluster".to_string(),
         prefix_rewrite: Some("/test".to_string()),
         http_methods: None,
+        ..Default::default()
     };
 
     store.add_route(route.clone());
@@ -69,8 +99,10 @@ async fn test_multiple_version_increments() {
             endpoints: vec![Endpoint {
                 host: "127.0.0.1".to_string(),
                 port: 8080 + i,
+                ..Default::default()
             }],
             lb_policy: None, // Use default
+            ..Default::default()
         };
 
         store.add_cluster(cluster);
@@ -114,8 +146,10 @@ async fn test_concurrent_version_increments() {
                     endpoints: vec![Endpoint {
                         host: "127.0.0.1".to_string(),
                         port: 8080 + i,
+                        ..Default::default()
                     }],
                     lb_policy: None, // Use default
+                    ..Default::default()
                 };
 
                 store.add_cluster(cluster);
@@ -145,8 +179,10 @@ async fn test_resource_deletion_with_version_increment() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     let cluster_name = cluster.name.clone();
@@ -176,8 +212,10 @@ async fn test_mixed_resource_operations() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     store.add_cluster(cluster);
@@ -190,6 +228,7 @@ async fn test_mixed_resource_operations() {
         cluster_name: "mixed-service".to_string(),
         prefix_rewrite: None,
         http_methods: None,
+        ..Default::default()
     };
 
     store.add_route(route);
@@ -258,8 +297,10 @@ async fn test_store_persistence_across_increments() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     store.add_cluster(cluster);
@@ -273,3 +314,447 @@ async fn test_store_persistence_across_increments() {
     assert_eq!(store.list_clusters().len(), 1);
     assert_eq!(store.list_clusters()[0].name, "persistent-service");
 }
+
+#[tokio::test]
+#[serial]
+async fn test_force_push_notifies_subscribed_streams() {
+    let store = ConfigStore::new();
+    let xds_server = SimpleXdsServer::new(store.clone());
+
+    // Each ADS stream holds one update subscription
+    let mut subscriber = xds_server.subscribe_updates();
+    let version_before = xds_server.current_version();
+
+    let notified = xds_server.force_push();
+
+    assert_eq!(notified, 1);
+    assert!(subscriber.try_recv().is_ok());
+    // A new version guarantees the stream pushes even if it was already up to date
+    assert!(xds_server.current_version() > version_before);
+}
+
+#[tokio::test]
+async fn test_unknown_type_url_lenient_returns_no_resources() {
+    let xds_server = SimpleXdsServer::new(ConfigStore::new());
+
+    let resources = xds_server
+        .resources_for_type("type.googleapis.com/envoy.config.endpoint.v3.ClusterLoadAssignment")
+        .expect("lenient mode answers unknown types");
+    assert!(resources.is_empty());
+}
+
+#[tokio::test]
+async fn test_unknown_type_url_strict_returns_unimplemented() {
+    let xds_server = SimpleXdsServer::new(ConfigStore::new()).with_strict_resource_types(true);
+
+    let status = xds_server
+        .resources_for_type("type.googleapis.com/envoy.config.endpoint.v3.ClusterLoadAssignment")
+        .expect_err("strict mode rejects unknown types");
+    assert_eq!(status.code(), tonic::Code::Unimplemented);
+
+    // Supported types are unaffected by strict mode
+    assert!(xds_server
+        .resources_for_type("type.googleapis.com/envoy.config.cluster.v3.Cluster")
+        .is_ok());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_connected_ads_client_listed() {
+    const CDS_TYPE: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    let xds_server = SimpleXdsServer::new(ConfigStore::new());
+
+    let (mut client, _server) = start_ads_server(&xds_server).await;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(DiscoveryRequest {
+        node: Some(Node {
+            id: "envoy-edge-1".to_string(),
+            cluster: "edge".to_string(),
+            ..Default::default()
+        }),
+        type_url: CDS_TYPE.to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut responses = client
+        .stream_aggregated_resources(ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    let response = responses.message().await.unwrap().expect("initial CDS response");
+
+    // ACK the response; the node is only sent on the first request
+    tx.send(DiscoveryRequest {
+        version_info: response.version_info.clone(),
+        nonce: response.nonce.clone(),
+        type_url: CDS_TYPE.to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut clients = Vec::new();
+    for _ in 0..50 {
+        clients = xds_server.connected_clients();
+        if clients.first().is_some_and(|c| c.acked_versions.contains_key(CDS_TYPE)) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].node_id, "envoy-edge-1");
+    assert_eq!(clients[0].node_cluster, "edge");
+    assert_eq!(clients[0].last_request_type.as_deref(), Some(CDS_TYPE));
+    assert_eq!(clients[0].acked_versions.get(CDS_TYPE), Some(&response.version_info));
+
+    // Closing the stream removes the client from the listing
+    drop(tx);
+    for _ in 0..50 {
+        if xds_server.connected_clients().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(xds_server.connected_clients().is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paused_xds_holds_updates_until_resume() {
+    const CDS_TYPE: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    let store = ConfigStore::new();
+    let xds_server = SimpleXdsServer::new(store.clone());
+
+    let (mut client, _server) = start_ads_server(&xds_server).await;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(DiscoveryRequest {
+        type_url: CDS_TYPE.to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut responses = client
+        .stream_aggregated_resources(ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    let initial = responses.message().await.unwrap().expect("initial CDS response");
+    assert!(initial.resources.is_empty());
+
+    let paused_version = xds_server.pause();
+    store.add_cluster(Cluster {
+        name: "paused-cluster".to_string(),
+        endpoints: vec![Endpoint {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    xds_server.increment_version();
+
+    // No push while paused
+    let held = tokio::time::timeout(Duration::from_millis(300), responses.message()).await;
+    assert!(held.is_err(), "paused server should not push a new version");
+    assert_eq!(paused_version.to_string(), initial.version_info);
+
+    xds_server.resume();
+    let pushed = tokio::time::timeout(Duration::from_secs(5), responses.message())
+        .await
+        .expect("update pushed after resume")
+        .unwrap()
+        .expect("CDS push");
+    assert_eq!(pushed.version_info, xds_server.current_version().to_string());
+    assert_eq!(pushed.resources.len(), 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_lds_request_returns_only_named_listeners() {
+    const LDS_TYPE: &str = "type.googleapis.com/envoy.config.listener.v3.Listener";
+    let xds_server = SimpleXdsServer::new(ConfigStore::new());
+    let main_listener = AppConfig::load()
+        .expect("config.yaml should load")
+        .envoy_generation
+        .bootstrap
+        .main_listener_name;
+
+    let (mut client, _server) = start_ads_server(&xds_server).await;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(DiscoveryRequest {
+        type_url: LDS_TYPE.to_string(),
+        resource_names: vec![main_listener.clone()],
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut responses = client
+        .stream_aggregated_resources(ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    let named = responses.message().await.unwrap().expect("LDS response for named listener");
+    assert_eq!(named.resources.len(), 1);
+    let listener = Listener::decode(&named.resources[0].value[..]).unwrap();
+    assert_eq!(listener.name, main_listener);
+
+    // Changing the subscription on the ACK answers with the new selection
+    tx.send(DiscoveryRequest {
+        version_info: named.version_info.clone(),
+        nonce: named.nonce.clone(),
+        type_url: LDS_TYPE.to_string(),
+        resource_names: vec!["no-such-listener".to_string()],
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let unknown = responses.message().await.unwrap().expect("LDS response for unknown listener");
+    assert!(unknown.resources.is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cds_request_returns_only_named_cluster() {
+    const CDS_TYPE: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    let store = ConfigStore::new();
+    for name in ["orders", "payments"] {
+        store.add_cluster(Cluster {
+            name: name.to_string(),
+            endpoints: vec![Endpoint {
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+    }
+    let xds_server = SimpleXdsServer::new(store);
+
+    let (mut client, _server) = start_ads_server(&xds_server).await;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(DiscoveryRequest {
+        type_url: CDS_TYPE.to_string(),
+        resource_names: vec!["payments".to_string()],
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut responses = client
+        .stream_aggregated_resources(ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    let response = responses.message().await.unwrap().expect("CDS response");
+
+    assert_eq!(response.resources.len(), 1);
+    let cluster =
+        envoy_types::pb::envoy::config::cluster::v3::Cluster::decode(&response.resources[0].value[..]).unwrap();
+    assert_eq!(cluster.name, "payments");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failed_conversion_serves_last_known_good_resources() {
+    const CDS_TYPE: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    let store = ConfigStore::new();
+    store
+        .add_cluster(Cluster {
+            name: "good-cluster".to_string(),
+            endpoints: vec![Endpoint {
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+    let xds_server = SimpleXdsServer::new(store.clone());
+
+    let (mut client, _server) = start_ads_server(&xds_server).await;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(DiscoveryRequest {
+        type_url: CDS_TYPE.to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut responses = client
+        .stream_aggregated_resources(ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    let initial = responses.message().await.unwrap().expect("initial CDS response");
+    assert_eq!(initial.resources.len(), 1);
+
+    // A cluster the store accepts but conversion rejects
+    store
+        .add_cluster(Cluster {
+            name: "broken-cluster".to_string(),
+            endpoints: vec![Endpoint {
+                host: "127.0.0.1".to_string(),
+                port: 8081,
+                ..Default::default()
+            }],
+            http_protocol: Some("http9".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    xds_server.increment_version();
+
+    let pushed = tokio::time::timeout(Duration::from_secs(5), responses.message())
+        .await
+        .expect("update pushed after the bad change")
+        .unwrap()
+        .expect("CDS push");
+    assert_eq!(pushed.resources, initial.resources);
+}
+
+#[tokio::test]
+async fn test_last_known_good_expires_after_grace_period() {
+    const CDS_TYPE: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    let store = ConfigStore::new();
+    let xds_server = SimpleXdsServer::new(store.clone()).with_last_known_good_grace(Some(Duration::ZERO));
+
+    store
+        .add_cluster(Cluster {
+            name: "good-cluster".to_string(),
+            endpoints: vec![Endpoint {
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(xds_server.resources_for_type(CDS_TYPE).unwrap().len(), 1);
+
+    store
+        .add_cluster(Cluster {
+            name: "broken-cluster".to_string(),
+            endpoints: vec![Endpoint {
+                host: "127.0.0.1".to_string(),
+                port: 8081,
+                ..Default::default()
+            }],
+            http_protocol: Some("http9".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    // With no grace period the failed conversion is not papered over
+    assert!(xds_server.resources_for_type(CDS_TYPE).unwrap().is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cds_withholds_clusters_tagged_for_other_nodes() {
+    const CDS_TYPE: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    let store = ConfigStore::new();
+    for (name, tag) in [("prod-orders", Some("prod-")), ("shared", None)] {
+        store
+            .add_cluster(Cluster {
+                name: name.to_string(),
+                endpoints: vec![Endpoint {
+                    host: "127.0.0.1".to_string(),
+                    port: 8080,
+                    ..Default::default()
+                }],
+                labels: tag.map(|tag| [("node_id".to_string(), tag.to_string())].into_iter().collect()),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+    let xds_server = SimpleXdsServer::new(store).with_node_match_mode("prefix".to_string());
+
+    let (mut client, _server) = start_ads_server(&xds_server).await;
+
+    for (node_id, expected) in [("staging-1", vec!["shared"]), ("prod-1", vec!["prod-orders", "shared"])] {
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tx.send(DiscoveryRequest {
+            node: Some(Node {
+                id: node_id.to_string(),
+                ..Default::default()
+            }),
+            type_url: CDS_TYPE.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let mut responses = client
+            .stream_aggregated_resources(ReceiverStream::new(rx))
+            .await
+            .unwrap()
+            .into_inner();
+        let response = responses.message().await.unwrap().expect("CDS response");
+
+        let mut names: Vec<String> = response
+            .resources
+            .iter()
+            .map(|resource| {
+                envoy_types::pb::envoy::config::cluster::v3::Cluster::decode(&resource.value[..]).unwrap().name
+            })
+            .collect();
+        names.sort();
+        assert_eq!(names, expected, "clusters served to {node_id}");
+    }
+}
+
+#[tokio::test]
+async fn test_xds_server_sends_keepalive_pings_at_configured_interval() {
+    let keepalive = XdsKeepaliveConfig {
+        interval_seconds: 2,
+        timeout_seconds: 1,
+    };
+    let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let service = AggregatedDiscoveryServiceServer::new(SimpleXdsServer::new(ConfigStore::new()));
+    let mut builder = with_xds_keepalive(tonic::transport::Server::builder(), &keepalive);
+    let server = tokio::spawn(builder.add_service(service).serve(addr));
+
+    let mut stream = None;
+    for _ in 0..50 {
+        if let Ok(connected) = tokio::net::TcpStream::connect(addr).await {
+            stream = Some(connected);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let mut stream = stream.expect("ADS server should accept connections");
+
+    // HTTP/2 connection preface followed by an empty SETTINGS frame, then stay idle
+    stream.write_all(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n").await.unwrap();
+    stream.write_all(&[0, 0, 0, 0x4, 0, 0, 0, 0, 0]).await.unwrap();
+
+    // Read frames until the server sends a PING (type 0x6) that is not an ACK
+    let ping = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let mut header = [0u8; 9];
+            stream.read_exact(&mut header).await.unwrap();
+            let length = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+            let mut payload = vec![0u8; length];
+            stream.read_exact(&mut payload).await.unwrap();
+            if header[3] == 0x6 && header[4] & 0x1 == 0 {
+                return payload;
+            }
+        }
+    })
+    .await
+    .expect("server should send an HTTP/2 keepalive PING on an idle connection");
+    assert_eq!(ping.len(), 8);
+
+    server.abort();
+}
@@ -23,8 +23,10 @@ async fn test_get_resources_by_type_clusters() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     store.add_cluster(cluster);
@@ -54,6 +56,7 @@ async fn test_get_resources_by_type_routes() {
         cluster_name: "test-cluster".to_string(),
         prefix_rewrite: Some("/test".to_string()),
         http_methods: None,
+        ..Default::default()
     };
 
     store.add_route(route);
@@ -109,8 +112,10 @@ async fn test_cluster_storage_and_retrieval() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     let cluster2 = Cluster {
@@ -118,8 +123,10 @@ async fn test_cluster_storage_and_retrieval() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8081,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     store.add_cluster(cluster1);
@@ -149,6 +156,7 @@ async fn test_route_storage_and_retrieval() {
         cluster_name: "user-service".to_string(),
         prefix_rewrite: Some("/users".to_string()),
         http_methods: None,
+        ..Default::default()
     };
 
     let route2 = Route {
@@ -157,6 +165,7 @@ async fn test_route_storage_and_retrieval() {
         cluster_name: "order-service".to_string(),
         prefix_rewrite: None,
         http_methods: None,
+        ..Default::default()
     };
 
     store.add_route(route1);
@@ -185,8 +194,10 @@ async fn test_resource_deletion() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     let route = Route {
@@ -195,6 +206,7 @@ async fn test_resource_deletion() {
         cluster_name: "test-cluster".to_string(),
         prefix_rewrite: None,
         http_methods: None,
+        ..Default::default()
     };
 
     store.add_cluster(cluster.clone());
@@ -227,8 +239,10 @@ async fn test_concurrent_resource_access() {
                     endpoints: vec![Endpoint {
                         host: "127.0.0.1".to_string(),
                         port: 8080 + i,
+                        ..Default::default()
                     }],
                     lb_policy: None, // Use default
+                    ..Default::default()
                 };
 
                 store.add_cluster(cluster);
@@ -255,8 +269,10 @@ async fn test_discovery_response_creation() {
         endpoints: vec![Endpoint {
             host: "127.0.0.1".to_string(),
             port: 8080,
+            ..Default::default()
         }],
         lb_policy: None, // Use default
+        ..Default::default()
     };
 
     store.add_cluster(cluster);